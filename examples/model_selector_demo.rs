@@ -235,6 +235,9 @@ impl ModelSelectorDemo {
                 ComboboxEvent::InputChanged(value) => {
                     println!("Grouped combobox input: {}", value);
                 }
+                ComboboxEvent::Create(value) => {
+                    println!("Grouped combobox created: {}", value);
+                }
             }
         });
 
@@ -260,6 +263,9 @@ impl ModelSelectorDemo {
                 ComboboxEvent::InputChanged(value) => {
                     println!("Fixed width combobox input: {}", value);
                 }
+                ComboboxEvent::Create(value) => {
+                    println!("Fixed width combobox created: {}", value);
+                }
             }
         });
 
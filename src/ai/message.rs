@@ -6,6 +6,8 @@ use gpui::*;
 use gpui::prelude::FluentBuilder;
 
 use crate::{Icon, IconName, IconSize};
+use crate::theme::*;
+use crate::utils::mix;
 use super::{Message, MessageRole, MessageContent, MessageId};
 
 /// Events emitted by MessageBubble
@@ -60,6 +62,7 @@ impl Default for MessageBubbleConfig {
 }
 
 /// Message bubble component for displaying AI conversation messages
+#[derive(IntoElement)]
 pub struct MessageBubble {
     /// The message to display
     message: Message,
@@ -134,10 +137,10 @@ impl MessageBubble {
     }
 }
 
-impl IntoElement for MessageBubble {
-    type Element = Div;
-    
-    fn into_element(self) -> Self::Element {
+impl RenderOnce for MessageBubble {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
+        let colors = &theme.colors;
         let is_user = self.message.role == MessageRole::User;
         
         div()
@@ -150,21 +153,21 @@ impl IntoElement for MessageBubble {
                 div()
                     .flex()
                     .flex_row()
-                    .gap_3()
+                    .gap(px(theme.spacing.md))
                     .max_w(relative(self.config.max_width_percent / 100.0))
                     .when(is_user, |this| this.flex_row_reverse())
                     .when(self.config.show_avatar, |this| {
-                        this.child(self.render_avatar())
+                        this.child(self.render_avatar(colors))
                     })
                     .child(
                         div()
                             .flex()
                             .flex_col()
-                            .gap_1()
+                            .gap(px(theme.spacing.xxs))
                             .flex_1()
-                            .child(self.render_message_content())
+                            .child(self.render_message_content(theme))
                             .when(self.config.show_timestamp, |this| {
-                                this.child(self.render_timestamp())
+                                this.child(self.render_timestamp(colors))
                             })
                     )
             )
@@ -172,13 +175,14 @@ impl IntoElement for MessageBubble {
 }
 
 impl MessageBubble {
-    fn render_avatar(&self) -> Div {
-        let (bg_color, icon, icon_color) = match self.message.role {
-            MessageRole::User => (rgb(0x696FC7), IconName::User, rgb(0xFFFFFF)),
-            MessageRole::Assistant => (rgb(0x4CAF50), IconName::Settings, rgb(0xFFFFFF)), // Using Settings as placeholder for Bot
-            MessageRole::System => (rgb(0xFF9800), IconName::Settings, rgb(0xFFFFFF)),
-            MessageRole::Tool => (rgb(0x9C27B0), IconName::Settings, rgb(0xFFFFFF)), // Using Settings as placeholder for Tool
+    fn render_avatar(&self, colors: &ColorPalette) -> Div {
+        let (bg_color, icon) = match self.message.role {
+            MessageRole::User => (colors.primary, IconName::User),
+            MessageRole::Assistant => (colors.success, IconName::Settings), // Using Settings as placeholder for Bot
+            MessageRole::System => (colors.warning, IconName::Settings),
+            MessageRole::Tool => (colors.info, IconName::Settings), // Using Settings as placeholder for Tool
         };
+        let icon_color = colors.text_on_primary;
         
         div()
             .size(px(32.))
@@ -194,88 +198,90 @@ impl MessageBubble {
             )
     }
     
-    fn render_message_content(&self) -> Div {
-        let container_style = self.get_container_style();
+    fn render_message_content(&self, theme: &Theme) -> Div {
+        let container_style = self.get_container_style(theme);
         
         container_style
             .children(
                 self.message.content.iter().map(|content| {
-                    self.render_content_block(content)
+                    self.render_content_block(content, theme)
                 })
             )
             .when(self.message.is_streaming, |this| {
-                this.child(self.render_typing_indicator())
+                this.child(self.render_typing_indicator(theme))
             })
     }
     
-    fn get_container_style(&self) -> Div {
+    fn get_container_style(&self, theme: &Theme) -> Div {
+        let colors = &theme.colors;
         let is_user = self.message.role == MessageRole::User;
         let is_system = self.message.role == MessageRole::System;
         
         let base_style = div()
             .flex()
             .flex_col()
-            .gap_2();
+            .gap(px(theme.spacing.sm));
+        let system_bg = mix(colors.warning, colors.background, 0.12);
             
         match self.config.variant {
             MessageBubbleVariant::Standard => {
                 let (bg_color, text_color) = if is_user {
-                    (rgb(0x696FC7), rgb(0xFFFFFF))
+                    (colors.primary, colors.text_on_primary)
                 } else if is_system {
-                    (rgb(0xFFF4E6), rgb(0x8B6914))
+                    (system_bg, colors.text)
                 } else {
-                    (rgb(0xF0F0F0), rgb(0x1A1A1A))
+                    (colors.background_hover, colors.text)
                 };
                 
                 base_style
                     .bg(bg_color)
                     .text_color(text_color)
-                    .px_4()
-                    .py_3()
+                    .px(px(theme.spacing.lg))
+                    .py(px(theme.spacing.md))
                     .rounded_xl()
             }
             MessageBubbleVariant::Minimal => {
                 base_style
-                    .text_color(rgb(0x1A1A1A))
-                    .px_2()
-                    .py_1()
+                    .text_color(colors.text)
+                    .px(px(theme.spacing.sm))
+                    .py(px(theme.spacing.xxs))
             }
             MessageBubbleVariant::Card => {
                 base_style
-                    .bg(rgb(0xFFFFFF))
-                    .text_color(rgb(0x1A1A1A))
-                    .px_4()
-                    .py_3()
+                    .bg(colors.background)
+                    .text_color(colors.text)
+                    .px(px(theme.spacing.lg))
+                    .py(px(theme.spacing.md))
                     .rounded_xl()
                     .border_1()
-                    .border_color(rgb(0xE0E0E0))
+                    .border_color(colors.border)
                     .shadow_sm()
             }
             MessageBubbleVariant::Compact => {
                 let bg_color = if is_user {
-                    rgb(0x696FC7)
+                    colors.primary
                 } else if is_system {
-                    rgb(0xFFF4E6)
+                    system_bg
                 } else {
-                    rgb(0xF5F5F5)
+                    colors.background_secondary
                 };
                 
                 base_style
                     .bg(bg_color)
-                    .text_color(if is_user { rgb(0xFFFFFF) } else { rgb(0x1A1A1A) })
-                    .px_3()
-                    .py_2()
+                    .text_color(if is_user { colors.text_on_primary } else { colors.text })
+                    .px(px(theme.spacing.md))
+                    .py(px(theme.spacing.sm))
                     .rounded_lg()
             }
         }
     }
     
-    fn render_content_block(&self, content: &MessageContent) -> Div {
+    fn render_content_block(&self, content: &MessageContent, theme: &Theme) -> Div {
         match content {
             MessageContent::Text(text) => self.render_text(text),
-            MessageContent::Code { language, code } => self.render_code_block(language, code),
-            MessageContent::Error(text) => self.render_error(text),
-            MessageContent::Thinking(text) => self.render_thinking(text),
+            MessageContent::Code { language, code } => self.render_code_block(language, code, theme),
+            MessageContent::Error(text) => self.render_error(text, theme),
+            MessageContent::Thinking(text) => self.render_thinking(text, theme),
             _ => self.render_text(&format!("{:?}", content)), // Fallback for other content types
         }
     }
@@ -286,12 +292,15 @@ impl MessageBubble {
             .child(text.to_string())
     }
     
-    fn render_code_block(&self, language: &str, code: &str) -> Div {
+    fn render_code_block(&self, language: &str, code: &str, theme: &Theme) -> Div {
+        let colors = &theme.colors;
         div()
             .flex()
             .flex_col()
             .w_full()
-            .bg(rgb(0x1E1E1E))
+            .bg(colors.background_secondary)
+            .border_1()
+            .border_color(colors.border)
             .rounded_lg()
             .overflow_hidden()
             .child(
@@ -301,109 +310,113 @@ impl MessageBubble {
                     .flex_row()
                     .items_center()
                     .justify_between()
-                    .px_3()
-                    .py_2()
-                    .bg(rgb(0x2D2D2D))
+                    .px(px(theme.spacing.md))
+                    .py(px(theme.spacing.sm))
+                    .bg(colors.background_hover)
                     .child(
                         div()
                             .text_xs()
-                            .text_color(rgb(0xCCCCCC))
+                            .text_color(colors.text_secondary)
                             .child(language.to_string())
                     )
             )
             .child(
                 // Code content
                 div()
-                    .px_3()
-                    .py_3()
+                    .px(px(theme.spacing.md))
+                    .py(px(theme.spacing.md))
                     .text_sm()
-                    .text_color(rgb(0xCCCCCC))
+                    .font_family(theme.typography.mono_font_family.clone())
+                    .text_color(colors.text)
                     .child(code.to_string())
             )
     }
     
-    fn render_thinking(&self, text: &str) -> Div {
+    fn render_thinking(&self, text: &str, theme: &Theme) -> Div {
+        let colors = &theme.colors;
         div()
             .flex()
             .flex_row()
             .items_start()
-            .gap_2()
-            .px_3()
-            .py_2()
-            .bg(rgb(0xF8F9FA))
+            .gap(px(theme.spacing.sm))
+            .px(px(theme.spacing.md))
+            .py(px(theme.spacing.sm))
+            .bg(colors.background_secondary)
             .rounded_lg()
             .border_l_4()
-            .border_color(rgb(0x6C757D))
+            .border_color(colors.text_secondary)
             .child(
                 Icon::new(IconName::Settings) // Placeholder for Brain icon
                     .size(IconSize::Small)
-                    .color(rgb(0x6C757D))
+                    .color(colors.text_secondary)
             )
             .child(
                 div()
                     .text_sm()
-                    .text_color(rgb(0x6C757D))
+                    .text_color(colors.text_secondary)
                     .child(text.to_string())
             )
     }
     
-    fn render_error(&self, text: &str) -> Div {
+    fn render_error(&self, text: &str, theme: &Theme) -> Div {
+        let colors = &theme.colors;
         div()
             .flex()
             .flex_row()
             .items_start()
-            .gap_2()
-            .px_3()
-            .py_2()
-            .bg(rgb(0xFFF5F5))
+            .gap(px(theme.spacing.sm))
+            .px(px(theme.spacing.md))
+            .py(px(theme.spacing.sm))
+            .bg(mix(colors.error, colors.background, 0.08))
             .rounded_lg()
             .border_l_4()
-            .border_color(rgb(0xF56565))
+            .border_color(colors.error)
             .child(
                 Icon::new(IconName::AlertTriangle)
                     .size(IconSize::Small)
-                    .color(rgb(0xF56565))
+                    .color(colors.error)
             )
             .child(
                 div()
                     .text_sm()
-                    .text_color(rgb(0xC53030))
+                    .text_color(colors.error)
                     .child(text.to_string())
             )
     }
     
-    fn render_typing_indicator(&self) -> Div {
+    fn render_typing_indicator(&self, theme: &Theme) -> Div {
+        let colors = &theme.colors;
         div()
             .flex()
             .flex_row()
             .items_center()
-            .gap_1()
+            .gap(px(theme.spacing.xxs))
             .child(
                 div()
                     .size(px(4.))
-                    .bg(rgb(0x999999))
+                    .bg(colors.text_disabled)
                     .rounded_full()
             )
             .child(
                 div()
                     .size(px(4.))
-                    .bg(rgb(0x999999))
+                    .bg(colors.text_disabled)
                     .rounded_full()
             )
             .child(
                 div()
                     .size(px(4.))
-                    .bg(rgb(0x999999))
+                    .bg(colors.text_disabled)
                     .rounded_full()
             )
     }
     
-    fn render_timestamp(&self) -> Div {
+    fn render_timestamp(&self, colors: &ColorPalette) -> Div {
         let time_str = self.message.timestamp.format("%I:%M %p").to_string();
         
         div()
             .text_xs()
-            .text_color(rgb(0x999999))
+            .text_color(colors.text_disabled)
             .child(time_str)
     }
 }
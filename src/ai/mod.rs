@@ -298,6 +298,21 @@ pub enum ModelCapability {
     WebSearch,
 }
 
+impl ModelCapability {
+    /// Short display name, e.g. "Functions"
+    pub fn label(&self) -> &'static str {
+        match self {
+            ModelCapability::TextGeneration => "Text",
+            ModelCapability::CodeGeneration => "Code",
+            ModelCapability::ImageGeneration => "Image Gen",
+            ModelCapability::ImageAnalysis => "Image Analysis",
+            ModelCapability::FunctionCalling => "Functions",
+            ModelCapability::DocumentAnalysis => "Documents",
+            ModelCapability::WebSearch => "Web Search",
+        }
+    }
+}
+
 /// Pricing information for a model
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PricingInfo {
@@ -6,6 +6,7 @@ use gpui::*;
 use gpui::prelude::FluentBuilder;
 
 use crate::{Combobox, ComboboxEvent, SelectOption, SelectOptionGroup, Icon, IconName, IconSize, ComponentSize, DropdownDirection, DropdownWidth, DropdownAlignment};
+use crate::theme::*;
 use super::{ModelInfo, ModelCapability, ProviderInfo};
 
 /// Events emitted by ModelSelector
@@ -599,8 +600,12 @@ impl ModelSelector {
                 for group in &filtered_groups {
                     for model in &group.models {
                         if &model.id == recently_used_id && !seen_ids.contains(&model.id) {
-                            let label = Self::format_model_label_static(model, config);
-                            recently_used_options.push(SelectOption::new(&model.id, &label));
+                            // No provider group label here, so name the provider
+                            let mut option = Self::model_option(model, config);
+                            if !config.compact && option.description.is_none() {
+                                option = option.description(&model.provider);
+                            }
+                            recently_used_options.push(option);
                             seen_ids.insert(model.id.clone());
                             break;
                         }
@@ -633,10 +638,7 @@ impl ModelSelector {
 
                 let options = unique_models
                     .iter()
-                    .map(|model| {
-                        let label = Self::format_model_label_static(model, config);
-                        SelectOption::new(&model.id, &label)
-                    })
+                    .map(|model| Self::model_option(model, config))
                     .collect();
 
                 SelectOptionGroup::new(&group.provider, options)
@@ -701,16 +703,61 @@ impl ModelSelector {
 
         all_models
             .iter()
-            .map(|model| {
-                let label = Self::format_model_label_static(model, config);
-                SelectOption::new(&model.id, &label)
-            })
+            .map(|model| Self::model_option(model, config))
             .collect()
     }
 
-    fn format_model_label_static(model: &ModelInfo, _config: &ModelSelectorConfig) -> String {
-        // 只显示模型名称，不显示描述
-        model.name.clone()
+    /// The option for a model: its name, with the description, an icon
+    /// and badge for its capabilities and the input price when configured
+    fn model_option(model: &ModelInfo, config: &ModelSelectorConfig) -> SelectOption {
+        let mut option = SelectOption::new(&model.id, &model.name);
+        if config.compact {
+            return option;
+        }
+        if config.show_descriptions {
+            if let Some(description) = &model.description {
+                option = option.description(description);
+            }
+        }
+
+        let mut badge = Vec::new();
+        if config.show_capabilities {
+            if let Some(icon) = Self::capability_icon(&model.capabilities) {
+                option = option.icon(icon);
+            }
+            // Every model generates text, so only the others are listed
+            badge.extend(
+                model
+                    .capabilities
+                    .iter()
+                    .filter(|capability| **capability != ModelCapability::TextGeneration)
+                    .map(|capability| capability.label().to_string()),
+            );
+        }
+        if config.show_pricing {
+            if let Some(pricing) = &model.pricing {
+                badge.push(format!("${:.3}/1K", pricing.input_price));
+            }
+        }
+        if !badge.is_empty() {
+            option = option.badge(badge.join(" · "));
+        }
+        option
+    }
+
+    /// Icon for the most specific of a model's capabilities
+    fn capability_icon(capabilities: &[ModelCapability]) -> Option<IconName> {
+        [
+            (ModelCapability::ImageGeneration, IconName::Image),
+            (ModelCapability::ImageAnalysis, IconName::Image),
+            (ModelCapability::WebSearch, IconName::Search),
+            (ModelCapability::DocumentAnalysis, IconName::Attachment),
+            (ModelCapability::FunctionCalling, IconName::Settings),
+            (ModelCapability::CodeGeneration, IconName::Task),
+        ]
+        .into_iter()
+        .find(|(capability, _)| capabilities.contains(capability))
+        .map(|(_, icon)| icon)
     }
 
     /// Filter models to show only popular ones and recently used
@@ -768,32 +815,6 @@ impl ModelSelector {
         // the builder pattern methods.
     }
 
-    #[allow(dead_code)]
-    fn format_model_label(&self, model: &ModelInfo) -> String {
-        if self.config.compact {
-            model.name.clone()
-        } else {
-            let mut label = model.name.clone();
-            
-            if self.config.show_descriptions {
-                if let Some(description) = &model.description {
-                    label.push_str(&format!(" - {}", description));
-                }
-            }
-            
-            if self.config.show_pricing {
-                if let Some(pricing) = &model.pricing {
-                    label.push_str(&format!(
-                        " (${:.3}/1K tokens)",
-                        pricing.input_price
-                    ));
-                }
-            }
-            
-            label
-        }
-    }
-
     fn handle_combobox_event(&mut self, event: &ComboboxEvent, cx: &mut Context<Self>) {
         match event {
             ComboboxEvent::Changed(value) => {
@@ -811,16 +832,20 @@ impl ModelSelector {
                 // 用户输入变化时，搜索功能会自动过滤选项
                 // 这里可以添加额外的处理逻辑如果需要
             }
+            ComboboxEvent::Create(_value) => {
+                // 模型列表不可创建新值；创建时随后的 Changed 事件会更新选择
+            }
         }
     }
 }
 
 impl Render for ModelSelector {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme().clone();
         let container = div()
             .flex()
             .flex_col()
-            .gap_2();
+            .gap(px(theme.spacing.sm));
 
         let container = if let Some(max_width) = self.config.max_width {
             container.max_w(px(max_width))
@@ -830,17 +855,17 @@ impl Render for ModelSelector {
 
         container
             .when(!self.config.compact, |this| {
-                this.child(self.render_header())
+                this.child(self.render_header(&theme))
             })
             .child(self.render_selector(window, cx))
             .when(self.selected_model.is_some() && !self.config.compact, |this| {
-                this.child(self.render_model_details())
+                this.child(self.render_model_details(&theme))
             })
     }
 }
 
 impl ModelSelector {
-    fn render_header(&self) -> impl IntoElement {
+    fn render_header(&self, theme: &Theme) -> impl IntoElement {
         div()
             .flex()
             .flex_row()
@@ -849,25 +874,26 @@ impl ModelSelector {
             .child(
                 div()
                     .text_sm()
-                    .text_color(rgb(0x374151))
+                    .text_color(theme.colors.text)
                     .child("AI Model")
             )
             .when(self.config.show_pricing, |this| {
                 this.child(
                     div()
                         .text_xs()
-                        .text_color(rgb(0x6B7280))
+                        .text_color(theme.colors.text_secondary)
                         .child(format!("{} models available", self.filtered_models().len()))
                 )
             })
     }
 
-    fn render_selector(&self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_selector(&self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme();
         div()
             .flex()
             .flex_row()
             .items_center()
-            .gap_2()
+            .gap(px(theme.spacing.sm))
             .child(
                 div()
                     .flex_1()
@@ -878,25 +904,26 @@ impl ModelSelector {
     }
 
     #[allow(dead_code)]
-    fn render_refresh_button(&self) -> impl IntoElement {
+    fn render_refresh_button(&self, theme: &Theme) -> impl IntoElement {
+        let hover_bg = theme.colors.background_hover;
         div()
-            .p_1()
+            .p(px(theme.spacing.xxs))
             .rounded_md()
             .cursor_pointer()
-            .hover(|this| this.bg(rgb(0xF3F4F6)))
+            .hover(move |this| this.bg(hover_bg))
             .child(
                 Icon::new(IconName::Settings) // Using Settings as placeholder for refresh
                     .size(IconSize::Small)
-                    .color(rgb(0x6B7280))
+                    .color(theme.colors.text_secondary)
             )
     }
 
     #[allow(dead_code)]
-    fn render_capability_filter(&self) -> impl IntoElement {
+    fn render_capability_filter(&self, theme: &Theme) -> impl IntoElement {
         div()
             .flex()
             .flex_row()
-            .gap_1()
+            .gap(px(theme.spacing.xxs))
             .flex_wrap()
             .children(
                 [
@@ -907,59 +934,52 @@ impl ModelSelector {
                     ModelCapability::FunctionCalling,
                 ]
                 .iter()
-                .map(|capability| self.render_capability_badge(capability))
+                .map(|capability| self.render_capability_badge(capability, theme))
             )
     }
 
     #[allow(dead_code)]
-    fn render_capability_badge(&self, capability: &ModelCapability) -> impl IntoElement {
+    fn render_capability_badge(&self, capability: &ModelCapability, theme: &Theme) -> impl IntoElement {
         let is_active = self.config.filter_capability.as_ref() == Some(capability);
-        let label = match capability {
-            ModelCapability::TextGeneration => "Text",
-            ModelCapability::CodeGeneration => "Code",
-            ModelCapability::ImageGeneration => "Image Gen",
-            ModelCapability::ImageAnalysis => "Image Analysis",
-            ModelCapability::FunctionCalling => "Functions",
-            ModelCapability::DocumentAnalysis => "Documents",
-            ModelCapability::WebSearch => "Web Search",
-        };
+        let hover_bg = theme.colors.border;
+        let label = capability.label();
 
         div()
-            .px_2()
-            .py_1()
+            .px(px(theme.spacing.sm))
+            .py(px(theme.spacing.xxs))
             .text_xs()
             .rounded_md()
             .cursor_pointer()
             .when(is_active, |this| {
-                this.bg(rgb(0x3B82F6))
-                    .text_color(rgb(0xFFFFFF))
+                this.bg(theme.colors.primary)
+                    .text_color(theme.colors.text_on_primary)
             })
             .when(!is_active, |this| {
-                this.bg(rgb(0xF3F4F6))
-                    .text_color(rgb(0x6B7280))
-                    .hover(|this| this.bg(rgb(0xE5E7EB)))
+                this.bg(theme.colors.background_hover)
+                    .text_color(theme.colors.text_secondary)
+                    .hover(move |this| this.bg(hover_bg))
             })
             .child(label)
     }
 
-    fn render_model_details(&self) -> impl IntoElement {
+    fn render_model_details(&self, theme: &Theme) -> impl IntoElement {
         if let Some(model_id) = &self.selected_model {
             if let Some(model) = self.get_model(model_id) {
                 let mut details = div()
                     .flex()
                     .flex_col()
-                    .gap_2()
-                    .p_3();
+                    .gap(px(theme.spacing.sm))
+                    .p(px(theme.spacing.md));
 
                 // Apply styling based on clean_style setting
                 if self.config.clean_style {
                     details = details.bg(rgba(0x00000000)); // Transparent background
                 } else {
                     details = details
-                        .bg(rgb(0xF9FAFB))
+                        .bg(theme.colors.background_secondary)
                         .rounded_lg()
                         .border_1()
-                        .border_color(rgb(0xE5E7EB));
+                        .border_color(theme.colors.border);
                 }
 
                 return details
@@ -972,13 +992,13 @@ impl ModelSelector {
                             .child(
                                 div()
                                     .text_sm()
-                                    .text_color(rgb(0x111827))
+                                    .text_color(theme.colors.text)
                                     .child(model.name.clone())
                             )
                             .child(
                                 div()
                                     .text_xs()
-                                    .text_color(rgb(0x6B7280))
+                                    .text_color(theme.colors.text_secondary)
                                     .child(model.provider.clone())
                             )
                     )
@@ -986,7 +1006,7 @@ impl ModelSelector {
                         this.child(
                             div()
                                 .text_xs()
-                                .text_color(rgb(0x6B7280))
+                                .text_color(theme.colors.text_secondary)
                                 .child(model.description.as_ref().unwrap().clone())
                         )
                     })
@@ -994,7 +1014,7 @@ impl ModelSelector {
                         this.child(
                             div()
                                 .text_xs()
-                                .text_color(rgb(0x6B7280))
+                                .text_color(theme.colors.text_secondary)
                                 .child(format!(
                                     "Context: {} tokens",
                                     model.context_length.unwrap()
@@ -1007,9 +1027,9 @@ impl ModelSelector {
                             div()
                                 .flex()
                                 .flex_row()
-                                .gap_4()
+                                .gap(px(theme.spacing.lg))
                                 .text_xs()
-                                .text_color(rgb(0x6B7280))
+                                .text_color(theme.colors.text_secondary)
                                 .child(format!("Input: ${:.3}/1K", pricing.input_price))
                                 .child(format!("Output: ${:.3}/1K", pricing.output_price))
                         )
@@ -1019,16 +1039,16 @@ impl ModelSelector {
                             div()
                                 .flex()
                                 .flex_row()
-                                .gap_1()
+                                .gap(px(theme.spacing.xxs))
                                 .flex_wrap()
                                 .children(
                                     model.capabilities.iter().map(|cap| {
                                         div()
-                                            .px_2()
-                                            .py_1()
+                                            .px(px(theme.spacing.sm))
+                                            .py(px(theme.spacing.xxs))
                                             .text_xs()
-                                            .bg(rgb(0xDCFCE7))
-                                            .text_color(rgb(0x166534))
+                                            .bg(crate::utils::mix(theme.colors.success, theme.colors.background, 0.15))
+                                            .text_color(theme.colors.success)
                                             .rounded_md()
                                             .child(format!("{:?}", cap))
                                     })
@@ -2,6 +2,7 @@ use gpui::*;
 use gpui::prelude::FluentBuilder;
 
 use crate::{TextArea, TextAreaEvent, Icon, IconName, IconSize, ComponentSize, DropdownDirection};
+use crate::theme::*;
 use super::super::{Attachment, AttachmentType, ModelInfo, ModelSelector, ModelSelectorEvent};
 
 /// Events emitted by PromptInput
@@ -68,7 +69,7 @@ impl Default for PromptInputConfig {
             show_attachments: true,
             show_send_button: true,
             show_toolbar: true,
-            background_color: Some(rgb(0xF5F5F5)), // Default gray background
+            background_color: None, // Follows the theme's secondary background
             enable_multiline: true,
             enable_file_upload: true,
             enable_image_upload: true,
@@ -134,10 +135,8 @@ impl PromptInput {
                 .max_height(config.max_height)
                 .no_border(); // Remove border from TextArea
             
-            // Set background color if provided
-            if let Some(bg_color) = config.background_color {
-                textarea = textarea.bg_color(bg_color);
-            }
+            // Set background color if provided, otherwise let the themed container show through
+            textarea = textarea.bg_color(config.background_color.unwrap_or(rgba(0x00000000)));
             
             textarea
         });
@@ -461,76 +460,73 @@ impl Render for PromptInput {
             self.model_selector = Some(selector);
         }
         
+        let theme = cx.theme().clone();
         let container_style = match self.config.variant {
-            PromptInputVariant::Default => self.render_default_container(),
-            PromptInputVariant::Compact => self.render_compact_container(),
-            PromptInputVariant::Floating => self.render_floating_container(),
-            PromptInputVariant::Embedded => self.render_embedded_container(),
-            PromptInputVariant::Minimal => self.render_minimal_container(),
+            PromptInputVariant::Default => self.render_default_container(&theme),
+            PromptInputVariant::Compact => self.render_compact_container(&theme),
+            PromptInputVariant::Floating => self.render_floating_container(&theme),
+            PromptInputVariant::Embedded => self.render_embedded_container(&theme),
+            PromptInputVariant::Minimal => self.render_minimal_container(&theme),
         };
         
         container_style
             .child(self.render_input_area(cx))
             .when(self.config.show_toolbar, |this| {
-                this.child(self.render_toolbar(cx))
+                this.child(self.render_toolbar(&theme, cx))
             })
             .when(!self.attachments.is_empty(), |this| {
-                this.child(self.render_attachments())
+                this.child(self.render_attachments(&theme))
             })
     }
 }
 
 impl PromptInput {
-    fn render_default_container(&self) -> Div {
-        let bg_color = self.config.background_color.unwrap_or(rgb(0xFFFFFF));
+    fn render_default_container(&self, theme: &Theme) -> Div {
+        let bg_color = self.config.background_color.unwrap_or(theme.colors.background_secondary);
         div()
             .flex()
             .flex_col()
             .w_full()
             .bg(bg_color)
             .border_1()
-            .border_color(if self.is_focused { rgb(0x696FC7) } else { rgb(0xE0E0E0) })
+            .border_color(if self.is_focused { theme.colors.border_focus } else { theme.colors.border })
             .rounded(px(self.config.border_radius))
-            .p_3()
+            .p(px(theme.spacing.md))
             .shadow_sm()
     }
     
-    fn render_compact_container(&self) -> Div {
-        let bg_color = self.config.background_color.unwrap_or(rgb(0xF8F8F8));
+    fn render_compact_container(&self, theme: &Theme) -> Div {
+        let bg_color = self.config.background_color.unwrap_or(theme.colors.background_secondary);
         div()
             .flex()
             .flex_col()
             .w_full()
             .bg(bg_color)
             .border_1()
-            .border_color(rgb(0xE0E0E0))
+            .border_color(theme.colors.border)
             .rounded_lg()
-            .p_2()
+            .p(px(theme.spacing.sm))
     }
     
-    fn render_floating_container(&self) -> Div {
-        let bg_color = self.config.background_color.unwrap_or(rgb(0xFFFFFF));
+    fn render_floating_container(&self, theme: &Theme) -> Div {
+        let bg_color = self.config.background_color.unwrap_or(theme.colors.background);
         div()
             .flex()
             .flex_col()
             .w_full()
             .bg(bg_color)
             .border_1()
-            .border_color(rgb(0xE0E0E0))
+            .border_color(theme.colors.border)
             .rounded_xl()
-            .p_4()
+            .p(px(theme.spacing.lg))
             .shadow_lg()
     }
     
-    fn render_embedded_container(&self) -> Div {
-        let bg_color = self.config.background_color.unwrap_or(rgb(0xF8F8F8));
-        // Border color darker than gray background
-        // If background is gray (around 0xF5F5F5), use darker gray border
-        let border_color = if bg_color.r < 0.95 && bg_color.g < 0.95 && bg_color.b < 0.95 {
-            rgb(0xD0D0D0) // Darker gray for gray backgrounds
-        } else {
-            rgb(0xE0E0E0) // Standard light gray for white backgrounds
-        };
+    fn render_embedded_container(&self, theme: &Theme) -> Div {
+        let bg_color = self.config.background_color.unwrap_or(theme.colors.background_secondary);
+        // Border slightly stronger than the regular border so it stays visible on the
+        // secondary background
+        let border_color = crate::utils::mix(theme.colors.border, theme.colors.text, 0.85);
         div()
             .flex()
             .flex_col()
@@ -539,18 +535,18 @@ impl PromptInput {
             .border_1()
             .border_color(border_color)
             .rounded_lg()
-            .p_2()
+            .p(px(theme.spacing.sm))
             // Don't set overflow_hidden to allow dropdown to overflow
     }
     
-    fn render_minimal_container(&self) -> Div {
-        let bg_color = self.config.background_color.unwrap_or(rgb(0xFFFFFF));
+    fn render_minimal_container(&self, theme: &Theme) -> Div {
+        let bg_color = self.config.background_color.unwrap_or(theme.colors.background);
         div()
             .flex()
             .flex_col()
             .w_full()
             .bg(bg_color)
-            .p_1()
+            .p(px(theme.spacing.xxs))
     }
     
     fn render_input_area(&self, _cx: &mut Context<Self>) -> impl IntoElement {
@@ -569,29 +565,29 @@ impl PromptInput {
         container.child(self.textarea.clone())
     }
     
-    fn render_toolbar(&self, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_toolbar(&self, theme: &Theme, cx: &mut Context<Self>) -> impl IntoElement {
         div()
             .flex()
             .flex_row()
             .w_full()
             .items_center()
             .justify_between()
-            .pt_2()
+            .pt(px(theme.spacing.sm))
             // Don't set overflow_hidden to allow dropdown to overflow
-            .child(self.render_left_actions(cx))
-            .child(self.render_right_actions(cx))
+            .child(self.render_left_actions(theme, cx))
+            .child(self.render_right_actions(theme, cx))
     }
     
-    fn render_left_actions(&self, _cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_left_actions(&self, theme: &Theme, _cx: &mut Context<Self>) -> impl IntoElement {
         div()
             .flex()
             .flex_row()
-            .gap_1()
+            .gap(px(theme.spacing.xxs))
             .when(self.config.enable_file_upload, |this| {
-                this.child(self.render_simple_button(IconName::Attachment))
+                this.child(self.render_simple_button(IconName::Attachment, theme))
             })
             .when(self.config.enable_image_upload, |this| {
-                this.child(self.render_simple_button(IconName::Image))
+                this.child(self.render_simple_button(IconName::Image, theme))
             })
             // Settings button removed - not needed for prompt input
             // .when(self.config.enable_code_input, |this| {
@@ -599,11 +595,11 @@ impl PromptInput {
             // })
     }
     
-    fn render_right_actions(&self, cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_right_actions(&self, theme: &Theme, cx: &mut Context<Self>) -> impl IntoElement {
         div()
             .flex()
             .flex_row()
-            .gap_2()
+            .gap(px(theme.spacing.sm))
             .items_center()
             .justify_end() // Right align all items
             // Don't set overflow_hidden to allow dropdown to overflow
@@ -625,66 +621,69 @@ impl PromptInput {
                 }
             })
             .when(self.config.show_send_button, |this| {
-                this.child(self.render_send_button(cx))
+                this.child(self.render_send_button(theme, cx))
             })
     }
     
-    fn render_simple_button(&self, icon: IconName) -> impl IntoElement {
+    fn render_simple_button(&self, icon: IconName, theme: &Theme) -> impl IntoElement {
+        let hover_bg = theme.colors.background_hover;
         div()
-            .p_1()
+            .p(px(theme.spacing.xxs))
             .rounded_md()
             .cursor_pointer()
-            .hover(|this| this.bg(rgb(0xF0F0F0)))
+            .hover(move |this| this.bg(hover_bg))
             .child(
                 Icon::new(icon)
                     .size(IconSize::Small)
-                    .color(rgb(0x666666))
+                    .color(theme.colors.text_secondary)
             )
     }
     
-    fn render_send_button(&self, _cx: &mut Context<Self>) -> impl IntoElement {
+    fn render_send_button(&self, theme: &Theme, _cx: &mut Context<Self>) -> impl IntoElement {
         let is_empty = self.current_text.trim().is_empty();
+        let hover_bg = theme.colors.primary_hover;
 
         div()
             .size(px(32.))
             .flex()
             .items_center()
             .justify_center()
-            .bg(if is_empty { rgb(0xCCCCCC) } else { rgb(0x696FC7) })
+            .bg(if is_empty { theme.colors.text_disabled } else { theme.colors.primary })
             .rounded_lg()
             .cursor_pointer()
             .when(!is_empty, |this| {
-                this.hover(|this| this.bg(rgb(0xA7AAE1)))
+                this.hover(move |this| this.bg(hover_bg))
             })
             .child(
                 Icon::new(IconName::Send)
                     .size(IconSize::Small)
-                    .color(rgb(0xFFFFFF))
+                    .color(theme.colors.text_on_primary)
             )
     }
     
-    fn render_attachments(&self) -> impl IntoElement {
+    fn render_attachments(&self, theme: &Theme) -> impl IntoElement {
         div()
             .flex()
             .flex_row()
-            .gap_2()
-            .pt_2()
+            .gap(px(theme.spacing.sm))
+            .pt(px(theme.spacing.sm))
             .children(
                 self.attachments.iter().map(|attachment| {
-                    self.render_attachment_chip(attachment)
+                    self.render_attachment_chip(attachment, theme)
                 })
             )
     }
     
-    fn render_attachment_chip(&self, attachment: &Attachment) -> impl IntoElement {
+    fn render_attachment_chip(&self, attachment: &Attachment, theme: &Theme) -> impl IntoElement {
+        let close_hover_bg = theme.colors.border;
         div()
             .flex()
             .flex_row()
             .items_center()
-            .gap_1()
-            .px_2()
-            .py_1()
-            .bg(rgb(0xF0F0F0))
+            .gap(px(theme.spacing.xxs))
+            .px(px(theme.spacing.sm))
+            .py(px(theme.spacing.xxs))
+            .bg(theme.colors.background_hover)
             .rounded_md()
             .child(
                 Icon::new(match attachment.attachment_type {
@@ -694,24 +693,24 @@ impl PromptInput {
                     _ => IconName::Attachment,
                 })
                 .size(IconSize::XSmall)
-                .color(rgb(0x666666))
+                .color(theme.colors.text_secondary)
             )
             .child(
                 div()
                     .text_xs()
-                    .text_color(rgb(0x666666))
+                    .text_color(theme.colors.text_secondary)
                     .child(attachment.name.clone())
             )
             .child(
                 div()
-                    .p_1()
+                    .p(px(theme.spacing.xxs))
                     .rounded_sm()
                     .cursor_pointer()
-                    .hover(|this| this.bg(rgb(0xE0E0E0)))
+                    .hover(move |this| this.bg(close_hover_bg))
                     .child(
                         Icon::new(IconName::Close)
                             .size(IconSize::XSmall)
-                            .color(rgb(0x999999))
+                            .color(theme.colors.text_disabled)
                     )
             )
     }
@@ -149,14 +149,14 @@ impl Button {
             }
             ButtonVariant::Outline => {
                 if is_pressed || is_hovered {
-                    rgba(0x00000005)
+                    crate::utils::with_alpha(theme.colors.text, 0.02)
                 } else {
                     rgba(0x00000000)  // Transparent, not black
                 }
             }
             ButtonVariant::Text => {
                 if is_pressed || is_hovered {
-                    rgba(0x00000008)
+                    crate::utils::with_alpha(theme.colors.text, 0.03)
                 } else {
                     rgba(0x00000000)  // Transparent, not black
                 }
@@ -171,36 +171,17 @@ impl Button {
         }
         
         match self.variant {
-            ButtonVariant::Primary => {
-                // Primary buttons always use white text for better contrast
-                rgb(0xFFFFFF)
-            }
-            ButtonVariant::Danger => {
-                // Danger buttons always use white text
-                rgb(0xFFFFFF)
+            ButtonVariant::Primary | ButtonVariant::Danger => {
+                // Filled buttons use the palette's on-primary foreground
+                theme.colors.text_on_primary
             }
-            ButtonVariant::Secondary => {
-                // Secondary buttons check background brightness
-                let bg = self.background_color(theme, false, false);
-                if self.is_dark_background(bg) {
-                    rgb(0xFFFFFF)
-                } else {
-                    theme.colors.text
-                }
-            }
-            ButtonVariant::Outline | ButtonVariant::Text => {
+            ButtonVariant::Secondary | ButtonVariant::Outline | ButtonVariant::Text => {
+                // These sit on the palette background, so the regular text color reads well
                 theme.colors.text
             }
         }
     }
     
-    /// Check if a color is dark (needs light text)
-    fn is_dark_background(&self, color: Rgba) -> bool {
-        // Using perceived luminance formula
-        let luminance = 0.299 * color.r + 0.587 * color.g + 0.114 * color.b;
-        luminance < 0.6  // Increased threshold for better readability
-    }
-    
     /// Get the border color for the current variant
     fn border_color(&self, theme: &Theme) -> Option<Rgba> {
         if self.disabled {
@@ -214,24 +195,14 @@ impl Button {
     }
 
     /// Get shadow style for the button
-    fn shadow_style(&self) -> Option<BoxShadow> {
+    fn shadow_style(&self, theme: &Theme) -> Option<Vec<BoxShadow>> {
         if self.disabled {
             return None;
         }
 
         match self.variant {
-            ButtonVariant::Primary => Some(BoxShadow {
-                color: rgba(0x00000018).into(),
-                offset: point(px(0.), px(1.)),
-                blur_radius: px(2.),
-                spread_radius: px(0.),
-            }),
-            ButtonVariant::Secondary => Some(BoxShadow {
-                color: rgba(0x0000000A).into(),
-                offset: point(px(0.), px(1.)),
-                blur_radius: px(2.),
-                spread_radius: px(0.),
-            }),
+            ButtonVariant::Primary => Some(theme.shadow_md()),
+            ButtonVariant::Secondary => Some(theme.shadow_sm()),
             _ => None,
         }
     }
@@ -243,13 +214,13 @@ impl Button {
 
 impl Render for Button {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let theme = Theme::default();
+        let theme = cx.theme().clone();
         let label = self.label.clone();
         let disabled = self.disabled;
         let loading = self.loading;
         let size = self.size;
-        let (padding_y, padding_x) = size.padding();
-        let min_width = size.min_width();
+        let (padding_y, padding_x) = theme.padding(size);
+        let min_width = theme.min_width(size);
         let text_color = self.text_color(&theme);
         let bg_color = self.background_color(&theme, false, false);
         
@@ -260,13 +231,13 @@ impl Render for Button {
             .flex_shrink_0()
             .items_center()
             .justify_center()
-            .gap_2()
+            .gap(px(theme.spacing.sm))
             .py(padding_y)
             .px(padding_x)
             .min_w(min_width)  // Apply standard minimum width
-            .rounded(px(BorderRadius::LG))
-            .text_size(size.font_size())
-            .font_weight(FontWeight::MEDIUM)
+            .rounded(px(theme.radius.lg))
+            .text_size(theme.font_size(size))
+            .font_weight(FontWeight(theme.typography.font_weight_medium))
             .bg(bg_color)
             .when(self.full_width, |this| this.w_full())
             .when(!disabled && !loading, |this| {
@@ -278,8 +249,8 @@ impl Render for Button {
             .when_some(self.border_color(&theme), |this, color| {
                 this.border_1().border_color(color)
             })
-            .when_some(self.shadow_style(), |this, shadow| {
-                this.shadow(vec![shadow])
+            .when_some(self.shadow_style(&theme), |this, shadow| {
+                this.shadow(shadow)
             })
             .on_mouse_down(MouseButton::Left, cx.listener(|this, _event: &MouseDownEvent, _window, cx| {
                 if !this.disabled && !this.loading {
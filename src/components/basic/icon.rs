@@ -1,5 +1,7 @@
 use gpui::*;
 
+use crate::theme::*;
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum IconName {
     ArrowLeft,
@@ -28,6 +30,7 @@ pub enum IconName {
     Success,
     AlertCircle,
     AlertTriangle,
+    Loader,
     UnfoldMore,
     Send,
     Attachment,
@@ -67,6 +70,7 @@ impl IconName {
             Self::Success => "icons/success.svg",
             Self::AlertCircle => "icons/alert-circle.svg",
             Self::AlertTriangle => "icons/alert-triangle.svg",
+            Self::Loader => "icons/loader.svg",
             Self::UnfoldMore => "icons/unfold-more.svg",
             Self::Send => "icons/send.svg",
             Self::Attachment => "icons/attachment.svg",
@@ -124,6 +128,7 @@ pub struct Icon {
     background: IconBackground,
     background_color: Option<Rgba>,
     border_radius: Option<Pixels>,
+    transformation: Option<Transformation>,
 }
 
 impl Icon {
@@ -135,6 +140,7 @@ impl Icon {
             background: IconBackground::None,
             background_color: None,
             border_radius: None,
+            transformation: None,
         }
     }
 
@@ -198,25 +204,36 @@ impl Icon {
         self.border_radius = Some(radius);
         self
     }
+
+    /// Transform the icon, e.g. rotate it in an animation
+    pub fn transform(mut self, transformation: Transformation) -> Self {
+        self.transformation = Some(transformation);
+        self
+    }
 }
 
 impl RenderOnce for Icon {
-    fn render(self, _window: &mut Window, _cx: &mut App) -> impl IntoElement {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme();
         let size = self.size.px();
-        let color = self.color.unwrap_or(rgb(0x333333));
+        let color = self.color.unwrap_or(theme.colors.text);
+        let default_bg_color = theme.colors.background_secondary;
         let path = self.name.path();
 
-        let icon = svg()
+        let mut icon = svg()
             .path(path)
             .size(size)
             .text_color(color)
             .flex_none();
+        if let Some(transformation) = self.transformation {
+            icon = icon.with_transformation(transformation);
+        }
 
         // Wrap in background if specified
         match self.background {
             IconBackground::None => icon.into_any_element(),
             IconBackground::Square => {
-                let bg_color = self.background_color.unwrap_or(rgb(0xF3F4F6));
+                let bg_color = self.background_color.unwrap_or(default_bg_color);
                 let padding = size * 0.25; // 25% padding
                 let total_size = size + padding * 2.0;
 
@@ -234,7 +251,7 @@ impl RenderOnce for Icon {
                 container.child(icon).into_any_element()
             }
             IconBackground::Rectangle { width, height } => {
-                let bg_color = self.background_color.unwrap_or(rgb(0xF3F4F6));
+                let bg_color = self.background_color.unwrap_or(default_bg_color);
 
                 let mut container = div()
                     .flex()
@@ -0,0 +1,350 @@
+// Options loaded on demand from an async source, e.g. a backend, for Select and Combobox
+
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gpui::*;
+
+use crate::components::basic::icon::{Icon, IconName};
+use crate::components::form::select::SelectOption;
+use crate::theme::Theme;
+
+/// Typing pauses shorter than this don't start a new request
+const LOAD_DEBOUNCE: Duration = Duration::from_millis(250);
+
+/// One page of options returned by an async option source
+#[derive(Clone, Debug, Default)]
+pub struct OptionsPage {
+    /// Options of this page
+    pub options: Vec<SelectOption>,
+    /// Whether more pages follow, shown as a "Load more" row
+    pub has_more: bool,
+}
+
+impl OptionsPage {
+    /// Create the last page with these options
+    pub fn new(options: Vec<SelectOption>) -> Self {
+        Self {
+            options,
+            has_more: false,
+        }
+    }
+
+    /// Set whether more pages follow
+    pub fn has_more(mut self, has_more: bool) -> Self {
+        self.has_more = has_more;
+        self
+    }
+}
+
+/// Outcome of loading a page: `Err` carries the message shown in the dropdown
+pub type OptionsResult = Result<OptionsPage, String>;
+
+type LoadFn = Rc<dyn Fn(&str, usize) -> Pin<Box<dyn Future<Output = OptionsResult>>>>;
+
+/// Options of a Select or Combobox loaded by an async source.
+///
+/// The source is called with the search query and a zero-based page
+/// number. Requests for a new query are debounced, and a new request
+/// cancels the running one so stale results never replace newer ones.
+pub(crate) struct AsyncOptions {
+    load: LoadFn,
+    /// Query of the loaded options, `None` before the first load
+    query: Option<String>,
+    /// Number of pages loaded for `query`
+    pages: usize,
+    options: Vec<SelectOption>,
+    has_more: bool,
+    loading: bool,
+    error: Option<String>,
+    /// Incremented on every request so stale results can be ignored
+    generation: usize,
+    /// Incremented whenever the options or the status row change
+    revision: usize,
+    _task: Option<Task<()>>,
+}
+
+impl AsyncOptions {
+    pub fn new<F, Fut>(load: F) -> Self
+    where
+        F: Fn(&str, usize) -> Fut + 'static,
+        Fut: Future<Output = OptionsResult> + 'static,
+    {
+        Self {
+            load: Rc::new(move |query, page| Box::pin(load(query, page))),
+            query: None,
+            pages: 0,
+            options: Vec::new(),
+            has_more: false,
+            loading: false,
+            error: None,
+            generation: 0,
+            revision: 0,
+            _task: None,
+        }
+    }
+
+    /// The options loaded so far
+    pub fn options(&self) -> &[SelectOption] {
+        &self.options
+    }
+
+    /// Changes whenever the options or the status row change, so menus
+    /// built from them know when to rebuild
+    pub fn revision(&self) -> usize {
+        self.revision
+    }
+
+    /// Whether a status row is shown, see `render_status`
+    pub fn has_status(&self) -> bool {
+        self.loading || self.error.is_some() || self.has_more
+    }
+
+    /// Whether options were loaded or requested for `query`
+    pub fn has_query(&self, query: &str) -> bool {
+        self.query.as_deref() == Some(query)
+    }
+
+    /// Load the first page for `query`, replacing the options when it
+    /// arrives. With `debounce` the request waits for typing to pause.
+    ///
+    /// `field` locates these options in the component, for async results.
+    pub fn load<E: 'static>(
+        &mut self,
+        query: &str,
+        debounce: bool,
+        field: fn(&mut E) -> &mut AsyncOptions,
+        cx: &mut Context<E>,
+    ) {
+        self.start_query(query);
+        self.fetch(debounce, field, cx);
+    }
+
+    /// Forget the loaded pages; the next fetch loads the first page for `query`
+    fn start_query(&mut self, query: &str) {
+        self.query = Some(query.to_string());
+        self.pages = 0;
+    }
+
+    /// Load the next page, appending its options
+    pub fn load_more<E: 'static>(&mut self, field: fn(&mut E) -> &mut AsyncOptions, cx: &mut Context<E>) {
+        if self.has_more && !self.loading {
+            self.fetch(false, field, cx);
+        }
+    }
+
+    /// Request the next page, after `pages`, for `query`
+    fn fetch<E: 'static>(
+        &mut self,
+        debounce: bool,
+        field: fn(&mut E) -> &mut AsyncOptions,
+        cx: &mut Context<E>,
+    ) {
+        let (generation, page) = self.begin_fetch();
+        let query = self.query.clone().unwrap_or_default();
+        let load = self.load.clone();
+        // Replacing the task drops, and so cancels, the running request,
+        // including a pending debounce timer
+        self._task = Some(cx.spawn(async move |this, cx| {
+            if debounce {
+                cx.background_executor().timer(LOAD_DEBOUNCE).await;
+            }
+            let result = load(&query, page).await;
+            _ = this.update(cx, |this, cx| {
+                if field(this).finish_fetch(generation, page, result) {
+                    cx.notify();
+                }
+            });
+        }));
+    }
+
+    /// Mark a request for the next page as running; returns its generation
+    /// and the page it loads
+    fn begin_fetch(&mut self) -> (usize, usize) {
+        self.generation += 1;
+        self.revision += 1;
+        self.loading = true;
+        self.error = None;
+        (self.generation, self.pages)
+    }
+
+    /// Apply the result of a request: the first page replaces the options,
+    /// later pages append to them. Returns `false` for a stale result,
+    /// from a request started before the latest one.
+    fn finish_fetch(&mut self, generation: usize, page: usize, result: OptionsResult) -> bool {
+        if self.generation != generation {
+            return false;
+        }
+        self.revision += 1;
+        self.loading = false;
+        match result {
+            Ok(loaded) => {
+                if page == 0 {
+                    self.options = loaded.options;
+                } else {
+                    self.options.extend(loaded.options);
+                }
+                self.pages = page + 1;
+                self.has_more = loaded.has_more;
+            }
+            Err(message) => self.error = Some(message),
+        }
+        true
+    }
+
+    /// Rows shown below the options: a loading spinner, an error with a
+    /// retry button, or a "Load more" button
+    pub fn render_status<E: 'static>(
+        &self,
+        field: fn(&mut E) -> &mut AsyncOptions,
+        theme: &Theme,
+        cx: &Context<E>,
+    ) -> Option<AnyElement> {
+        let row = || {
+            div()
+                .flex()
+                .items_center()
+                .gap(px(theme.spacing.sm))
+                .px(px(theme.spacing.md))
+                .py(px(theme.spacing.sm))
+                .text_size(px(13.))
+        };
+
+        if self.loading {
+            return Some(
+                row()
+                    .text_color(theme.colors.text_secondary)
+                    .child(
+                        Icon::new(IconName::Loader)
+                            .xsmall()
+                            .color(theme.colors.text_secondary)
+                            .with_animation(
+                                "options-loading",
+                                Animation::new(Duration::from_secs(1)).repeat(),
+                                |icon, delta| icon.transform(Transformation::rotate(percentage(delta))),
+                            ),
+                    )
+                    .child("Loading...")
+                    .into_any_element(),
+            );
+        }
+
+        if let Some(error) = &self.error {
+            return Some(
+                row()
+                    .text_color(theme.colors.error)
+                    .child(Icon::new(IconName::AlertCircle).xsmall().color(theme.colors.error))
+                    .child(div().flex_1().min_w_0().child(error.clone()))
+                    .child(
+                        div()
+                            .id("options-retry")
+                            .flex_none()
+                            .cursor(CursorStyle::PointingHand)
+                            .text_color(theme.colors.primary)
+                            .child("Retry")
+                            .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event: &MouseDownEvent, _window, cx| {
+                                field(this).fetch(false, field, cx);
+                                cx.notify();
+                            })),
+                    )
+                    .into_any_element(),
+            );
+        }
+
+        self.has_more.then(|| {
+            row()
+                .id("options-load-more")
+                .justify_center()
+                .rounded(px(theme.radius.sm))
+                .cursor(CursorStyle::PointingHand)
+                .text_color(theme.colors.primary)
+                .hover(|style| style.bg(theme.colors.background_hover))
+                .child("Load more")
+                .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event: &MouseDownEvent, _window, cx| {
+                    field(this).load_more(field, cx);
+                    cx.notify();
+                }))
+                .into_any_element()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AsyncOptions, OptionsPage};
+    use crate::components::form::select::SelectOption;
+
+    fn source() -> AsyncOptions {
+        AsyncOptions::new(|_query, _page| async { Ok(OptionsPage::default()) })
+    }
+
+    fn page(values: &[&str]) -> OptionsPage {
+        OptionsPage::new(values.iter().map(|value| SelectOption::new(*value, *value)).collect())
+    }
+
+    fn values(options: &AsyncOptions) -> Vec<&str> {
+        options.options().iter().map(|option| option.value.as_str()).collect()
+    }
+
+    #[test]
+    fn later_pages_append_and_a_new_query_replaces() {
+        let mut options = source();
+        options.start_query("a");
+        let (generation, page_ix) = options.begin_fetch();
+        assert_eq!(page_ix, 0);
+        assert!(options.finish_fetch(generation, page_ix, Ok(page(&["a1", "a2"]).has_more(true))));
+        assert!(options.has_status(), "more pages show a load more row");
+
+        let (generation, page_ix) = options.begin_fetch();
+        assert_eq!(page_ix, 1);
+        assert!(options.finish_fetch(generation, page_ix, Ok(page(&["a3"]))));
+        assert_eq!(values(&options), ["a1", "a2", "a3"]);
+        assert!(!options.has_status());
+
+        options.start_query("b");
+        let (generation, page_ix) = options.begin_fetch();
+        assert!(options.finish_fetch(generation, page_ix, Ok(page(&["b1"]))));
+        assert_eq!(values(&options), ["b1"]);
+        assert!(options.has_query("b"));
+    }
+
+    #[test]
+    fn stale_results_are_ignored() {
+        let mut options = source();
+        options.start_query("a");
+        let (stale, _) = options.begin_fetch();
+        options.start_query("ab");
+        let (latest, _) = options.begin_fetch();
+
+        let revision = options.revision();
+        assert!(!options.finish_fetch(stale, 0, Ok(page(&["stale"]))));
+        assert!(options.options().is_empty());
+        assert_eq!(options.revision(), revision, "stale results don't change the menu");
+        assert!(options.has_status(), "the latest request is still loading");
+
+        assert!(options.finish_fetch(latest, 0, Ok(page(&["ab"]))));
+        assert_eq!(values(&options), ["ab"]);
+    }
+
+    #[test]
+    fn errors_keep_the_options_and_retry_the_same_page() {
+        let mut options = source();
+        options.start_query("");
+        let (generation, page_ix) = options.begin_fetch();
+        options.finish_fetch(generation, page_ix, Ok(page(&["1"]).has_more(true)));
+
+        let (generation, page_ix) = options.begin_fetch();
+        options.finish_fetch(generation, page_ix, Err("offline".into()));
+        assert_eq!(options.error.as_deref(), Some("offline"));
+        assert_eq!(values(&options), ["1"]);
+
+        // Retrying loads the failed page again and clears the error
+        let (generation, page_ix) = options.begin_fetch();
+        assert_eq!(page_ix, 1);
+        assert!(options.error.is_none());
+        options.finish_fetch(generation, page_ix, Ok(page(&["2"])));
+        assert_eq!(values(&options), ["1", "2"]);
+    }
+}
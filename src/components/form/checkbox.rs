@@ -1,7 +1,9 @@
 use gpui::prelude::FluentBuilder;
 use gpui::*;
+use serde_json::Value;
 use crate::theme::*;
 use crate::components::basic::icon::{Icon, IconName};
+use crate::components::form::form_container::FormControl;
 
 // ============================================================================
 // Events
@@ -113,24 +115,39 @@ impl Checkbox {
     }
 }
 
+impl FormControl for Checkbox {
+    fn form_value(&self) -> Value {
+        Value::Bool(self.checked)
+    }
+
+    fn set_form_value(&mut self, value: &Value, cx: &mut Context<Self>) {
+        let checked = value.as_bool().unwrap_or(false);
+        if checked != self.checked {
+            self.checked = checked;
+            cx.emit(CheckboxEvent::Changed(checked));
+            cx.notify();
+        }
+    }
+}
+
 // ============================================================================
 // Render
 // ============================================================================
 
 impl Render for Checkbox {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let theme = Theme::default();
+        let theme = cx.theme().clone();
         let checked = self.checked;
         let disabled = self.disabled;
         let label = self.label.clone();
         let box_size = self.box_size();
-        let text_size = self.size.font_size();
+        let text_size = theme.font_size(self.size);
 
         div()
             .id("checkbox")
             .flex()
             .items_center()
-            .gap_2()
+            .gap(px(theme.spacing.sm))
             .cursor(if disabled { CursorStyle::Arrow } else { CursorStyle::PointingHand })
             .when(disabled, |this| {
                 this.opacity(0.64)
@@ -145,7 +162,7 @@ impl Render for Checkbox {
                     .items_center()
                     .justify_center()
                     .size(box_size)
-                    .rounded(px(4.))
+                    .rounded(px(theme.radius.sm))
                     .border_1()
                     .border_color(if checked {
                         theme.colors.primary
@@ -155,13 +172,13 @@ impl Render for Checkbox {
                     .bg(if checked {
                         theme.colors.primary
                     } else {
-                        rgb(0xFFFFFF)
+                        theme.colors.background
                     })
                     .when(checked, |this| {
                         this.child(
                             Icon::new(IconName::Check)
                                 .size(crate::components::basic::icon::IconSize::Small)
-                                .color(rgb(0xFFFFFF))
+                                .color(theme.colors.text_on_primary)
                         )
                     })
             )
@@ -1,7 +1,10 @@
 use gpui::prelude::FluentBuilder;
 use gpui::*;
+use serde_json::Value;
 use crate::theme::*;
 use crate::components::basic::icon::{Icon, IconName};
+use crate::components::form::validation::{render_error_message, validation_methods, ValidateOn, Validation, ValidationState};
+use crate::components::form::form_container::{value_to_strings, FormControl};
 
 // ============================================================================
 // Events
@@ -74,6 +77,8 @@ pub struct CheckboxGroup {
     direction: CheckboxGroupDirection,
     /// Custom text color
     custom_text_color: Option<Rgba>,
+    /// Validation rules for the selected values, and the current error
+    validation: Validation<[String]>,
 }
 
 /// Layout direction for checkbox group
@@ -86,6 +91,12 @@ pub enum CheckboxGroupDirection {
     Horizontal,
 }
 
+validation_methods! {
+    /// The rule receives the checked values. Checking or unchecking counts
+    /// as a change and a blur.
+    CheckboxGroup: [String] = |this| this.selected_values.clone()
+}
+
 impl CheckboxGroup {
     /// Create a new CheckboxGroup
     pub fn new(_cx: &mut Context<Self>) -> Self {
@@ -96,6 +107,7 @@ impl CheckboxGroup {
             size: ComponentSize::Medium,
             direction: CheckboxGroupDirection::Vertical,
             custom_text_color: None,
+            validation: Validation::default(),
         }
     }
 
@@ -159,11 +171,37 @@ impl CheckboxGroup {
             self.selected_values.push(value);
         }
 
+        self.validate_for(ValidateOn::Blur, cx);
         cx.emit(CheckboxGroupEvent::Changed(self.selected_values.clone()));
         cx.notify();
     }
 }
 
+impl FormControl for CheckboxGroup {
+    fn form_value(&self) -> Value {
+        Value::from(self.selected_values.clone())
+    }
+
+    fn set_form_value(&mut self, value: &Value, cx: &mut Context<Self>) {
+        self.selected_values = value_to_strings(value);
+        cx.emit(CheckboxGroupEvent::Changed(self.selected_values.clone()));
+        cx.notify();
+    }
+
+    fn validate_form(&mut self, cx: &mut Context<Self>) -> ValidationState {
+        self.run_validation(cx);
+        self.validation.state().clone()
+    }
+
+    fn form_validation_state(&self) -> ValidationState {
+        self.validation.state().clone()
+    }
+
+    fn clear_form_error(&mut self, cx: &mut Context<Self>) {
+        self.set_error(None, cx);
+    }
+}
+
 // ============================================================================
 // Render
 // ============================================================================
@@ -183,19 +221,25 @@ impl CheckboxGroup {
 
 impl Render for CheckboxGroup {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let theme = Theme::default();
+        let theme = cx.theme().clone();
         let disabled = self.disabled;
         let direction = self.direction;
         let options = self.options.clone();
         let selected_values = self.selected_values.clone();
         let box_size = self.box_size();
-        let text_size = self.size.font_size();
+        let text_size = theme.font_size(self.size);
         let text_color = self.custom_text_color.unwrap_or(theme.colors.text);
+        let error = self.validation.error().map(str::to_string);
+        let box_border = if error.is_some() {
+            theme.colors.border_error
+        } else {
+            theme.colors.border
+        };
 
-        div()
+        let group = div()
             .id("checkbox-group")
             .flex()
-            .gap_3()
+            .gap(px(theme.spacing.md))
             .when(matches!(direction, CheckboxGroupDirection::Vertical), |this| {
                 this.flex_col()
             })
@@ -228,7 +272,7 @@ impl Render for CheckboxGroup {
                                 .id(("checkbox-group-item", idx))
                                 .flex()
                                 .items_center()
-                                .gap_2()
+                                .gap(px(theme.spacing.sm))
                                 .cursor(if disabled { CursorStyle::Arrow } else { CursorStyle::PointingHand })
                                 .when(disabled, |this| {
                                     this.opacity(0.64)
@@ -240,23 +284,23 @@ impl Render for CheckboxGroup {
                                         .items_center()
                                         .justify_center()
                                         .size(box_size)
-                                        .rounded(px(4.))
+                                        .rounded(px(theme.radius.sm))
                                         .border_1()
                                         .border_color(if is_selected {
                                             theme.colors.primary
                                         } else {
-                                            theme.colors.border
+                                            box_border
                                         })
                                         .bg(if is_selected {
                                             theme.colors.primary
                                         } else {
-                                            rgb(0xFFFFFF)
+                                            theme.colors.background
                                         })
                                         .when(is_selected, |this| {
                                             this.child(
                                                 Icon::new(IconName::Check)
                                                     .size(crate::components::basic::icon::IconSize::Small)
-                                                    .color(rgb(0xFFFFFF))
+                                                    .color(theme.colors.text_on_primary)
                                             )
                                         })
                                 )
@@ -268,7 +312,15 @@ impl Render for CheckboxGroup {
                                 )
                         )
                 }
-            }))
+            }));
+
+        div()
+            .flex()
+            .flex_col()
+            .child(group)
+            .when_some(error, |this, message| {
+                this.child(render_error_message(&message, &theme))
+            })
     }
 }
 
@@ -1,9 +1,17 @@
 use gpui::prelude::FluentBuilder;
 use gpui::*;
+use serde_json::Value;
 use crate::theme::*;
 use crate::components::basic::icon::{Icon, IconName};
 use crate::components::form::select::{SelectOption, SelectOptionGroup, DropdownDirection, DropdownAlignment, DropdownWidth};
 use crate::components::form::text_input::{TextInput, TextInputEvent};
+use crate::components::form::async_options::{AsyncOptions, OptionsResult};
+use crate::components::form::creatable::{self, CreateRow, CreateRule};
+use crate::components::form::dropdown_list::{DropdownList, ItemsKey, MenuItems, MenuRow};
+use crate::components::form::fuzzy::fuzzy_match;
+use crate::components::form::list_navigation::move_highlight;
+use crate::components::form::validation::{render_error_message, validation_methods, ValidateOn, Validation, ValidationResult, ValidationState};
+use crate::components::form::form_container::{value_to_string, FormControl};
 
 // ============================================================================
 // Events
@@ -16,6 +24,9 @@ pub enum ComboboxEvent {
     Changed(String),
     /// Input value changed
     InputChanged(String),
+    /// A value was created from the typed text, see [`Combobox::creatable`].
+    /// `Changed` with the same value follows.
+    Create(String),
 }
 
 impl EventEmitter<ComboboxEvent> for Combobox {}
@@ -31,6 +42,12 @@ impl EventEmitter<ComboboxEvent> for Combobox {}
 ///    to expand the dropdown list and select from a preset list of options
 /// 2. Free text input: Users can directly input custom values in the text box that don't exist in the list
 ///
+/// Typing filters the options with fuzzy matching: "g4o" finds "gpt-4o". Results are ranked by
+/// how well they match, with the matched characters highlighted.
+///
+/// In creatable mode, text that matches no option can be committed as a new value, see
+/// [`Combobox::creatable`].
+///
 /// # Example
 ///
 /// ```rust,ignore
@@ -48,6 +65,7 @@ impl EventEmitter<ComboboxEvent> for Combobox {}
 ///     match event {
 ///         ComboboxEvent::Changed(value) => println!("Selected: {}", value),
 ///         ComboboxEvent::InputChanged(value) => println!("Input: {}", value),
+///         ComboboxEvent::Create(value) => println!("Created: {}", value),
 ///     }
 /// });
 /// ```
@@ -92,6 +110,8 @@ pub struct Combobox {
     calculated_width: Option<f32>,
     /// Flag to blur the input after selection
     should_blur: bool,
+    /// Set while the input text is updated from outside, e.g. by a form reset
+    syncing_input: bool,
     /// Focus handle for the combobox
     focus_handle: FocusHandle,
     /// Whether to use fixed width (text and button don't move with content)
@@ -100,10 +120,27 @@ pub struct Combobox {
     fixed_width: bool,
     /// Text alignment in fixed width mode
     text_alignment: TextAlign,
+    /// Validation rules for the input text, and the current error
+    validation: Validation<str>,
+    /// Option highlighted by the keyboard, as an index into the menu's options
+    highlighted: Option<usize>,
+    /// Virtualized dropdown rows, only the visible ones are rendered
+    dropdown: DropdownList,
+    /// Source the options are loaded from, instead of `options`
+    async_options: Option<AsyncOptions>,
+    /// Whether typed text matching no option can be committed as a new value
+    creatable: bool,
+    /// Rule new values must pass before they can be created
+    create_rule: Option<CreateRule>,
     /// Event subscriptions
     _subscriptions: Vec<Subscription>,
 }
 
+validation_methods! {
+    /// The rule receives the input text.
+    Combobox: str = |this| this.input_value.clone()
+}
+
 impl Combobox {
     /// Create a new Combobox
     pub fn new(cx: &mut Context<Self>) -> Self {
@@ -137,9 +174,16 @@ impl Combobox {
             compact: false,
             calculated_width: None,
             should_blur: false,
+            syncing_input: false,
             focus_handle: cx.focus_handle(),
             fixed_width: false, // Default to dynamic width
             text_alignment: gpui::TextAlign::Left, // Default to left alignment
+            validation: Validation::default(),
+            highlighted: None,
+            dropdown: DropdownList::default(),
+            async_options: None,
+            creatable: false,
+            create_rule: None,
             _subscriptions: Vec::new(),
         }
     }
@@ -151,6 +195,55 @@ impl Combobox {
         self
     }
 
+    /// Load the options from an async source, e.g. a backend, as the user types.
+    ///
+    /// The source is called with the typed text and a zero-based page number.
+    /// Requests wait for typing to pause, and a new request cancels the
+    /// running one. Pages with `has_more` set end in a "Load more" row.
+    ///
+    /// ```rust,ignore
+    /// Combobox::new(cx).async_options(|query, page| {
+    ///     let request = api.search_models(query, page);
+    ///     async move {
+    ///         let models = request.await.map_err(|e| e.to_string())?;
+    ///         Ok(OptionsPage::new(models.items).has_more(models.has_next))
+    ///     }
+    /// })
+    /// ```
+    pub fn async_options<F, Fut>(mut self, load: F) -> Self
+    where
+        F: Fn(&str, usize) -> Fut + 'static,
+        Fut: std::future::Future<Output = OptionsResult> + 'static,
+    {
+        self.async_options = Some(AsyncOptions::new(load));
+        self.option_groups.clear(); // Loaded options are flat
+        self
+    }
+
+    /// The flat options, loaded ones when an async source is set
+    fn flat_options(&self) -> &[SelectOption] {
+        match &self.async_options {
+            Some(source) => source.options(),
+            None => &self.options,
+        }
+    }
+
+    /// Locates the async options for results of the async source
+    fn async_field(this: &mut Self) -> &mut AsyncOptions {
+        this.async_options.as_mut().expect("async options are set")
+    }
+
+    /// Request options for the typed text from the async source, unless
+    /// they were already requested
+    fn load_async_options(&mut self, debounce: bool, cx: &mut Context<Self>) {
+        let query = if self.is_filtering() { self.input_value.clone() } else { String::new() };
+        if let Some(source) = &mut self.async_options {
+            if !source.has_query(&query) {
+                source.load(&query, debounce, Self::async_field, cx);
+            }
+        }
+    }
+
     /// Set the option groups
     pub fn option_groups(mut self, groups: Vec<SelectOptionGroup>) -> Self {
         self.option_groups = groups;
@@ -210,6 +303,33 @@ impl Combobox {
         self
     }
 
+    /// Allow committing typed text that matches no option as a new value.
+    ///
+    /// The dropdown then ends in a `Create '<text>'` row, and pressing Enter
+    /// or leaving the input commits the typed text. Creating emits
+    /// [`ComboboxEvent::Create`] followed by [`ComboboxEvent::Changed`]; add
+    /// the new value to the options to offer it again.
+    ///
+    /// ```rust,ignore
+    /// Combobox::new(cx)
+    ///     .options(tags)
+    ///     .creatable(true)
+    ///     .validate_create(|text| {
+    ///         if text.len() <= 20 { Ok(()) } else { Err("Tags are at most 20 characters".into()) }
+    ///     })
+    /// ```
+    pub fn creatable(mut self, creatable: bool) -> Self {
+        self.creatable = creatable;
+        self
+    }
+
+    /// Add a rule for created values; while it fails the create row shows
+    /// its error and the text can't be committed
+    pub fn validate_create(mut self, rule: impl Fn(&str) -> ValidationResult + 'static) -> Self {
+        self.create_rule = Some(Box::new(rule));
+        self
+    }
+
     /// Set the size
     pub fn size(mut self, size: ComponentSize) -> Self {
         self.size = size;
@@ -309,73 +429,139 @@ impl Combobox {
         self
     }
 
-    /// Filter options based on input value
+    /// Whether the options are filtered by the input text
     /// Only filters when user is actively typing, not when dropdown is opened after selection
-    fn filtered_options(&self) -> Vec<SelectOption> {
-        let all_options = if !self.option_groups.is_empty() {
-            // Flatten option groups
-            self.option_groups
-                .iter()
-                .flat_map(|group| group.options.iter())
-                .cloned()
-                .collect()
-        } else {
-            self.options.clone()
-        };
+    fn is_filtering(&self) -> bool {
+        self.is_user_typing && !self.input_value.is_empty()
+    }
 
-        // Only filter if user is actively typing (not just when input has value from selection)
-        if self.is_user_typing && !self.input_value.is_empty() {
-            let input_lower = self.input_value.to_lowercase();
-            let filtered: Vec<SelectOption> = all_options
-                .iter()
-                .filter(|opt| {
-                    opt.label.to_lowercase().contains(&input_lower) ||
-                    opt.value.to_lowercase().contains(&input_lower)
-                })
-                .cloned()
+    /// Fuzzy match score of an option against the input text, the better of
+    /// its label and value, or `None` if neither matches
+    fn option_score(&self, option: &SelectOption) -> Option<i32> {
+        let label = fuzzy_match(&self.input_value, &option.label).map(|m| m.score);
+        let value = fuzzy_match(&self.input_value, &option.value).map(|m| m.score);
+        label.max(value)
+    }
+
+    /// Matching options ranked by score, best first
+    fn rank_options(&self, options: &[SelectOption]) -> Vec<(i32, SelectOption)> {
+        let mut ranked: Vec<(i32, SelectOption)> = options
+            .iter()
+            .filter_map(|opt| self.option_score(opt).map(|score| (score, opt.clone())))
+            .collect();
+        // Stable sort keeps the original order for equal scores
+        ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        ranked
+    }
+
+    /// Filter options based on input value, in display order
+    fn filtered_options(&self) -> Vec<SelectOption> {
+        if !self.option_groups.is_empty() {
+            // Flatten option groups, which are ranked as a whole
+            return self.filtered_option_groups()
+                .into_iter()
+                .flat_map(|group| group.options)
                 .collect();
+        }
 
-            // Return filtered results (even if empty - user can see no matches)
-            return filtered;
+        // Loaded options were already filtered by the async source
+        if self.is_filtering() && self.async_options.is_none() {
+            // Return ranked results (even if empty - user can see no matches)
+            return self.rank_options(&self.options)
+                .into_iter()
+                .map(|(_, option)| option)
+                .collect();
         }
 
         // No filtering - show all options
-        all_options
+        self.flat_options().to_vec()
     }
 
     /// Filter option groups based on input value
+    /// Groups keep their options together and are ordered by their best match
     fn filtered_option_groups(&self) -> Vec<SelectOptionGroup> {
-        // Only filter if user is actively typing (not just when input has value from selection)
-        if self.is_user_typing && !self.input_value.is_empty() {
-            let input_lower = self.input_value.to_lowercase();
-            let filtered: Vec<SelectOptionGroup> = self.option_groups
+        if self.is_filtering() {
+            let mut ranked: Vec<(i32, SelectOptionGroup)> = self.option_groups
                 .iter()
-                .map(|group| {
-                    let filtered_options: Vec<SelectOption> = group.options
-                        .iter()
-                        .filter(|opt| {
-                            opt.label.to_lowercase().contains(&input_lower) ||
-                            opt.value.to_lowercase().contains(&input_lower)
-                        })
-                        .cloned()
-                        .collect();
-
-                    SelectOptionGroup {
+                .filter_map(|group| {
+                    let options = self.rank_options(&group.options);
+                    // Only include groups with matching options
+                    let best = options.first()?.0;
+                    Some((best, SelectOptionGroup {
                         label: group.label.clone(),
-                        options: filtered_options,
-                    }
+                        options: options.into_iter().map(|(_, option)| option).collect(),
+                    }))
                 })
-                .filter(|group| !group.options.is_empty()) // Only include groups with matching options
                 .collect();
+            ranked.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
 
             // Return filtered results (even if empty - user can see no matches)
-            return filtered;
+            return ranked.into_iter().map(|(_, group)| group).collect();
         }
 
         // No filtering - show all option groups
         self.option_groups.clone()
     }
 
+    /// Option label with the characters matching the input text highlighted
+    fn render_label(&self, label: &str, is_selected: bool, theme: &Theme) -> StyledText {
+        let ranges = if self.is_filtering() {
+            fuzzy_match(&self.input_value, label).map(|m| m.ranges).unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+        let style = HighlightStyle {
+            font_weight: Some(FontWeight(theme.typography.font_weight_bold)),
+            // Selected options already use the primary background
+            color: (!is_selected).then_some(theme.colors.primary.into()),
+            ..Default::default()
+        };
+        StyledText::new(label.to_string())
+            .with_highlights(ranges.into_iter().map(|range| (range, style)))
+    }
+
+    /// The option whose label or value equals `text`, ignoring case and
+    /// surrounding whitespace
+    fn exact_match(&self, text: &str) -> Option<SelectOption> {
+        creatable::exact_match(&self.all_options(), text).cloned()
+    }
+
+    /// The create row for the typed text, if it is offered
+    fn create_row(&self) -> Option<CreateRow> {
+        if !self.creatable || !self.is_filtering() {
+            return None;
+        }
+        creatable::create_row(&self.input_value, &self.all_options(), self.create_rule.as_ref())
+    }
+
+    /// Commit the typed text as a new value. Returns `false` if it can't
+    /// be created, see `create_row`
+    fn create_value(&mut self, cx: &mut Context<Self>) -> bool {
+        let Some(CreateRow::Create(value)) = self.create_row() else {
+            return false;
+        };
+        if value != self.input_value {
+            self.input_value = value.clone();
+            if let Some(text_input) = &self.text_input {
+                // The input's Change event must not count as typing
+                self.syncing_input = true;
+                let text = value.clone();
+                text_input.update(cx, |input, cx| input.set_value(text, cx));
+            }
+        }
+        self.selected_value = Some(value.clone());
+        self.is_user_typing = false;
+        self.is_open = false;
+        self.highlighted = None;
+
+        // Creating commits the value, like picking an option
+        self.validate_for(ValidateOn::Blur, cx);
+        cx.emit(ComboboxEvent::Create(value.clone()));
+        cx.emit(ComboboxEvent::Changed(value));
+        cx.notify();
+        true
+    }
+
     /// Get all options (flattened from groups if needed) for selection
     fn all_options(&self) -> Vec<SelectOption> {
         if !self.option_groups.is_empty() {
@@ -385,20 +571,24 @@ impl Combobox {
                 .cloned()
                 .collect()
         } else {
-            self.options.clone()
+            self.flat_options().to_vec()
         }
     }
 
     /// Toggle dropdown open/closed
-    fn toggle_dropdown(&mut self) {
+    fn toggle_dropdown(&mut self, cx: &mut Context<Self>) {
         if !self.disabled {
             self.is_open = !self.is_open;
+            self.highlighted = None;
             // When opening dropdown via arrow click, only reset typing flag if input is empty
             // If input has value, preserve typing state to maintain filtering
             if self.is_open && self.input_value.is_empty() {
                 self.is_user_typing = false;
             }
             // If input has value, keep is_user_typing as is to preserve filtering state
+            if self.is_open {
+                self.load_async_options(false, cx);
+            }
         }
     }
 
@@ -414,6 +604,87 @@ impl Combobox {
         }
     }
 
+    /// Rebuild the dropdown rows if the input text or the options changed
+    /// since they were built, instead of ranking every option on each render
+    fn sync_menu_items(&mut self) {
+        let key = ItemsKey {
+            query: self.is_filtering().then(|| self.input_value.clone()),
+            revision: self.async_options.as_ref().map_or(0, |source| source.revision()),
+        };
+        if !self.dropdown.has_items_for(&key) {
+            let items = self.menu_items();
+            self.dropdown.set_items(key, items);
+        }
+    }
+
+    /// Filtered options in display order, and the dropdown rows showing them
+    fn menu_items(&self) -> MenuItems {
+        let items = if self.option_groups.is_empty() {
+            MenuItems::flat(self.filtered_options())
+        } else {
+            MenuItems::grouped(self.filtered_option_groups())
+        };
+        items
+            .with_create(self.create_row().is_some())
+            .with_status(self.async_options.as_ref().is_some_and(|source| source.has_status()))
+    }
+
+    /// Highlight an option, or the create row after the options, and scroll it into view
+    fn highlight(&mut self, index: Option<usize>) {
+        self.highlighted = index;
+        if let Some(index) = index {
+            self.dropdown.reveal_option(index);
+        }
+    }
+
+    /// Handle navigation keys before the text input sees them
+    fn handle_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if self.disabled {
+            return;
+        }
+        match event.keystroke.key.as_str() {
+            key @ ("up" | "down") => {
+                if self.is_open {
+                    let items = self.dropdown.items();
+                    let options = &items.options;
+                    // The create row comes after the options
+                    let len = options.len() + usize::from(items.has_create());
+                    let create_enabled = matches!(self.create_row(), Some(CreateRow::Create(_)));
+                    let enabled = |ix: usize| options.get(ix).map_or(create_enabled, |option| !option.disabled);
+                    if let Some(index) = move_highlight(self.highlighted, len, key, enabled) {
+                        self.highlight(index);
+                    }
+                } else {
+                    self.is_open = true;
+                    self.highlighted = None;
+                    self.load_async_options(false, cx);
+                }
+            }
+            // Enter without a highlight falls through to the input's submit
+            "enter" if self.is_open && self.highlighted == Some(self.dropdown.items().options.len()) => {
+                if self.create_value(cx) {
+                    self.should_blur = true;
+                }
+            }
+            "enter" if self.is_open && self.highlighted.is_some() => {
+                let option = self
+                    .highlighted
+                    .and_then(|index| self.dropdown.items().options.get(index).cloned())
+                    .filter(|option| !option.disabled);
+                if let Some(option) = option {
+                    self.select_option(option.value, window, cx);
+                }
+            }
+            "escape" if self.is_open => {
+                self.is_open = false;
+                self.highlighted = None;
+            }
+            _ => return,
+        }
+        cx.stop_propagation();
+        cx.notify();
+    }
+
     /// Select an option
     fn select_option(&mut self, value: String, _window: &mut Window, cx: &mut Context<Self>) {
         let all_options = self.all_options();
@@ -445,6 +716,8 @@ impl Combobox {
                 });
             }
 
+            // Picking an option commits the value
+            self.validate_for(ValidateOn::Blur, cx);
             cx.emit(ComboboxEvent::Changed(value));
             cx.notify();
         }
@@ -452,10 +725,9 @@ impl Combobox {
 
     /// Render the dropdown overlay
     fn render_dropdown_overlay(&self, cx: &Context<Self>) -> impl IntoElement {
-        let theme = Theme::default();
-        let has_groups = !self.option_groups.is_empty();
+        let theme = cx.theme().clone();
 
-        let mut menu = div()
+        let menu = div()
             .occlude()  // Ensure popup content is above other content
             .id("combobox-popup")
             .flex()
@@ -470,124 +742,20 @@ impl Combobox {
                 // Set a reasonable minimum width to ensure content fits
                 this.min_w(px(200.))
             })
-            .max_h(px(300.))
-            .overflow_y_scroll()
             .overflow_x_hidden()
             // Only round bottom corners when connected to input
-            .rounded_bl(px(BorderRadius::LG))
-            .rounded_br(px(BorderRadius::LG))
+            .rounded_bl(px(theme.radius.lg))
+            .rounded_br(px(theme.radius.lg))
             .border_1()
             .border_color(theme.colors.border)
             .bg(theme.colors.background)
             .when(self.show_shadow, |this| {
-                this.shadow(vec![
-                    BoxShadow {
-                        color: rgba(0x00000010).into(),
-                        offset: point(px(0.), px(4.)),
-                        blur_radius: px(16.),
-                        spread_radius: px(-2.),
-                    },
-                    BoxShadow {
-                        color: rgba(0x00000008).into(),
-                        offset: point(px(0.), px(2.)),
-                        blur_radius: px(8.),
-                        spread_radius: px(0.),
-                    },
-                ])
+                this.shadow(theme.shadow_lg())
             })
-            .pb(px(6.));
-
-        // Render grouped or flat options
-        if has_groups {
-            let filtered_groups = self.filtered_option_groups();
-            let mut item_counter: usize = 0;
-            menu = menu.children(filtered_groups.iter().enumerate().map(|(group_idx, group)| {
-                div()
-                    .flex()
-                    .flex_col()
-                    .map(|this| {
-                        // Add top margin for groups after the first one
-                        if group_idx > 0 {
-                            this.mt(if self.compact { px(2.) } else { px(4.) })
-                        } else {
-                            this.mt(px(6.))
-                        }
-                    })
-                    .child({
-                        // Group label with clear, bold styling
-                        let label_py = if self.compact { px(4.) } else { px(8.) };
-                        let label_px = if self.compact { px(8.) } else { px(12.) };
-
-                        div()
-                            .px(label_px)
-                            .py(label_py)
-                            .text_sm()
-                            .font_weight(FontWeight::BOLD)
-                            .text_color(theme.colors.text)
-                            .child(group.label.clone())
-                    })
-                    .children(group.options.iter().map(|option| {
-                        let _id = ("combobox-group-item", item_counter);
-                        item_counter += 1;
-                        // For grouped items, create a container with full-width background
-                        // and overlay content with indentation
-                        let value = option.value.clone();
-                        let label = option.label.clone();
-                        let size = self.size;
-                        
-                        // Check if this option is selected
-                        let is_selected = if let Some(ref selected_value) = self.selected_value {
-                            selected_value == &value && self.input_value == label
-                        } else {
-                            false
-                        };
-                        
-                        // Single item div with background and content
-                        div()
-                            .w_full()
-                            .min_h(px(32.)) // Ensure minimum height for items
-                            .mx(px(6.)) // Horizontal margin instead of padding to allow background to span full width
-                            .px(if self.compact { px(8.) } else { px(12.) })
-                            .py(if self.compact { px(3.) } else { px(8.) })
-                            .flex()
-                            .items_center()
-                            .justify_between()
-                            .cursor(CursorStyle::PointingHand)
-                            .text_size(size.font_size())
-                            .rounded(px(BorderRadius::SM))
-                            .map(|this| {
-                                if is_selected {
-                                    this.bg(theme.colors.primary)
-                                        .text_color(rgb(0xFFFFFF))
-                                } else {
-                                    this.hover(|style| style.bg(theme.colors.background_hover))
-                                        .text_color(theme.colors.text)
-                                }
-                            })
-                            .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event: &MouseDownEvent, window, cx| {
-                                this.select_option(value.clone(), window, cx);
-                            }))
-                            .child(label)
-                    }))
-                    .child(
-                        // Separator line below the last option in this group
-                        div()
-                            .h(px(1.))
-                            .bg(theme.colors.border)
-                            .mx(px(12.))
-                            .mt(if self.compact { px(1.) } else { px(2.) })
-                    )
-            }));
-        } else {
-            let filtered_options = self.filtered_options();
-            menu = menu.children(filtered_options.iter().enumerate().map(|(idx, option)| {
-                div()
-                    .when(idx == 0, |this| {
-                        this.mt(px(6.))
-                    })
-                    .child(self.render_option(option, ("combobox-item", idx), &theme, cx))
-            }));
-        }
+            .pt(px(theme.spacing.xs))
+            .pb(px(theme.spacing.xs))
+            // Only the visible rows are rendered, see `render_row`
+            .child(self.dropdown.render(px(288.), cx, Self::render_row));
 
         let input_width = self.calculated_width.unwrap_or_else(|| {
             // Fallback: estimate width based on placeholder length
@@ -658,10 +826,109 @@ impl Combobox {
             .child(menu)
     }
 
+    /// Render a row of the dropdown menu: a group label, an option, a
+    /// separator or the status of an async source
+    fn render_row(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) -> AnyElement {
+        let theme = cx.theme().clone();
+        let items = self.dropdown.items();
+
+        match self.dropdown.row(ix) {
+            Some(MenuRow::GroupLabel(group_ix)) => {
+                // Group label with clear, bold styling
+                let label_py = if self.compact { px(theme.spacing.xxs) } else { px(theme.spacing.sm) };
+                let label_px = if self.compact { px(theme.spacing.sm) } else { px(theme.spacing.md) };
+
+                div()
+                    // Add top margin for groups after the first one
+                    .when(group_ix > 0, |this| {
+                        this.mt(if self.compact { px(2.) } else { px(4.) })
+                    })
+                    .px(label_px)
+                    .py(label_py)
+                    .text_size(theme.font_size(self.size))
+                    .font_weight(FontWeight(theme.typography.font_weight_bold))
+                    .text_color(theme.colors.text)
+                    .child(items.group_labels[group_ix].clone())
+                    .into_any_element()
+            }
+            Some(MenuRow::Option(option_ix)) => {
+                let highlighted = self.highlighted == Some(option_ix);
+                self.render_option(&items.options[option_ix], ("combobox-item", option_ix), highlighted, &theme, window, cx)
+                    .into_any_element()
+            }
+            Some(MenuRow::Create) => {
+                let highlighted = self.highlighted == Some(items.options.len());
+                self.render_create_row(highlighted, &theme, cx).into_any_element()
+            }
+            Some(MenuRow::Separator) => {
+                // Separator line below the last option in a group
+                div()
+                    .h(px(1.))
+                    .bg(theme.colors.border)
+                    .mx(px(theme.spacing.md))
+                    .mt(if self.compact { px(1.) } else { px(2.) })
+                    .into_any_element()
+            }
+            Some(MenuRow::Status) => self
+                .async_options
+                .as_ref()
+                .and_then(|source| source.render_status(Self::async_field, &theme, cx))
+                .unwrap_or_else(|| div().into_any_element()),
+            None => div().into_any_element(),
+        }
+    }
+
+    /// Render the row creating a value from the typed text, or the error
+    /// of the `validate_create` rule
+    fn render_create_row(&self, highlighted: bool, theme: &Theme, cx: &mut Context<Self>) -> impl IntoElement {
+        let padding_y = if self.compact { px(theme.spacing.xxs) } else { px(theme.spacing.sm) };
+        let padding_x = if self.compact { px(theme.spacing.sm) } else { px(theme.spacing.md) };
+
+        div()
+            .id("combobox-create")
+            .w_full()
+            .min_h(px(32.))
+            .mx(px(theme.spacing.xs))
+            .px(padding_x)
+            .py(padding_y)
+            .flex()
+            .items_center()
+            .gap(px(theme.spacing.sm))
+            .text_size(theme.font_size(self.size))
+            .rounded(px(theme.radius.sm))
+            .map(|this| match self.create_row() {
+                Some(CreateRow::Rejected(error)) => this
+                    .text_color(theme.colors.error)
+                    .child(Icon::new(IconName::AlertCircle).xsmall().color(theme.colors.error))
+                    .child(div().flex_1().min_w_0().child(error)),
+                _ => this
+                    .text_color(theme.colors.text)
+                    .when(highlighted, |this| this.bg(theme.colors.background_hover))
+                    .hover(|style| style.bg(theme.colors.background_hover))
+                    .cursor(CursorStyle::PointingHand)
+                    .child(Icon::new(IconName::Plus).xsmall().color(theme.colors.text_secondary))
+                    .child(div().flex_1().min_w_0().child(format!("Create '{}'", self.input_value.trim())))
+                    .on_mouse_down(MouseButton::Left, cx.listener(|this, _event: &MouseDownEvent, _window, cx| {
+                        if this.create_value(cx) {
+                            this.should_blur = true;
+                        }
+                    })),
+            })
+    }
+
     /// Render a single option item with full background styling
-    fn render_option(&self, option: &SelectOption, id: impl Into<ElementId>, theme: &Theme, cx: &Context<Self>) -> impl IntoElement {
+    fn render_option(
+        &self,
+        option: &SelectOption,
+        id: impl Into<ElementId>,
+        highlighted: bool,
+        theme: &Theme,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
         let value = option.value.clone();
         let label = option.label.clone();
+        let disabled = option.disabled;
         let size = self.size;
 
         // Check if this option is selected
@@ -673,38 +940,45 @@ impl Combobox {
         } else {
             false
         };
+        let content = option.render_content(self.render_label(&label, is_selected, theme), is_selected, theme, window, cx);
 
         // Use compact spacing if enabled
-        let padding_y = if self.compact { px(3.) } else { px(8.) };
-        let padding_x = if self.compact { px(8.) } else { px(12.) };
+        let padding_y = if self.compact { px(theme.spacing.xxs) } else { px(theme.spacing.sm) };
+        let padding_x = if self.compact { px(theme.spacing.sm) } else { px(theme.spacing.md) };
 
         // Single item div with background and content
         div()
             .id(id)
             .w_full()
             .min_h(px(32.)) // Ensure minimum height for items
-            .mx(px(6.)) // Horizontal margin instead of padding to allow background to span full width
+            .mx(px(theme.spacing.xs)) // Horizontal margin instead of padding to allow background to span full width
             .px(padding_x)
             .py(padding_y)
             .flex()
             .items_center()
             .justify_between()
-            .cursor(CursorStyle::PointingHand)
-            .text_size(size.font_size())
-            .rounded(px(BorderRadius::SM))
+            .text_size(theme.font_size(size))
+            .rounded(px(theme.radius.sm))
             .map(|this| {
                 if is_selected {
                     this.bg(theme.colors.primary)
-                        .text_color(rgb(0xFFFFFF))
+                        .text_color(theme.colors.text_on_primary)
+                } else if disabled {
+                    this.text_color(theme.colors.text)
                 } else {
-                    this.hover(|style| style.bg(theme.colors.background_hover))
+                    this.when(highlighted, |this| this.bg(theme.colors.background_hover))
+                        .hover(|style| style.bg(theme.colors.background_hover))
                         .text_color(theme.colors.text)
                 }
             })
-            .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event: &MouseDownEvent, window, cx| {
-                this.select_option(value.clone(), window, cx);
-            }))
-            .child(label)
+            .when(disabled, |this| this.opacity(0.5))
+            .when(!disabled, |this| {
+                this.cursor(CursorStyle::PointingHand)
+                    .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event: &MouseDownEvent, window, cx| {
+                        this.select_option(value.clone(), window, cx);
+                    }))
+            })
+            .child(content)
     }
 }
 
@@ -714,6 +988,47 @@ impl Focusable for Combobox {
     }
 }
 
+impl FormControl for Combobox {
+    /// The selected option value, or the typed text if none is selected
+    fn form_value(&self) -> Value {
+        Value::String(self.selected_value.clone().unwrap_or_else(|| self.input_value.clone()))
+    }
+
+    fn set_form_value(&mut self, value: &Value, cx: &mut Context<Self>) {
+        let value = value_to_string(value);
+        let option = self.all_options().into_iter().find(|opt| opt.value == value);
+        self.selected_value = option.as_ref().map(|opt| opt.value.clone());
+        self.input_value = option.map_or(value, |opt| opt.label);
+        self.is_user_typing = false;
+        if let Some(text_input) = &self.text_input {
+            if text_input.read(cx).get_value() != self.input_value {
+                // The input's Change event must not count as typing
+                self.syncing_input = true;
+                let text = self.input_value.clone();
+                text_input.update(cx, |input, cx| input.set_value(text, cx));
+            }
+        }
+        cx.notify();
+    }
+
+    fn validate_form(&mut self, cx: &mut Context<Self>) -> ValidationState {
+        self.run_validation(cx);
+        self.validation.state().clone()
+    }
+
+    fn form_validation_state(&self) -> ValidationState {
+        self.validation.state().clone()
+    }
+
+    fn clear_form_error(&mut self, cx: &mut Context<Self>) {
+        self.set_error(None, cx);
+    }
+
+    fn form_focus_handle(&self, cx: &App) -> Option<FocusHandle> {
+        self.text_input.as_ref().map(|input| input.focus_handle(cx))
+    }
+}
+
 // ============================================================================
 // Render
 // ============================================================================
@@ -727,10 +1042,15 @@ impl Render for Combobox {
             cx.focus_self(window);
         }
 
-        let theme = Theme::default();
+        let theme = cx.theme().clone();
         let disabled = self.disabled;
         let is_open = self.is_open;
         let text_input = self.text_input.clone();
+        if is_open {
+            // Pick up filtering and loaded options
+            self.sync_menu_items();
+        }
+        let error = self.validation.error().map(str::to_string);
 
         // Always recalculate width to ensure it's accurate
         // This handles cases where input_value changes but calculated_width hasn't been updated
@@ -740,19 +1060,13 @@ impl Render for Combobox {
             &self.input_value
         };
         
-        // Measure text width using TextRun API
-        let font = gpui::Font {
-            family: ".SystemUIFont".into(),
-            features: Default::default(),
-            weight: Default::default(),
-            style: Default::default(),
-            fallbacks: None,
-        };
-        let font_size = px(14.);
+        // Measure text width using TextRun API, at the size the TextInput renders
+        let font = theme.typography.font();
+        let font_size = theme.font_size(ComponentSize::Medium);
         let text_runs = vec![gpui::TextRun {
             len: text_to_measure.len(),
             font: font.clone(),
-            color: rgb(0x333333).into(),
+            color: theme.colors.text.into(),
             background_color: None,
             underline: None,
             strikethrough: None,
@@ -772,7 +1086,8 @@ impl Render for Combobox {
         } else {
             px(4.) // Dynamic width: minimal space for icon (just padding)
         };
-        let calculated_width = shaped_line.width + px(14.) + right_space; // Left padding 12px + right padding 2px = 14px
+        // The input's left padding, plus its 2px right padding
+        let calculated_width = shaped_line.width + px(theme.spacing.md + 2.) + right_space;
         // Always update calculated_width to ensure it matches current input
         self.calculated_width = Some(calculated_width.max(min_width).into());
 
@@ -789,7 +1104,7 @@ impl Render for Combobox {
                                 );
 
                             // Skip processing if this is a programmatic change (from select_option)
-                            if is_programmatic {
+                            if std::mem::take(&mut this.syncing_input) || is_programmatic {
                                 return;
                             }
 
@@ -811,19 +1126,13 @@ impl Render for Combobox {
                                 value
                             };
                             
-                            // Measure text width using TextRun API
-                            let font = gpui::Font {
-                                family: ".SystemUIFont".into(),
-                                features: Default::default(),
-                                weight: Default::default(),
-                                style: Default::default(),
-                                fallbacks: None,
-                            };
-                            let font_size = px(14.);
+                            // Measure text width using TextRun API, at the size the TextInput renders
+                            let font = cx.theme().typography.font();
+                            let font_size = cx.theme().font_size(ComponentSize::Medium);
                             let text_runs = vec![gpui::TextRun {
                                 len: text_to_measure.len(),
                                 font: font.clone(),
-                                color: rgb(0x333333).into(),
+                                color: cx.theme().colors.text.into(),
                                 background_color: None,
                                 underline: None,
                                 strikethrough: None,
@@ -843,13 +1152,20 @@ impl Render for Combobox {
                             } else {
                                 px(4.) // Dynamic width: minimal space for icon (just padding)
                             };
-                            let calculated_width = shaped_line.width + px(14.) + right_space; // Left padding 12px + right padding 2px = 14px
+                            // The input's left padding, plus its 2px right padding
+                            let calculated_width = shaped_line.width + px(cx.theme().spacing.md + 2.) + right_space;
                             this.calculated_width = Some(calculated_width.max(min_width).into());
 
+                            // The filtered list changed, so the old highlight is stale
+                            this.highlighted = None;
                             // Always keep dropdown open when user is typing (for filtering)
                             if !this.is_open {
                                 this.is_open = true;
                             }
+                            this.load_async_options(true, cx);
+                            // Rank the options once per edit, before navigation keys use them
+                            this.sync_menu_items();
+                            this.validate_for(ValidateOn::Change, cx);
                             cx.emit(ComboboxEvent::InputChanged(value.clone()));
                             cx.notify();
                         }
@@ -868,28 +1184,36 @@ impl Render for Combobox {
                                 if !this.is_open {
                                     this.is_open = true;
                                 }
+                                this.load_async_options(false, cx);
                                 cx.notify();
                             }
                         }
                         TextInputEvent::Submit(value) => {
+                            this.validate_for(ValidateOn::Submit, cx);
+                            cx.notify();
                             // When user presses Enter, check if input matches any option
-                            if !value.is_empty() {
+                            if !value.trim().is_empty() {
                                 let all_options = this.all_options();
 
                                 // First, try exact match (case-insensitive)
-                                let exact_match = all_options.iter().find(|opt|
-                                    opt.label.eq_ignore_ascii_case(value) || opt.value.eq_ignore_ascii_case(value)
-                                );
+                                let exact_match = this.exact_match(value.trim()).filter(|opt| !opt.disabled);
 
                                 if let Some(matched_option) = exact_match {
                                     // Found exact match, select it
                                     this.select_option(matched_option.value.clone(), window, cx);
+                                } else if this.creatable {
+                                    // Commit the typed text as is; if the create rule
+                                    // rejects it, the open dropdown shows why
+                                    if this.create_value(cx) {
+                                        this.should_blur = true;
+                                    } else {
+                                        this.is_open = true;
+                                    }
                                 } else {
                                     // No exact match, check if there's only one filtered option
-                                    let filtered = all_options.iter().filter(|opt|
-                                        opt.label.to_lowercase().contains(&value.to_lowercase()) ||
-                                        opt.value.to_lowercase().contains(&value.to_lowercase())
-                                    ).collect::<Vec<_>>();
+                                    let filtered = all_options.iter()
+                                        .filter(|opt| !opt.disabled && this.option_score(opt).is_some())
+                                        .collect::<Vec<_>>();
 
                                     if filtered.len() == 1 {
                                         // Only one match, select it
@@ -902,6 +1226,23 @@ impl Render for Combobox {
                         TextInputEvent::Blur => {
                             // Close dropdown when losing focus (but allow clicking dropdown)
                             // This is handled by clicking_menu flag
+                            // Leaving the input commits typed text in creatable mode,
+                            // which also validates it
+                            let committed = this.creatable && this.is_filtering() && !this.clicking_menu && {
+                                match this.exact_match(&this.input_value).filter(|opt| !opt.disabled) {
+                                    Some(option) => {
+                                        this.select_option(option.value, window, cx);
+                                        // Focus already moved elsewhere
+                                        this.should_blur = false;
+                                        true
+                                    }
+                                    None => this.create_value(cx),
+                                }
+                            };
+                            if !committed {
+                                this.validate_for(ValidateOn::Blur, cx);
+                            }
+                            cx.notify();
                         }
                     }
                 });
@@ -917,6 +1258,8 @@ impl Render for Combobox {
                     this.close_dropdown(cx);
                 }))
             })
+            // Capture navigation keys before the text input handles them
+            .capture_key_down(cx.listener(Self::handle_key_down))
             .child(
                 div()
                     .id("combobox-container")
@@ -946,16 +1289,20 @@ impl Render for Combobox {
                             .gap_0()
                             .when(is_open, |this| {
                                 // When dropdown is open, only round top corners
-                                this.rounded_tl(px(BorderRadius::LG))
-                                    .rounded_tr(px(BorderRadius::LG))
+                                this.rounded_tl(px(theme.radius.lg))
+                                    .rounded_tr(px(theme.radius.lg))
                             })
                             .when(!is_open, |this| {
                                 // When dropdown is closed, round all corners
-                                this.rounded(px(BorderRadius::LG))
+                                this.rounded(px(theme.radius.lg))
                             })
                             .when(self.show_border, |this| {
                                 this.border_1()
-                                    .border_color(theme.colors.border)
+                                    .border_color(if error.is_some() {
+                                        theme.colors.border_error
+                                    } else {
+                                        theme.colors.border
+                                    })
                                     // When dropdown is open, remove bottom border to connect seamlessly
                                     // When closed, show all borders including bottom
                                     .when(is_open, |this| {
@@ -965,14 +1312,9 @@ impl Render for Combobox {
                             .when(!self.transparent_background, |this| {
                                 this.bg(theme.colors.background)
                             })
-                            .min_h(px(36.))  // Ensure minimum height matches TextInput
+                            .min_h(theme.height(ComponentSize::Medium))  // Ensure minimum height matches TextInput
                             .when(self.show_shadow, |this| {
-                                this.shadow(vec![BoxShadow {
-                                    color: rgba(0x0000000A).into(),
-                                    offset: point(px(0.), px(1.)),
-                                    blur_radius: px(2.),
-                                    spread_radius: px(0.),
-                                }])
+                                this.shadow(theme.shadow_sm())
                             })
                             .when(disabled, |this| {
                                 this.opacity(0.64)
@@ -1043,22 +1385,22 @@ impl Render for Combobox {
                                             .justify_center()
                                             .when(self.fixed_width, |this| {
                                                 // Fixed width mode: icon at right with minimal padding
-                                                this.pl(px(2.)).pr(px(2.))
+                                                this.pl(px(theme.spacing.xxxs)).pr(px(theme.spacing.xxxs))
                                             })
                                             .when(!self.fixed_width, |this| {
                                                 // Dynamic width mode: icon close to text (text container has negative margin)
-                                                this.px(px(2.))
+                                                this.px(px(theme.spacing.xxxs))
                                             })
                                             .flex_none()
                                             .cursor(CursorStyle::PointingHand)
                                             .on_mouse_down(MouseButton::Left, cx.listener(|this, _event: &MouseDownEvent, _window, cx| {
-                                                this.toggle_dropdown();
+                                                this.toggle_dropdown(cx);
                                                 cx.notify();
                                             }))
                                             .child(
                                                 Icon::new(IconName::ChevronUpDown)
                                                     .small()
-                                                    .color(rgb(0x666666))
+                                                    .color(theme.colors.text_secondary)
                                             )
                                     )
                             )
@@ -1071,6 +1413,9 @@ impl Render for Combobox {
                         None
                     })
             )
+            .when_some(error, |this, message| {
+                this.child(render_error_message(&message, &theme))
+            })
     }
 }
 
@@ -0,0 +1,106 @@
+// Creating values from typed text in a creatable Combobox
+
+use super::select::SelectOption;
+use super::validation::ValidationResult;
+
+/// Rule deciding whether typed text can be created, see `Combobox::validate_create`
+pub(crate) type CreateRule = Box<dyn Fn(&str) -> ValidationResult>;
+
+/// What the create row offers for the typed text
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum CreateRow {
+    /// Create the typed text, trimmed
+    Create(String),
+    /// The create rule rejects the typed text with this error
+    Rejected(String),
+}
+
+/// Whether two strings are equal ignoring case, for any script
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a == b || a.to_lowercase() == b.to_lowercase()
+}
+
+/// The option whose label or value equals `text`, ignoring case and
+/// surrounding whitespace
+pub(crate) fn exact_match<'a>(options: &'a [SelectOption], text: &str) -> Option<&'a SelectOption> {
+    let text = text.trim();
+    options
+        .iter()
+        .find(|opt| eq_ignore_case(&opt.label, text) || eq_ignore_case(&opt.value, text))
+}
+
+/// The create row for `text`, or `None` if it is blank or matches an option.
+///
+/// Disabled options count as matches, so they can't be created again.
+pub(crate) fn create_row(
+    text: &str,
+    options: &[SelectOption],
+    rule: Option<&CreateRule>,
+) -> Option<CreateRow> {
+    let text = text.trim();
+    if text.is_empty() || exact_match(options, text).is_some() {
+        return None;
+    }
+    match rule.map(|rule| rule(text)) {
+        Some(Err(error)) => Some(CreateRow::Rejected(error)),
+        _ => Some(CreateRow::Create(text.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{create_row, exact_match, CreateRow, CreateRule};
+    use crate::components::form::select::SelectOption;
+
+    fn options() -> Vec<SelectOption> {
+        vec![
+            SelectOption::new("rust", "Rust"),
+            SelectOption::new("zurich", "Zürich"),
+            SelectOption::new("go", "Go").disabled(true),
+        ]
+    }
+
+    #[test]
+    fn exact_match_ignores_case_and_whitespace() {
+        let options = options();
+        let value = |text| exact_match(&options, text).map(|opt| opt.value.as_str());
+
+        assert_eq!(value("RUST"), Some("rust"));
+        assert_eq!(value("  rust \n"), Some("rust"));
+        assert_eq!(value("ZÜRICH"), Some("zurich"));
+        assert_eq!(value("ZURICH"), Some("zurich"));
+        assert_eq!(value("ru"), None);
+    }
+
+    #[test]
+    fn create_row_is_offered_for_new_trimmed_text() {
+        let options = options();
+
+        assert_eq!(create_row("  Python ", &options, None), Some(CreateRow::Create("Python".into())));
+        assert_eq!(create_row("   ", &options, None), None);
+        assert_eq!(create_row(" rust", &options, None), None);
+        assert_eq!(create_row("zÜrich", &options, None), None);
+        // Disabled options can't be created either
+        assert_eq!(create_row("GO", &options, None), None);
+    }
+
+    #[test]
+    fn create_rule_sees_the_trimmed_text() {
+        let options = options();
+        let rule: CreateRule = Box::new(|text| {
+            if text.len() < 3 {
+                Err(format!("'{}' is too short", text))
+            } else {
+                Ok(())
+            }
+        });
+
+        assert_eq!(
+            create_row(" js ", &options, Some(&rule)),
+            Some(CreateRow::Rejected("'js' is too short".into()))
+        );
+        assert_eq!(create_row("java", &options, Some(&rule)), Some(CreateRow::Create("java".into())));
+        // Matches are selected, not rejected
+        assert_eq!(create_row("Go", &options, Some(&rule)), None);
+    }
+}
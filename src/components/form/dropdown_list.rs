@@ -0,0 +1,252 @@
+// Virtualized rows of Select and Combobox dropdowns: group labels, options and separators
+
+use gpui::*;
+
+use crate::components::form::select::{SelectOption, SelectOptionGroup};
+
+/// Height of the rows rendered beyond the visible ones, and of the first
+/// layout before the list knows its size
+const OVERDRAW: f32 = 300.0;
+
+/// A row of a dropdown menu
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum MenuRow {
+    /// Label of the group with this index
+    GroupLabel(usize),
+    /// Option with this index in [`MenuItems::options`]
+    Option(usize),
+    /// Separator below a group
+    Separator,
+    /// Row creating a value from the typed text, highlighted as the option
+    /// after the last one
+    Create,
+    /// Loading, error or "Load more" row of an async source
+    Status,
+}
+
+/// The options of a dropdown in display order, and the rows showing them
+#[derive(Default)]
+pub(crate) struct MenuItems {
+    pub rows: Vec<MenuRow>,
+    pub options: Vec<SelectOption>,
+    pub group_labels: Vec<String>,
+}
+
+impl MenuItems {
+    /// One row per option
+    pub fn flat(options: Vec<SelectOption>) -> Self {
+        Self {
+            rows: (0..options.len()).map(MenuRow::Option).collect(),
+            options,
+            group_labels: Vec::new(),
+        }
+    }
+
+    /// A label, the options and a separator per group
+    pub fn grouped(groups: Vec<SelectOptionGroup>) -> Self {
+        let mut items = Self::default();
+        for (group_ix, group) in groups.into_iter().enumerate() {
+            items.rows.push(MenuRow::GroupLabel(group_ix));
+            for option in group.options {
+                items.rows.push(MenuRow::Option(items.options.len()));
+                items.options.push(option);
+            }
+            items.rows.push(MenuRow::Separator);
+            items.group_labels.push(group.label);
+        }
+        items
+    }
+
+    /// Add a create row after the options
+    pub fn with_create(mut self, create: bool) -> Self {
+        if create {
+            self.rows.push(MenuRow::Create);
+        }
+        self
+    }
+
+    /// Whether the create row is shown
+    pub fn has_create(&self) -> bool {
+        self.rows.contains(&MenuRow::Create)
+    }
+
+    /// Add a status row at the end
+    pub fn with_status(mut self, status: bool) -> Self {
+        if status {
+            self.rows.push(MenuRow::Status);
+        }
+        self
+    }
+
+    /// Whether the groups are shown
+    pub fn is_grouped(&self) -> bool {
+        !self.group_labels.is_empty()
+    }
+
+    /// The row showing an option, or the create row for the index after the last option
+    pub fn option_row(&self, option_ix: usize) -> Option<usize> {
+        let target = if option_ix == self.options.len() {
+            MenuRow::Create
+        } else {
+            MenuRow::Option(option_ix)
+        };
+        self.rows.iter().position(|row| *row == target)
+    }
+
+    fn same_rows(&self, other: &MenuItems) -> bool {
+        self.rows == other.rows
+            && self.group_labels == other.group_labels
+            && self
+                .options
+                .iter()
+                .zip(&other.options)
+                .all(|(a, b)| a.value == b.value && a.label == b.label)
+    }
+}
+
+/// What menu items were built from: the filter text, if filtering, and the
+/// revision of the async option source
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct ItemsKey {
+    pub query: Option<String>,
+    pub revision: usize,
+}
+
+/// A dropdown list that only renders the visible rows, for long option lists
+pub(crate) struct DropdownList {
+    state: ListState,
+    items: MenuItems,
+    /// Key of `items`, `None` before they are first set
+    key: Option<ItemsKey>,
+}
+
+impl Default for DropdownList {
+    fn default() -> Self {
+        Self {
+            state: ListState::new(0, ListAlignment::Top, px(OVERDRAW)),
+            items: MenuItems::default(),
+            key: None,
+        }
+    }
+}
+
+impl DropdownList {
+    pub fn items(&self) -> &MenuItems {
+        &self.items
+    }
+
+    pub fn row(&self, ix: usize) -> Option<MenuRow> {
+        self.items.rows.get(ix).copied()
+    }
+
+    /// Whether the items were built for `key`. Building them filters and
+    /// ranks every option, so it is skipped while the key stays the same.
+    pub fn has_items_for(&self, key: &ItemsKey) -> bool {
+        self.key.as_ref() == Some(key)
+    }
+
+    /// Replace the items built for `key`, scrolling back to the top if the
+    /// rows changed
+    pub fn set_items(&mut self, key: ItemsKey, items: MenuItems) {
+        if !self.items.same_rows(&items) {
+            self.state.reset(items.rows.len());
+        }
+        self.items = items;
+        self.key = Some(key);
+    }
+
+    /// Scroll so an option is the first visible row, e.g. when opening
+    pub fn scroll_to_option(&self, option_ix: usize) {
+        if let Some(item_ix) = self.items.option_row(option_ix) {
+            self.state.scroll_to(ListOffset {
+                item_ix,
+                offset_in_item: px(0.),
+            });
+        }
+    }
+
+    /// Scroll just enough to show an option, e.g. when moving the highlight
+    pub fn reveal_option(&self, option_ix: usize) {
+        if let Some(item_ix) = self.items.option_row(option_ix) {
+            self.state.scroll_to_reveal_item(item_ix);
+        }
+    }
+
+    /// The list element, sized to its rows up to `max_height`.
+    ///
+    /// `render_row` is called with row indices for the visible rows only.
+    pub fn render<E: 'static>(
+        &self,
+        max_height: Pixels,
+        cx: &Context<E>,
+        render_row: fn(&mut E, usize, &mut Window, &mut Context<E>) -> AnyElement,
+    ) -> List {
+        list(self.state.clone(), cx.processor(render_row))
+            .with_sizing_behavior(ListSizingBehavior::Infer)
+            .max_h(max_height)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DropdownList, ItemsKey, MenuItems, MenuRow};
+    use crate::components::form::select::{SelectOption, SelectOptionGroup};
+
+    #[test]
+    fn grouped_rows_have_labels_and_separators() {
+        let items = MenuItems::grouped(vec![
+            SelectOptionGroup::new("Fruit", vec![SelectOption::new("a", "Apple"), SelectOption::new("b", "Banana")]),
+            SelectOptionGroup::new("Vegetables", vec![SelectOption::new("c", "Carrot")]),
+        ])
+        .with_status(true);
+
+        assert_eq!(
+            items.rows,
+            vec![
+                MenuRow::GroupLabel(0),
+                MenuRow::Option(0),
+                MenuRow::Option(1),
+                MenuRow::Separator,
+                MenuRow::GroupLabel(1),
+                MenuRow::Option(2),
+                MenuRow::Separator,
+                MenuRow::Status,
+            ]
+        );
+        assert_eq!(items.options[2].label, "Carrot");
+        assert_eq!(items.option_row(2), Some(5));
+        assert_eq!(items.option_row(3), None);
+    }
+
+    #[test]
+    fn flat_rows_map_one_to_one() {
+        let items = MenuItems::flat(vec![SelectOption::new("a", "A"), SelectOption::new("b", "B")]);
+        assert!(!items.is_grouped());
+        assert_eq!(items.option_row(1), Some(1));
+        assert_eq!(items.rows.len(), 2);
+    }
+
+    #[test]
+    fn items_are_kept_until_the_key_changes() {
+        let mut list = DropdownList::default();
+        let key = ItemsKey { query: Some("a".into()), revision: 0 };
+        assert!(!list.has_items_for(&key));
+
+        list.set_items(key.clone(), MenuItems::flat(vec![SelectOption::new("a", "A")]));
+        assert!(list.has_items_for(&key));
+        assert!(!list.has_items_for(&ItemsKey { query: Some("ab".into()), revision: 0 }));
+        assert!(!list.has_items_for(&ItemsKey { query: Some("a".into()), revision: 1 }));
+        assert_eq!(list.row(0), Some(MenuRow::Option(0)));
+    }
+
+    #[test]
+    fn create_row_follows_the_options() {
+        let items = MenuItems::flat(vec![SelectOption::new("a", "A")])
+            .with_create(true)
+            .with_status(true);
+        assert_eq!(items.rows, vec![MenuRow::Option(0), MenuRow::Create, MenuRow::Status]);
+        assert!(items.has_create());
+        assert_eq!(items.option_row(1), Some(1));
+        assert!(!MenuItems::flat(Vec::new()).with_create(false).has_create());
+    }
+}
@@ -0,0 +1,699 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use super::form_field::{FormField, LabelPlacement};
+use super::validation::ValidationState;
+use crate::theme::*;
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// Events emitted by the Form component
+#[derive(Clone, Debug)]
+pub enum FormEvent {
+    /// All fields are valid; carries the values keyed by field name
+    Submit(Value),
+    /// Submitting failed; carries the names of the invalid fields
+    Invalid(Vec<String>),
+}
+
+impl EventEmitter<FormEvent> for Form {}
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// A component whose value a [`Form`] can read, reset and validate.
+///
+/// Implemented by all form components. Values are JSON: strings for text
+/// inputs, numbers for `NumberInput`, booleans for `Checkbox`, arrays for
+/// multi selects and `CheckboxGroup`, and `null` for an empty selection.
+pub trait FormControl: Render {
+    /// The current value
+    fn form_value(&self) -> Value;
+
+    /// Set the value, e.g. when the form is reset
+    fn set_form_value(&mut self, value: &Value, cx: &mut Context<Self>);
+
+    /// Run the validation rules now and return the resulting state
+    fn validate_form(&mut self, _cx: &mut Context<Self>) -> ValidationState {
+        self.form_validation_state()
+    }
+
+    /// The current validation state
+    fn form_validation_state(&self) -> ValidationState {
+        ValidationState::Unvalidated
+    }
+
+    /// Clear the validation error, e.g. when the form is reset
+    fn clear_form_error(&mut self, _cx: &mut Context<Self>) {}
+
+    /// Focus handle of the control, used to track when it was touched
+    fn form_focus_handle(&self, _cx: &App) -> Option<FocusHandle> {
+        None
+    }
+}
+
+/// State of one field of a [`Form`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct FieldState {
+    /// The value differs from the initial value
+    pub dirty: bool,
+    /// The field lost focus or was changed since the form was created or reset
+    pub touched: bool,
+    /// Validation state of the control
+    pub validation: ValidationState,
+}
+
+/// Label, helper text and required marker of a [`Form`] field.
+///
+/// Strings convert to a plain label: `.field("email", "Email", &email, cx)`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct FieldLabel {
+    /// Label text, `None` for controls with their own label, e.g. a checkbox
+    pub label: Option<SharedString>,
+    /// Helper text below the control
+    pub help: Option<SharedString>,
+    /// Whether to show the required marker
+    pub required: bool,
+}
+
+impl FieldLabel {
+    /// Create a FieldLabel with a label
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: Some(label.into()),
+            ..Self::default()
+        }
+    }
+
+    /// Create a FieldLabel without a label
+    pub fn unlabeled() -> Self {
+        Self::default()
+    }
+
+    /// Set the helper text shown below the control
+    pub fn help(mut self, help: impl Into<SharedString>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Show a required marker after the label
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+}
+
+impl From<&'static str> for FieldLabel {
+    fn from(label: &'static str) -> Self {
+        Self::new(label)
+    }
+}
+
+impl From<String> for FieldLabel {
+    fn from(label: String) -> Self {
+        Self::new(label)
+    }
+}
+
+/// Text of a JSON value for text controls: `null` is empty, strings are
+/// used as is and other values are written as JSON
+pub(crate) fn value_to_string(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Selected values from a JSON value: an array, a single value or `null`
+pub(crate) fn value_to_strings(value: &Value) -> Vec<String> {
+    match value {
+        Value::Null => Vec::new(),
+        Value::Array(values) => values.iter().map(value_to_string).collect(),
+        other => vec![value_to_string(other)],
+    }
+}
+
+/// Type-erased access to a registered control
+trait AnyFormControl {
+    fn value(&self, cx: &App) -> Value;
+    fn set_value(&self, value: &Value, cx: &mut App);
+    fn validate(&self, cx: &mut App) -> ValidationState;
+    fn validation_state(&self, cx: &App) -> ValidationState;
+    fn clear_error(&self, cx: &mut App);
+    fn focus_handle(&self, cx: &App) -> Option<FocusHandle>;
+}
+
+impl<T: FormControl> AnyFormControl for Entity<T> {
+    fn value(&self, cx: &App) -> Value {
+        self.read(cx).form_value()
+    }
+
+    fn set_value(&self, value: &Value, cx: &mut App) {
+        self.update(cx, |control, cx| control.set_form_value(value, cx));
+    }
+
+    fn validate(&self, cx: &mut App) -> ValidationState {
+        self.update(cx, |control, cx| control.validate_form(cx))
+    }
+
+    fn validation_state(&self, cx: &App) -> ValidationState {
+        self.read(cx).form_validation_state()
+    }
+
+    fn clear_error(&self, cx: &mut App) {
+        self.update(cx, |control, cx| control.clear_form_error(cx));
+    }
+
+    fn focus_handle(&self, cx: &App) -> Option<FocusHandle> {
+        self.read(cx).form_focus_handle(cx)
+    }
+}
+
+/// Dirty and touched state of a field, relative to its initial value
+#[derive(Debug, Clone, PartialEq)]
+struct FieldTracker {
+    initial: Value,
+    dirty: bool,
+    touched: bool,
+}
+
+impl FieldTracker {
+    fn new(initial: Value) -> Self {
+        Self {
+            initial,
+            dirty: false,
+            touched: false,
+        }
+    }
+
+    /// The value changed; changing it touches the field, even if it's
+    /// changed back later. Returns whether dirty or touched changed.
+    fn changed(&mut self, value: &Value) -> bool {
+        let before = (self.dirty, self.touched);
+        self.dirty = *value != self.initial;
+        self.touched |= self.dirty;
+        (self.dirty, self.touched) != before
+    }
+
+    /// The field lost focus
+    fn touch(&mut self) {
+        self.touched = true;
+    }
+
+    /// Forget changes; the control gets the initial value back
+    fn reset(&mut self) {
+        self.dirty = false;
+        self.touched = false;
+    }
+
+    /// Make `value` the initial value
+    fn mark_pristine(&mut self, value: Value) {
+        *self = Self::new(value);
+    }
+}
+
+/// Outcome of a submit once validation has settled
+#[derive(Debug, Clone, PartialEq)]
+enum SubmitOutcome {
+    Valid,
+    /// Names of the invalid fields
+    Invalid(Vec<String>),
+}
+
+/// A submit that waits for async validation to settle
+#[derive(Debug, Default)]
+struct PendingSubmit {
+    waiting: bool,
+}
+
+impl PendingSubmit {
+    fn start(&mut self) {
+        self.waiting = true;
+    }
+
+    fn cancel(&mut self) {
+        self.waiting = false;
+    }
+
+    /// The outcome of a started submit once no field is pending, given
+    /// each field's name and validation state. Returns `None` while
+    /// validation is pending or if no submit was started.
+    fn settle<'a>(
+        &mut self,
+        fields: impl IntoIterator<Item = (&'a str, ValidationState)>,
+    ) -> Option<SubmitOutcome> {
+        if !self.waiting {
+            return None;
+        }
+        let fields: Vec<(&str, ValidationState)> = fields.into_iter().collect();
+        if fields.iter().any(|(_, state)| state.is_pending()) {
+            return None;
+        }
+        self.waiting = false;
+
+        let invalid: Vec<String> = fields
+            .iter()
+            .filter(|(_, state)| state.is_invalid())
+            .map(|(name, _)| name.to_string())
+            .collect();
+        Some(if invalid.is_empty() {
+            SubmitOutcome::Valid
+        } else {
+            SubmitOutcome::Invalid(invalid)
+        })
+    }
+}
+
+/// A registered field
+struct FormEntry {
+    name: String,
+    label: FieldLabel,
+    control: Box<dyn AnyFormControl>,
+    view: AnyView,
+    tracker: FieldTracker,
+    _observer: Subscription,
+    /// Registered on render, once the control has a focus handle
+    _blur_subscription: Option<Subscription>,
+}
+
+// ============================================================================
+// Component
+// ============================================================================
+
+/// A form that collects named fields and submits their values together
+///
+/// Each field is a form component entity registered under a name. The form
+/// renders the fields in registration order, each in a [`FormField`] with
+/// its label, helper text and required marker. It tracks whether each one
+/// is dirty or touched, validates them all on [`submit`](Form::submit) and
+/// emits their values as JSON.
+///
+/// Touched tracking needs the form to be rendered, so lay out fields by
+/// registering them rather than placing the controls next to the form.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// #[derive(Deserialize)]
+/// struct Signup { email: String, age: f64, terms: bool }
+///
+/// let email = cx.new(|cx| TextInput::new(cx).placeholder("Email")
+///     .validate(|v| if v.contains('@') { Ok(()) } else { Err("Enter a valid email".into()) }));
+/// let age = cx.new(|cx| NumberInput::new(cx).range(18.0, 120.0));
+/// let terms = cx.new(|cx| Checkbox::new(cx).label("I accept the terms"));
+///
+/// let form = cx.new(|cx| {
+///     Form::new(cx)
+///         .field("email", FieldLabel::new("Email").required(true), &email, cx)
+///         .field("age", FieldLabel::new("Age").help("You must be 18 or older"), &age, cx)
+///         .field("terms", FieldLabel::unlabeled(), &terms, cx)
+/// });
+///
+/// cx.subscribe(&form, |this, form, event: &FormEvent, cx| match event {
+///     FormEvent::Submit(_) => {
+///         let signup: Signup = form.read(cx).values_as(cx).unwrap();
+///     }
+///     FormEvent::Invalid(fields) => println!("Fix: {:?}", fields),
+/// });
+///
+/// // On the submit button
+/// form.update(cx, |form, cx| form.submit(cx));
+/// ```
+pub struct Form {
+    /// Registered fields in display order
+    fields: Vec<FormEntry>,
+    /// A submit waiting for async validation to complete
+    pending_submit: PendingSubmit,
+    /// Where fields place their labels
+    label_placement: LabelPlacement,
+    /// Width of the label column with horizontal placement
+    label_width: Option<Pixels>,
+}
+
+impl Form {
+    /// Create an empty Form
+    pub fn new(_cx: &mut Context<Self>) -> Self {
+        Self {
+            fields: Vec::new(),
+            pending_submit: PendingSubmit::default(),
+            label_placement: LabelPlacement::Vertical,
+            label_width: None,
+        }
+    }
+
+    /// Register a field under `name` with its label; its current value
+    /// becomes the initial value
+    pub fn field<T: FormControl>(
+        mut self,
+        name: impl Into<String>,
+        label: impl Into<FieldLabel>,
+        control: &Entity<T>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        self.add_field(name, label, control, cx);
+        self
+    }
+
+    /// Set where fields place their labels (default: above the control)
+    pub fn label_placement(mut self, placement: LabelPlacement) -> Self {
+        self.label_placement = placement;
+        self
+    }
+
+    /// Set the width of the label column with horizontal placement
+    pub fn label_width(mut self, width: Pixels) -> Self {
+        self.label_width = Some(width);
+        self
+    }
+
+    /// Register a field (mutable reference version for use in update closures).
+    ///
+    /// Replaces a field already registered under the same name.
+    pub fn add_field<T: FormControl>(
+        &mut self,
+        name: impl Into<String>,
+        label: impl Into<FieldLabel>,
+        control: &Entity<T>,
+        cx: &mut Context<Self>,
+    ) {
+        let name = name.into();
+        let observer = cx.observe(control, {
+            let name = name.clone();
+            move |this, control, cx| this.field_changed(&name, control.read(cx).form_value(), cx)
+        });
+        let entry = FormEntry {
+            name: name.clone(),
+            label: label.into(),
+            tracker: FieldTracker::new(control.read(cx).form_value()),
+            control: Box::new(control.clone()),
+            view: control.clone().into(),
+            _observer: observer,
+            _blur_subscription: None,
+        };
+        match self.fields.iter_mut().find(|field| field.name == name) {
+            Some(field) => *field = entry,
+            None => self.fields.push(entry),
+        }
+        cx.notify();
+    }
+
+    /// Unregister a field
+    pub fn remove_field(&mut self, name: &str, cx: &mut Context<Self>) {
+        self.fields.retain(|field| field.name != name);
+        cx.notify();
+    }
+
+    /// The values of all fields as a JSON object keyed by field name
+    pub fn values(&self, cx: &App) -> Value {
+        let values: Map<String, Value> = self
+            .fields
+            .iter()
+            .map(|field| (field.name.clone(), field.control.value(cx)))
+            .collect();
+        Value::Object(values)
+    }
+
+    /// The values deserialized into a struct with one member per field
+    pub fn values_as<T: DeserializeOwned>(&self, cx: &App) -> Result<T, serde_json::Error> {
+        serde_json::from_value(self.values(cx))
+    }
+
+    /// The value of one field
+    pub fn value(&self, name: &str, cx: &App) -> Option<Value> {
+        self.entry(name).map(|field| field.control.value(cx))
+    }
+
+    /// The dirty, touched and validation state of one field
+    pub fn field_state(&self, name: &str, cx: &App) -> Option<FieldState> {
+        self.entry(name).map(|field| FieldState {
+            dirty: field.tracker.dirty,
+            touched: field.tracker.touched,
+            validation: field.control.validation_state(cx),
+        })
+    }
+
+    /// Whether any field differs from its initial value
+    pub fn is_dirty(&self) -> bool {
+        self.fields.iter().any(|field| field.tracker.dirty)
+    }
+
+    /// Whether any field was touched
+    pub fn is_touched(&self) -> bool {
+        self.fields.iter().any(|field| field.tracker.touched)
+    }
+
+    /// Whether no field currently shows a validation error.
+    ///
+    /// This doesn't run validation; [`submit`](Self::submit) does.
+    pub fn is_valid(&self, cx: &App) -> bool {
+        !self
+            .fields
+            .iter()
+            .any(|field| field.control.validation_state(cx).is_invalid())
+    }
+
+    /// Validate all fields and emit `Submit` with the values, or `Invalid`.
+    ///
+    /// If async rules are still running, the event is emitted once they
+    /// complete.
+    pub fn submit(&mut self, cx: &mut Context<Self>) {
+        for field in &mut self.fields {
+            field.control.validate(cx);
+            field.tracker.touch();
+        }
+        self.pending_submit.start();
+        self.finish_submit(cx);
+        cx.notify();
+    }
+
+    /// Restore the initial values and clear dirty, touched and errors
+    pub fn reset(&mut self, cx: &mut Context<Self>) {
+        for field in &mut self.fields {
+            field.control.set_value(&field.tracker.initial, cx);
+            field.control.clear_error(cx);
+            field.tracker.reset();
+        }
+        self.pending_submit.cancel();
+        cx.notify();
+    }
+
+    /// Make the current values the initial ones, e.g. after saving them
+    pub fn mark_pristine(&mut self, cx: &mut Context<Self>) {
+        for field in &mut self.fields {
+            field.tracker.mark_pristine(field.control.value(cx));
+        }
+        cx.notify();
+    }
+
+    fn entry(&self, name: &str) -> Option<&FormEntry> {
+        self.fields.iter().find(|field| field.name == name)
+    }
+
+    /// Called whenever a control notifies, which includes cursor blinks,
+    /// so the form only re-renders when its own state changed
+    fn field_changed(&mut self, name: &str, value: Value, cx: &mut Context<Self>) {
+        let changed = self
+            .fields
+            .iter_mut()
+            .find(|field| field.name == name)
+            .is_some_and(|field| field.tracker.changed(&value));
+        let submitted = self.finish_submit(cx);
+        if changed || submitted {
+            cx.notify();
+        }
+    }
+
+    /// Emit the result of a submit once no async validation is pending.
+    /// Returns whether it was emitted.
+    fn finish_submit(&mut self, cx: &mut Context<Self>) -> bool {
+        let states = self
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.control.validation_state(cx)));
+        match self.pending_submit.settle(states) {
+            Some(SubmitOutcome::Valid) => cx.emit(FormEvent::Submit(self.values(cx))),
+            Some(SubmitOutcome::Invalid(fields)) => cx.emit(FormEvent::Invalid(fields)),
+            None => return false,
+        }
+        true
+    }
+
+    /// Mark fields touched when they lose focus
+    fn subscribe_blur(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        for index in 0..self.fields.len() {
+            if self.fields[index]._blur_subscription.is_some() {
+                continue;
+            }
+            // Some controls only create their focus handle when first rendered
+            let Some(focus_handle) = self.fields[index].control.focus_handle(cx) else {
+                continue;
+            };
+            let name = self.fields[index].name.clone();
+            let subscription = cx.on_blur(&focus_handle, window, move |this, _window, cx| {
+                if let Some(field) = this.fields.iter_mut().find(|field| field.name == name) {
+                    field.tracker.touch();
+                    cx.notify();
+                }
+            });
+            self.fields[index]._blur_subscription = Some(subscription);
+        }
+    }
+}
+
+// ============================================================================
+// Render
+// ============================================================================
+
+impl Render for Form {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        self.subscribe_blur(window, cx);
+        let theme = cx.theme().clone();
+
+        let fields: Vec<FormField> = self
+            .fields
+            .iter()
+            .map(|field| {
+                let label = &field.label;
+                let form_field = match &label.label {
+                    Some(text) => FormField::new(text.clone()),
+                    None => FormField::unlabeled(),
+                };
+                form_field
+                    .required(label.required)
+                    .placement(self.label_placement)
+                    .when_some(self.label_width, |this, width| this.label_width(width))
+                    .when_some(label.help.clone(), |this, help| this.help(help))
+                    .when_some(field.control.focus_handle(cx), |this, handle| {
+                        this.focus_handle(handle)
+                    })
+                    .child(field.view.clone())
+            })
+            .collect();
+
+        div()
+            .id("form")
+            .flex()
+            .flex_col()
+            .gap(px(theme.spacing.lg))
+            .w_full()
+            .children(fields)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{value_to_string, value_to_strings, FieldTracker, PendingSubmit, SubmitOutcome};
+    use crate::components::form::validation::ValidationState;
+    use serde_json::json;
+
+    #[test]
+    fn json_values_convert_to_control_text() {
+        assert_eq!(value_to_string(&json!(null)), "");
+        assert_eq!(value_to_string(&json!("a@b.c")), "a@b.c");
+        assert_eq!(value_to_string(&json!(42)), "42");
+    }
+
+    #[test]
+    fn json_values_convert_to_selections() {
+        assert!(value_to_strings(&json!(null)).is_empty());
+        assert_eq!(value_to_strings(&json!("vue")), vec!["vue"]);
+        assert_eq!(value_to_strings(&json!(["a", 1])), vec!["a", "1"]);
+    }
+
+    #[test]
+    fn changes_make_fields_dirty_and_touched() {
+        let mut field = FieldTracker::new(json!("a"));
+        assert!(!field.dirty && !field.touched);
+
+        assert!(field.changed(&json!("ab")));
+        assert!(field.dirty && field.touched);
+        // Notifies without a new value, e.g. a cursor blink, change nothing
+        assert!(!field.changed(&json!("ab")));
+
+        // Back to the initial value: no longer dirty, but still touched
+        assert!(field.changed(&json!("a")));
+        assert!(!field.dirty && field.touched);
+        assert!(!field.changed(&json!("a")));
+
+        let mut blurred = FieldTracker::new(json!(1));
+        blurred.touch();
+        assert!(!blurred.dirty && blurred.touched);
+    }
+
+    #[test]
+    fn reset_and_mark_pristine_clear_the_state() {
+        let mut field = FieldTracker::new(json!(false));
+        field.changed(&json!(true));
+        field.reset();
+        assert_eq!(field, FieldTracker::new(json!(false)));
+
+        field.changed(&json!(true));
+        field.mark_pristine(json!(true));
+        assert_eq!(field, FieldTracker::new(json!(true)));
+        field.changed(&json!(false));
+        assert!(field.dirty);
+    }
+
+    #[test]
+    fn submit_reports_valid_or_the_invalid_fields() {
+        let mut submit = PendingSubmit::default();
+        let valid = [
+            ("email", ValidationState::Valid),
+            ("terms", ValidationState::Unvalidated),
+        ];
+        // Nothing was submitted
+        assert_eq!(submit.settle(valid.clone()), None);
+
+        submit.start();
+        assert_eq!(submit.settle(valid.clone()), Some(SubmitOutcome::Valid));
+        // Each submit settles once
+        assert_eq!(submit.settle(valid), None);
+
+        submit.start();
+        let invalid = [
+            (
+                "email",
+                ValidationState::Invalid("Enter a valid email".into()),
+            ),
+            ("age", ValidationState::Valid),
+            ("name", ValidationState::Invalid("Required".into())),
+        ];
+        assert_eq!(
+            submit.settle(invalid),
+            Some(SubmitOutcome::Invalid(vec!["email".into(), "name".into()]))
+        );
+    }
+
+    #[test]
+    fn submit_waits_for_pending_async_validation() {
+        let mut submit = PendingSubmit::default();
+        submit.start();
+        assert_eq!(
+            submit.settle([
+                ("email", ValidationState::Valid),
+                ("name", ValidationState::Pending)
+            ]),
+            None
+        );
+        // The async rule completes
+        assert_eq!(
+            submit.settle([
+                ("email", ValidationState::Valid),
+                ("name", ValidationState::Invalid("Taken".into())),
+            ]),
+            Some(SubmitOutcome::Invalid(vec!["name".into()]))
+        );
+
+        // A reset drops a waiting submit
+        submit.start();
+        assert_eq!(submit.settle([("name", ValidationState::Pending)]), None);
+        submit.cancel();
+        assert_eq!(submit.settle([("name", ValidationState::Valid)]), None);
+    }
+}
@@ -0,0 +1,215 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use crate::theme::*;
+
+/// Default width of the label column with [`LabelPlacement::Horizontal`]
+const DEFAULT_LABEL_WIDTH: f32 = 120.0;
+
+// ============================================================================
+// Types
+// ============================================================================
+
+/// Where a [`FormField`] places its label
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum LabelPlacement {
+    /// Label above the control (default)
+    #[default]
+    Vertical,
+    /// Label in a fixed-width column left of the control
+    Horizontal,
+}
+
+// ============================================================================
+// Component
+// ============================================================================
+
+/// A label, helper text and error text around a form control
+///
+/// Clicking the label focuses the control, if its focus handle is set.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let email = cx.new(|cx| TextInput::new(cx).placeholder("you@example.com"));
+///
+/// // In render
+/// FormField::new("Email")
+///     .required(true)
+///     .help("We never share your email")
+///     .focus_handle(email.focus_handle(cx))
+///     .child(email.clone())
+/// ```
+#[derive(IntoElement)]
+pub struct FormField {
+    /// Label text
+    label: Option<SharedString>,
+    /// Helper text below the control
+    help: Option<SharedString>,
+    /// Error text below the control, shown instead of the helper text
+    error: Option<SharedString>,
+    /// Whether to show the required marker
+    required: bool,
+    /// Label placement
+    placement: LabelPlacement,
+    /// Width of the label column with horizontal placement
+    label_width: Pixels,
+    /// Focused when the label is clicked
+    focus_handle: Option<FocusHandle>,
+    /// The control
+    child: Option<AnyElement>,
+}
+
+impl FormField {
+    /// Create a FormField with a label
+    pub fn new(label: impl Into<SharedString>) -> Self {
+        Self {
+            label: Some(label.into()),
+            help: None,
+            error: None,
+            required: false,
+            placement: LabelPlacement::Vertical,
+            label_width: px(DEFAULT_LABEL_WIDTH),
+            focus_handle: None,
+            child: None,
+        }
+    }
+
+    /// Create a FormField without a label, e.g. for a checkbox with its own label
+    pub fn unlabeled() -> Self {
+        Self {
+            label: None,
+            ..Self::new("")
+        }
+    }
+
+    /// Set the control
+    pub fn child(mut self, child: impl IntoElement) -> Self {
+        self.child = Some(child.into_any_element());
+        self
+    }
+
+    /// Set the helper text shown below the control
+    pub fn help(mut self, help: impl Into<SharedString>) -> Self {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Set the error text, shown in place of the helper text.
+    ///
+    /// Controls with validation rules already show their own errors; use this
+    /// for other errors, e.g. one returned by a server.
+    pub fn error(mut self, error: impl Into<SharedString>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+
+    /// Show a required marker after the label
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Set the label placement
+    pub fn placement(mut self, placement: LabelPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    /// Place the label left of the control (convenience method)
+    pub fn horizontal(mut self) -> Self {
+        self.placement = LabelPlacement::Horizontal;
+        self
+    }
+
+    /// Place the label above the control (convenience method)
+    pub fn vertical(mut self) -> Self {
+        self.placement = LabelPlacement::Vertical;
+        self
+    }
+
+    /// Set the width of the label column with horizontal placement
+    pub fn label_width(mut self, width: Pixels) -> Self {
+        self.label_width = width;
+        self
+    }
+
+    /// Set the focus handle of the control, focused when the label is clicked
+    pub fn focus_handle(mut self, focus_handle: FocusHandle) -> Self {
+        self.focus_handle = Some(focus_handle);
+        self
+    }
+
+    fn render_label(
+        label: SharedString,
+        required: bool,
+        focus_handle: Option<FocusHandle>,
+        theme: &Theme,
+    ) -> impl IntoElement {
+        div()
+            .flex()
+            .gap(px(theme.spacing.xxs))
+            .text_size(theme.font_size(ComponentSize::Medium))
+            .font_weight(FontWeight(theme.typography.font_weight_medium))
+            .text_color(theme.colors.text)
+            .child(label)
+            .when(required, |this| {
+                this.child(div().text_color(theme.colors.error).child("*"))
+            })
+            .when_some(focus_handle, |this, focus_handle| {
+                this.cursor(CursorStyle::PointingHand)
+                    .on_mouse_down(MouseButton::Left, move |_event, window, _cx| {
+                        focus_handle.focus(window);
+                    })
+            })
+    }
+}
+
+// ============================================================================
+// Render
+// ============================================================================
+
+impl RenderOnce for FormField {
+    fn render(self, _window: &mut Window, cx: &mut App) -> impl IntoElement {
+        let theme = cx.theme().clone();
+        let horizontal = self.placement == LabelPlacement::Horizontal;
+
+        let label = self
+            .label
+            .map(|label| Self::render_label(label, self.required, self.focus_handle, &theme));
+
+        // Errors replace the helper text
+        let message = match (self.error, self.help) {
+            (Some(error), _) => Some((error, theme.colors.error)),
+            (None, Some(help)) => Some((help, theme.colors.text_secondary)),
+            (None, None) => None,
+        };
+
+        let control = div()
+            .flex()
+            .flex_col()
+            .flex_1()
+            .min_w_0()
+            .children(self.child)
+            .when_some(message, |this, (text, color)| {
+                this.child(div().mt(px(theme.spacing.xxs)).text_size(theme.font_size(ComponentSize::Small)).text_color(color).child(text))
+            });
+
+        let field = div().flex().w_full();
+        let field = if horizontal {
+            field.flex_row().items_start().gap(px(theme.spacing.md)).child(
+                // Align the label with the text of a single-line control
+                div()
+                    .flex_none()
+                    .w(self.label_width)
+                    .min_h(theme.height(ComponentSize::Medium))
+                    .flex()
+                    .items_center()
+                    .children(label),
+            )
+        } else {
+            field.flex_col().gap(px(theme.spacing.xs)).children(label)
+        };
+
+        field.child(control)
+    }
+}
@@ -0,0 +1,147 @@
+// Scored fuzzy matching of option labels, used by Combobox filtering
+
+use std::ops::Range;
+
+/// Score of each matched character
+const MATCH: i32 = 16;
+/// Bonus when a character matches at the start of the text
+const PREFIX_BONUS: i32 = 16;
+/// Bonus when a character matches at the start of a word, e.g. after `-` or in camelCase
+const WORD_START_BONUS: i32 = 12;
+/// Bonus when a character directly follows the previous match
+const CONSECUTIVE_BONUS: i32 = 8;
+/// Bonus when a character matches with the same case
+const CASE_BONUS: i32 = 1;
+/// Penalty per skipped character between two matches
+const GAP_PENALTY: i32 = 1;
+
+/// A fuzzy match of a query in a text
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FuzzyMatch {
+    /// Higher is better
+    pub score: i32,
+    /// Byte ranges of the matched characters, merged where adjacent
+    pub ranges: Vec<Range<usize>>,
+}
+
+/// Match `query` as a case-insensitive subsequence of `text`.
+///
+/// Returns the best scoring match, or `None` if some query character can't
+/// be found in order. An empty query matches everything with score 0.
+pub(crate) fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query.chars().collect();
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch { score: 0, ranges: Vec::new() });
+    }
+    if query.len() > chars.len() {
+        return None;
+    }
+
+    // best[i][j]: best score with query[i] matched at chars[j], and where
+    // query[i - 1] was matched for backtracking
+    let mut best: Vec<Vec<Option<(i32, usize)>>> = vec![vec![None; chars.len()]; query.len()];
+    for (i, &q) in query.iter().enumerate() {
+        for (j, &(_, c)) in chars.iter().enumerate() {
+            if !eq_ignore_case(q, c) {
+                continue;
+            }
+            let score = char_score(q, &chars, j);
+            if i == 0 {
+                best[i][j] = Some((score, 0));
+                continue;
+            }
+            best[i][j] = (0..j)
+                .filter_map(|k| {
+                    let (prev, _) = best[i - 1][k]?;
+                    let link = if k + 1 == j {
+                        CONSECUTIVE_BONUS
+                    } else {
+                        -GAP_PENALTY * (j - k - 1) as i32
+                    };
+                    Some((prev + link + score, k))
+                })
+                // Ties go to the earliest previous match
+                .max_by_key(|&(score, k)| (score, std::cmp::Reverse(k)));
+        }
+    }
+
+    let last = query.len() - 1;
+    let (mut j, score) = best[last]
+        .iter()
+        .enumerate()
+        .filter_map(|(j, entry)| entry.map(|(score, _)| (j, score)))
+        .max_by_key(|&(j, score)| (score, std::cmp::Reverse(j)))?;
+
+    let mut positions = vec![j; query.len()];
+    for i in (1..query.len()).rev() {
+        j = best[i][j].map(|(_, k)| k)?;
+        positions[i - 1] = j;
+    }
+
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for j in positions {
+        let (start, c) = chars[j];
+        let end = start + c.len_utf8();
+        match ranges.last_mut() {
+            Some(range) if range.end == start => range.end = end,
+            _ => ranges.push(start..end),
+        }
+    }
+    Some(FuzzyMatch { score, ranges })
+}
+
+fn eq_ignore_case(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Score of matching query character `q` at `chars[j]`, without neighbours
+fn char_score(q: char, chars: &[(usize, char)], j: usize) -> i32 {
+    let c = chars[j].1;
+    let mut score = MATCH;
+    if j == 0 {
+        score += PREFIX_BONUS;
+    } else {
+        let prev = chars[j - 1].1;
+        let word_start = !prev.is_alphanumeric()
+            || (prev.is_lowercase() && c.is_uppercase())
+            || (prev.is_alphabetic() && c.is_numeric());
+        if word_start {
+            score += WORD_START_BONUS;
+        }
+    }
+    if q == c {
+        score += CASE_BONUS;
+    }
+    score
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fuzzy_match;
+
+    #[test]
+    fn matches_subsequences_and_reports_ranges() {
+        let m = fuzzy_match("g4o", "GPT-4o").unwrap();
+        assert_eq!(m.ranges, vec![0..1, 4..6]);
+        assert!(fuzzy_match("g4o", "Claude 3").is_none());
+        assert!(fuzzy_match("ab", "a").is_none());
+        assert_eq!(fuzzy_match("", "any").unwrap().score, 0);
+        // Multi-byte characters get byte ranges
+        assert_eq!(fuzzy_match("é", "café").unwrap().ranges, vec![3..5]);
+    }
+
+    #[test]
+    fn ranks_prefix_word_starts_and_case() {
+        let score = |query, text| fuzzy_match(query, text).unwrap().score;
+        // Prefix beats a match in the middle
+        assert!(score("gpt", "gpt-4o") > score("gpt", "chatgpt"));
+        // Word starts beat scattered letters
+        assert!(score("cs", "claude-sonnet") > score("cs", "chess"));
+        assert!(score("gT", "getTime") > score("gT", "gotta"));
+        // Consecutive letters beat gaps
+        assert!(score("son", "sonnet") > score("son", "season"));
+        // Same case breaks ties
+        assert!(score("A", "Apple") > score("A", "apple"));
+    }
+}
@@ -0,0 +1,119 @@
+// Undo/redo history shared by TextInput and TextArea
+
+use std::time::{Duration, Instant};
+
+/// Maximum number of undo steps kept per editor
+const MAX_HISTORY: usize = 100;
+
+/// Typing pauses longer than this start a new undo step
+const GROUP_INTERVAL: Duration = Duration::from_millis(1000);
+
+/// The kind of edit being recorded, used to group consecutive edits
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum EditKind {
+    /// Typed text, grouped with adjacent typing
+    Insert,
+    /// Backspace/delete of a single character, grouped with adjacent deletes
+    Delete,
+    /// IME composition, recorded once when composition starts
+    Compose,
+    /// Paste, cut, programmatic changes: always a separate step
+    Other,
+}
+
+/// Editor state captured before an edit.
+///
+/// Positions are byte offsets; `selection` is `(anchor, cursor)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct EditSnapshot {
+    pub value: String,
+    pub cursor: usize,
+    pub selection: Option<(usize, usize)>,
+}
+
+/// Undo and redo stacks for a text editor
+#[derive(Default)]
+pub(crate) struct EditHistory {
+    undo_stack: Vec<EditSnapshot>,
+    redo_stack: Vec<EditSnapshot>,
+    /// Kind, time and resulting cursor of the last recorded edit, for grouping
+    last_edit: Option<(EditKind, Instant, usize)>,
+}
+
+impl EditHistory {
+    /// Record the state before an edit.
+    ///
+    /// `cursor_after` is where the cursor ends up after the edit. Typing or
+    /// deleting that continues from the previous edit's cursor, within
+    /// [`GROUP_INTERVAL`], extends the current undo step instead of starting
+    /// a new one. Typing whitespace after a word also starts a new step.
+    pub fn record(&mut self, kind: EditKind, before: EditSnapshot, inserted: &str, cursor_after: usize) {
+        let now = Instant::now();
+        let continues = match self.last_edit {
+            Some((last_kind, at, last_cursor)) => {
+                last_kind == kind
+                    && matches!(kind, EditKind::Insert | EditKind::Delete)
+                    && before.selection.is_none()
+                    && before.cursor == last_cursor
+                    && now.duration_since(at) < GROUP_INTERVAL
+                    && !(kind == EditKind::Insert && inserted.chars().all(char::is_whitespace))
+            }
+            None => false,
+        };
+
+        if !continues {
+            self.undo_stack.push(before);
+            if self.undo_stack.len() > MAX_HISTORY {
+                self.undo_stack.remove(0);
+            }
+        }
+        self.redo_stack.clear();
+        self.last_edit = Some((kind, now, cursor_after));
+    }
+
+    /// Update the grouping cursor without recording, e.g. while composing
+    pub fn touch(&mut self, cursor_after: usize) {
+        if let Some((_, at, cursor)) = self.last_edit.as_mut() {
+            *at = Instant::now();
+            *cursor = cursor_after;
+        }
+    }
+
+    /// End the current group so the next edit starts a new undo step
+    pub fn break_group(&mut self) {
+        self.last_edit = None;
+    }
+
+    /// Drop the last undo step if it didn't change the value,
+    /// e.g. a cancelled IME composition
+    pub fn discard_if_unchanged(&mut self, value: &str) {
+        if self.undo_stack.last().is_some_and(|snapshot| snapshot.value == value) {
+            self.undo_stack.pop();
+            self.last_edit = None;
+        }
+    }
+
+    /// Pop the previous state, pushing `current` onto the redo stack
+    pub fn undo(&mut self, current: EditSnapshot) -> Option<EditSnapshot> {
+        let snapshot = self.undo_stack.pop()?;
+        self.redo_stack.push(current);
+        self.last_edit = None;
+        Some(snapshot)
+    }
+
+    /// Pop the next state, pushing `current` onto the undo stack
+    pub fn redo(&mut self, current: EditSnapshot) -> Option<EditSnapshot> {
+        let snapshot = self.redo_stack.pop()?;
+        self.undo_stack.push(current);
+        self.last_edit = None;
+        Some(snapshot)
+    }
+
+    pub fn can_undo(&self) -> bool {
+        !self.undo_stack.is_empty()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        !self.redo_stack.is_empty()
+    }
+}
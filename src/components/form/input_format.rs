@@ -0,0 +1,409 @@
+// Input masks and live formatters for TextInput
+
+/// Reformats the text of a [`TextInput`](super::TextInput) as the user types.
+///
+/// After every edit the input passes its text and cursor to [`format`],
+/// which returns the text to display and where the cursor belongs in it.
+/// [`unformat`] recovers the raw value, e.g. the digits of a phone number.
+///
+/// [`format`]: InputFormatter::format
+/// [`unformat`]: InputFormatter::unformat
+///
+/// # Examples
+///
+/// An upper-case formatter:
+///
+/// ```
+/// use fluix::InputFormatter;
+///
+/// struct Uppercase;
+///
+/// impl InputFormatter for Uppercase {
+///     fn format(&self, text: &str, cursor: usize) -> (String, usize) {
+///         // Upper-casing ASCII keeps byte offsets unchanged
+///         (text.to_ascii_uppercase(), cursor)
+///     }
+///
+///     fn unformat(&self, text: &str) -> String {
+///         text.to_string()
+///     }
+/// }
+///
+/// assert_eq!(Uppercase.format("abc", 2), ("ABC".to_string(), 2));
+/// ```
+pub trait InputFormatter: 'static {
+    /// Reformat edited text.
+    ///
+    /// `cursor` is a byte offset into `text`; the returned offset is the
+    /// cursor position in the formatted text. `text` may contain leftover
+    /// formatting characters, misplaced or invalid input, which should be
+    /// dropped or moved to where they belong.
+    fn format(&self, text: &str, cursor: usize) -> (String, usize);
+
+    /// The raw value of formatted text, without literals or placeholders
+    fn unformat(&self, text: &str) -> String;
+
+    /// Placeholder to show while the input is empty, if the input has none
+    fn placeholder(&self) -> Option<String> {
+        None
+    }
+}
+
+/// One position of an [`InputMask`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum MaskToken {
+    Digit,
+    Letter,
+    Alphanumeric,
+    Literal(char),
+}
+
+impl MaskToken {
+    fn accepts(self, c: char) -> bool {
+        match self {
+            MaskToken::Digit => c.is_ascii_digit(),
+            MaskToken::Letter => c.is_alphabetic(),
+            MaskToken::Alphanumeric => c.is_alphanumeric(),
+            MaskToken::Literal(_) => false,
+        }
+    }
+}
+
+/// A fixed-format input mask such as `(999) 999-9999`.
+///
+/// Pattern characters:
+///
+/// - `9` - a digit
+/// - `a` - a letter
+/// - `*` - a letter or digit
+/// - `\` - the next character is a literal
+///
+/// Everything else is a literal that is inserted automatically. Slots that
+/// have not been filled yet show a placeholder character (`_` by default),
+/// and characters that don't fit the next slot are rejected.
+///
+/// # Examples
+///
+/// ```
+/// use fluix::{InputFormatter, InputMask};
+///
+/// let mask = InputMask::phone();
+/// assert_eq!(mask.format("555123", 6), ("(555) 123-____".to_string(), 9));
+/// assert_eq!(mask.unformat("(555) 123-____"), "555123");
+/// assert_eq!(mask.template(), "(___) ___-____");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InputMask {
+    tokens: Vec<MaskToken>,
+    placeholder: char,
+}
+
+impl InputMask {
+    /// Create a mask from a pattern
+    pub fn new(pattern: &str) -> Self {
+        let mut tokens = Vec::new();
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            tokens.push(match c {
+                '9' => MaskToken::Digit,
+                'a' => MaskToken::Letter,
+                '*' => MaskToken::Alphanumeric,
+                '\\' => MaskToken::Literal(chars.next().unwrap_or('\\')),
+                c => MaskToken::Literal(c),
+            });
+        }
+        Self {
+            tokens,
+            placeholder: '_',
+        }
+    }
+
+    /// US phone number: `(999) 999-9999`
+    pub fn phone() -> Self {
+        Self::new("(999) 999-9999")
+    }
+
+    /// ISO 8601 date: `9999-99-99`
+    pub fn date() -> Self {
+        Self::new("9999-99-99")
+    }
+
+    /// 16-digit card number in groups of four: `9999 9999 9999 9999`
+    pub fn credit_card() -> Self {
+        Self::new("9999 9999 9999 9999")
+    }
+
+    /// Dotted IPv4 address with three digits per octet: `999.999.999.999`.
+    ///
+    /// Use [`Ipv4Formatter`] to accept octets of any length.
+    pub fn ipv4() -> Self {
+        Self::new("999.999.999.999")
+    }
+
+    /// Set the character shown in unfilled slots
+    pub fn placeholder_char(mut self, placeholder: char) -> Self {
+        self.placeholder = placeholder;
+        self
+    }
+
+    /// The mask with every slot unfilled, e.g. `(___) ___-____`
+    pub fn template(&self) -> String {
+        self.tokens
+            .iter()
+            .map(|token| match token {
+                MaskToken::Literal(c) => *c,
+                _ => self.placeholder,
+            })
+            .collect()
+    }
+
+    /// Whether every slot of the mask is filled in `text`
+    pub fn is_complete(&self, text: &str) -> bool {
+        let slots = self
+            .tokens
+            .iter()
+            .filter(|token| !matches!(token, MaskToken::Literal(_)))
+            .count();
+        self.unformat(text).chars().count() == slots
+    }
+
+    /// Fit `text` into the mask, returning the formatted text, the raw
+    /// value and the new cursor position
+    fn apply(&self, text: &str, cursor: usize) -> (String, String, usize) {
+        let mut formatted = String::new();
+        let mut raw = String::new();
+        let mut new_cursor = None;
+        let mut next = 0;
+
+        for (idx, c) in text.char_indices() {
+            if idx >= cursor && new_cursor.is_none() {
+                new_cursor = Some(formatted.len());
+            }
+            if next == self.tokens.len() {
+                break;
+            }
+
+            // Literals between here and the next slot
+            let slot = self.tokens[next..]
+                .iter()
+                .position(|token| !matches!(token, MaskToken::Literal(_)))
+                .map_or(self.tokens.len(), |offset| next + offset);
+            let literal = (next..slot).find(|idx| self.tokens[*idx] == MaskToken::Literal(c));
+
+            let end = match literal {
+                // A literal typed or left over at its place
+                Some(idx) => idx + 1,
+                None if slot < self.tokens.len() && self.tokens[slot].accepts(c) => {
+                    raw.push(c);
+                    slot + 1
+                }
+                // Placeholders, misplaced literals and rejected input
+                _ => continue,
+            };
+            for token in &self.tokens[next..end] {
+                formatted.push(match token {
+                    MaskToken::Literal(literal) => *literal,
+                    _ => c,
+                });
+            }
+            next = end;
+        }
+
+        if raw.is_empty() {
+            return (String::new(), raw, 0);
+        }
+        let new_cursor = new_cursor.unwrap_or(formatted.len());
+        for token in &self.tokens[next..] {
+            formatted.push(match token {
+                MaskToken::Literal(literal) => *literal,
+                _ => self.placeholder,
+            });
+        }
+        (formatted, raw, new_cursor)
+    }
+}
+
+impl InputFormatter for InputMask {
+    fn format(&self, text: &str, cursor: usize) -> (String, usize) {
+        let (formatted, _, cursor) = self.apply(text, cursor);
+        (formatted, cursor)
+    }
+
+    fn unformat(&self, text: &str) -> String {
+        self.apply(text, 0).1
+    }
+
+    fn placeholder(&self) -> Option<String> {
+        Some(self.template())
+    }
+}
+
+/// Live formatter for dotted IPv4 addresses.
+///
+/// Accepts digits and dots, starts a new octet after three digits or when
+/// an octet would exceed 255, and stops after four octets. The raw value is
+/// the address itself.
+///
+/// ```
+/// use fluix::{InputFormatter, Ipv4Formatter};
+///
+/// assert_eq!(Ipv4Formatter.format("19216801", 8).0, "192.168.0.1");
+/// assert_eq!(Ipv4Formatter.format("10..0.x1", 8).0, "10.0.1");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Ipv4Formatter;
+
+impl InputFormatter for Ipv4Formatter {
+    fn format(&self, text: &str, cursor: usize) -> (String, usize) {
+        let mut formatted = String::new();
+        let mut new_cursor = None;
+        let mut octets = 1;
+        let mut digits = 0;
+        let mut value = 0;
+
+        for (idx, c) in text.char_indices() {
+            if idx >= cursor && new_cursor.is_none() {
+                new_cursor = Some(formatted.len());
+            }
+            match c {
+                '0'..='9' => {
+                    let digit = c as u32 - '0' as u32;
+                    // A leading zero is a complete octet
+                    let full = digits == 3 || (digits == 1 && value == 0);
+                    if full || value * 10 + digit > 255 {
+                        if octets == 4 {
+                            continue;
+                        }
+                        formatted.push('.');
+                        octets += 1;
+                        digits = 0;
+                        value = 0;
+                    }
+                    formatted.push(c);
+                    digits += 1;
+                    value = value * 10 + digit;
+                }
+                '.' if digits > 0 && octets < 4 => {
+                    formatted.push('.');
+                    octets += 1;
+                    digits = 0;
+                    value = 0;
+                }
+                _ => {}
+            }
+        }
+
+        let new_cursor = new_cursor.unwrap_or(formatted.len());
+        (formatted, new_cursor)
+    }
+
+    fn unformat(&self, text: &str) -> String {
+        self.format(text, 0).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{InputFormatter, InputMask, Ipv4Formatter};
+
+    /// Insert `typed` at `cursor` and format, as TextInput does on typing
+    fn type_at(
+        formatter: &dyn InputFormatter,
+        text: &str,
+        cursor: usize,
+        typed: &str,
+    ) -> (String, usize) {
+        let mut edited = text.to_string();
+        edited.insert_str(cursor, typed);
+        formatter.format(&edited, cursor + typed.len())
+    }
+
+    /// Type `input` one character at a time into an empty field
+    fn type_all(formatter: &dyn InputFormatter, input: &str) -> (String, usize) {
+        let mut state = (String::new(), 0);
+        for c in input.chars() {
+            state = type_at(formatter, &state.0, state.1, &c.to_string());
+        }
+        state
+    }
+
+    #[test]
+    fn mask_fills_slots_and_inserts_literals() {
+        let mask = InputMask::phone();
+        assert_eq!(type_all(&mask, "5"), ("(5__) ___-____".to_string(), 2));
+        assert_eq!(type_all(&mask, "5551"), ("(555) 1__-____".to_string(), 7));
+        assert_eq!(type_all(&mask, "5551234567").0, "(555) 123-4567");
+        // Input past the last slot is dropped
+        assert_eq!(type_all(&mask, "55512345678").0, "(555) 123-4567");
+    }
+
+    #[test]
+    fn mask_rejects_characters_that_do_not_fit() {
+        let mask = InputMask::phone();
+        assert_eq!(type_all(&mask, "5a5"), ("(55_) ___-____".to_string(), 3));
+        assert_eq!(type_all(&mask, "x"), (String::new(), 0));
+
+        let plate = InputMask::new("aaa-999");
+        assert_eq!(type_all(&plate, "ab1c2").0, "abc-2__");
+    }
+
+    #[test]
+    fn mask_keeps_the_cursor_in_place_when_editing_the_middle() {
+        let mask = InputMask::date();
+        let (text, cursor) = type_all(&mask, "20241231");
+        assert_eq!((text.as_str(), cursor), ("2024-12-31", 10));
+
+        // Backspace the "4": later digits shift left through the literals
+        let mut edited = text.clone();
+        edited.replace_range(3..4, "");
+        assert_eq!(mask.format(&edited, 3), ("2021-23-1_".to_string(), 3));
+
+        // Type it back in
+        assert_eq!(type_at(&mask, "2021-23-1_", 3, "4"), ("2024-12-31".to_string(), 4));
+    }
+
+    #[test]
+    fn mask_backspace_over_a_literal_moves_the_cursor() {
+        let mask = InputMask::phone();
+        // Deleting the space after ")" puts it back; the cursor steps over it
+        assert_eq!(mask.format("(555)1__-____", 5), ("(555) 1__-____".to_string(), 5));
+        assert_eq!(mask.format("(555 1__-____", 4), ("(555) 1__-____".to_string(), 4));
+    }
+
+    #[test]
+    fn mask_with_literal_prefix() {
+        let mask = InputMask::new("+1 (999) 999-9999");
+        assert_eq!(type_all(&mask, "415").0, "+1 (415) ___-____");
+        assert_eq!(mask.unformat("+1 (415) 555-0100"), "4155550100");
+        // The escaped 9 is a literal
+        assert_eq!(InputMask::new("\\9-9").format("5", 1).0, "9-5");
+    }
+
+    #[test]
+    fn mask_templates_and_completion() {
+        assert_eq!(InputMask::credit_card().template(), "____ ____ ____ ____");
+        assert_eq!(InputMask::date().placeholder_char('#').template(), "####-##-##");
+
+        let mask = InputMask::date();
+        assert!(!mask.is_complete("2024-12-__"));
+        assert!(mask.is_complete("2024-12-31"));
+    }
+
+    #[test]
+    fn ipv4_splits_octets() {
+        assert_eq!(type_all(&Ipv4Formatter, "19216801").0, "192.168.0.1");
+        assert_eq!(type_all(&Ipv4Formatter, "10.0.0.1"), ("10.0.0.1".to_string(), 8));
+        // 256 would exceed an octet, so the 6 starts the next one
+        assert_eq!(type_all(&Ipv4Formatter, "256").0, "25.6");
+        // No empty octets and at most four
+        assert_eq!(type_all(&Ipv4Formatter, ".1..2").0, "1.2");
+        assert_eq!(type_all(&Ipv4Formatter, "1.2.3.4.5").0, "1.2.3.45");
+    }
+
+    #[test]
+    fn ipv4_cursor_follows_inserted_dots() {
+        assert_eq!(type_at(&Ipv4Formatter, "192", 3, "1"), ("192.1".to_string(), 5));
+        // Deleting an automatic dot puts it back in front of the cursor
+        assert_eq!(Ipv4Formatter.format("1921", 3), ("192.1".to_string(), 3));
+    }
+}
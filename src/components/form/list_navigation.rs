@@ -0,0 +1,130 @@
+// Keyboard navigation of the highlighted option in Select and Combobox dropdowns
+
+use std::time::{Duration, Instant};
+
+/// Pause after which type-ahead starts a new search
+const TYPEAHEAD_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// The option highlighted after pressing `key` in a list of `len` options,
+/// or `None` if `key` doesn't move the highlight.
+///
+/// Options for which `enabled` returns false are skipped. Up and down stop
+/// at the ends; from no highlight, down starts at the first option and up
+/// at the last.
+pub(crate) fn move_highlight(
+    current: Option<usize>,
+    len: usize,
+    key: &str,
+    enabled: impl Fn(usize) -> bool,
+) -> Option<Option<usize>> {
+    let next = match key {
+        "down" => (current.map_or(0, |ix| ix + 1)..len).find(|&ix| enabled(ix)),
+        "up" => (0..current.unwrap_or(len)).rev().find(|&ix| enabled(ix)),
+        "home" => (0..len).find(|&ix| enabled(ix)),
+        "end" => (0..len).rev().find(|&ix| enabled(ix)),
+        _ => return None,
+    };
+    // Stay put at the ends
+    Some(next.or(current.filter(|&ix| ix < len)))
+}
+
+/// Type-ahead search: jumps to the next option starting with the typed letters
+#[derive(Default)]
+pub(crate) struct TypeAhead {
+    query: String,
+    last_key: Option<Instant>,
+}
+
+impl TypeAhead {
+    /// Whether a search is in progress, so space is part of the query
+    pub fn is_active(&self, now: Instant) -> bool {
+        !self.query.is_empty() && self.last_key.is_some_and(|at| now - at < TYPEAHEAD_TIMEOUT)
+    }
+
+    /// Add typed text and return the option to highlight.
+    ///
+    /// Typing a letter moves to the next option starting with it, so typing
+    /// the same letter again cycles through them. Typing more letters
+    /// refines the match, starting at the current option.
+    pub fn search<'a>(
+        &mut self,
+        text: &str,
+        labels: impl IntoIterator<Item = &'a str>,
+        current: Option<usize>,
+        now: Instant,
+    ) -> Option<usize> {
+        if !self.is_active(now) {
+            self.query.clear();
+        }
+        self.last_key = Some(now);
+        self.query.push_str(&text.to_lowercase());
+
+        let labels: Vec<String> = labels.into_iter().map(str::to_lowercase).collect();
+        let mut chars = self.query.chars();
+        let first = chars.next()?;
+        if chars.all(|c| c == first) {
+            // "b", "bb", "bbb": the next option starting with "b"
+            let start = current.map_or(0, |ix| ix + 1);
+            find_from(&labels, &first.to_string(), start)
+        } else {
+            find_from(&labels, &self.query, current.unwrap_or(0))
+        }
+    }
+}
+
+/// First label starting with `query` at or after `start`, wrapping around
+fn find_from(labels: &[String], query: &str, start: usize) -> Option<usize> {
+    let len = labels.len();
+    (0..len)
+        .map(|offset| (start + offset) % len)
+        .find(|&ix| labels[ix].starts_with(query))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{move_highlight, TypeAhead, TYPEAHEAD_TIMEOUT};
+    use std::time::Instant;
+
+    #[test]
+    fn arrows_and_home_end_stop_at_the_ends() {
+        let all = |_| true;
+        assert_eq!(move_highlight(None, 3, "down", all), Some(Some(0)));
+        assert_eq!(move_highlight(None, 3, "up", all), Some(Some(2)));
+        assert_eq!(move_highlight(Some(2), 3, "down", all), Some(Some(2)));
+        assert_eq!(move_highlight(Some(0), 3, "up", all), Some(Some(0)));
+        assert_eq!(move_highlight(Some(1), 3, "home", all), Some(Some(0)));
+        assert_eq!(move_highlight(Some(1), 3, "end", all), Some(Some(2)));
+        assert_eq!(move_highlight(None, 0, "down", all), Some(None));
+        assert_eq!(move_highlight(Some(1), 3, "a", all), None);
+    }
+
+    #[test]
+    fn disabled_options_are_skipped() {
+        // Options 0 and 2 of 4 are disabled
+        let enabled = |ix| ix % 2 == 1;
+        assert_eq!(move_highlight(None, 4, "down", enabled), Some(Some(1)));
+        assert_eq!(move_highlight(Some(1), 4, "down", enabled), Some(Some(3)));
+        assert_eq!(move_highlight(Some(3), 4, "down", enabled), Some(Some(3)));
+        assert_eq!(move_highlight(Some(1), 4, "up", enabled), Some(Some(1)));
+        assert_eq!(move_highlight(Some(3), 4, "home", enabled), Some(Some(1)));
+        assert_eq!(move_highlight(None, 4, "end", enabled), Some(Some(3)));
+        assert_eq!(move_highlight(None, 4, "down", |_| false), Some(None));
+    }
+
+    #[test]
+    fn typeahead_refines_and_cycles() {
+        let labels = ["Apple", "Avocado", "Banana", "Blueberry"];
+        let now = Instant::now();
+        let mut typeahead = TypeAhead::default();
+
+        assert_eq!(typeahead.search("b", labels, None, now), Some(2));
+        assert_eq!(typeahead.search("l", labels, Some(2), now), Some(3));
+
+        // Same letter again moves to the next match, wrapping around
+        let later = now + TYPEAHEAD_TIMEOUT;
+        assert_eq!(typeahead.search("a", labels, Some(3), later), Some(0));
+        assert_eq!(typeahead.search("a", labels, Some(0), later), Some(1));
+        assert_eq!(typeahead.search("a", labels, Some(1), later), Some(0));
+        assert_eq!(typeahead.search("z", labels, Some(0), later + TYPEAHEAD_TIMEOUT), None);
+    }
+}
@@ -7,6 +7,18 @@ pub mod checkbox;
 pub mod checkbox_group;
 pub mod combobox;
 pub mod radio_group;
+pub mod input_format;
+pub mod number_input;
+pub mod validation;
+pub mod form_container;
+pub mod form_field;
+pub mod async_options;
+mod creatable;
+mod dropdown_list;
+mod fuzzy;
+mod history;
+mod list_navigation;
+mod text_buffer;
 
 pub use text_input::*;
 pub use text_area::*;
@@ -15,3 +27,9 @@ pub use checkbox::*;
 pub use checkbox_group::*;
 pub use combobox::*;
 pub use radio_group::*;
+pub use input_format::*;
+pub use number_input::*;
+pub use validation::*;
+pub use form_container::*;
+pub use form_field::*;
+pub use async_options::*;
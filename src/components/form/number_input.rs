@@ -0,0 +1,760 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use serde_json::Value;
+use crate::theme::*;
+use crate::components::basic::icon::{Icon, IconName};
+use crate::components::form::form_container::FormControl;
+use crate::components::form::input_format::InputFormatter;
+use crate::components::form::text_input::{TextInput, TextInputEvent};
+use crate::components::form::validation::ValidationState;
+
+/// Scroll distance that counts as one step for pixel-precise devices
+const SCROLL_STEP: f32 = 20.0;
+
+/// Width of each stepper button
+const STEPPER_WIDTH: f32 = 28.0;
+
+// ============================================================================
+// Events
+// ============================================================================
+
+/// Events emitted by the NumberInput component
+#[derive(Clone, Debug)]
+pub enum NumberInputEvent {
+    /// The value changed, by typing, stepping or programmatically
+    Change(f64),
+}
+
+impl EventEmitter<NumberInputEvent> for NumberInput {}
+
+// ============================================================================
+// Number format
+// ============================================================================
+
+/// Whether a [`NumberInput`] accepts fractions
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NumberMode {
+    /// Whole numbers only; the decimal separator is rejected
+    Integer,
+    /// Numbers with a fractional part
+    #[default]
+    Decimal,
+}
+
+/// Decimal and digit group separators used to display and parse numbers
+///
+/// # Example
+///
+/// ```
+/// use fluix::NumberLocale;
+///
+/// let german = NumberLocale::from_tag("de-DE");
+/// assert_eq!(german.decimal_separator, ',');
+/// assert_eq!(german.group_separator, Some('.'));
+///
+/// // POSIX locale strings work too
+/// assert_eq!(NumberLocale::from_tag("fr_FR.UTF-8").decimal_separator, ',');
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberLocale {
+    /// Separator between the integer and fractional part
+    pub decimal_separator: char,
+    /// Separator between groups of three integer digits, if grouped
+    pub group_separator: Option<char>,
+}
+
+impl Default for NumberLocale {
+    /// `.` as decimal separator, without digit grouping
+    fn default() -> Self {
+        Self::new('.', None)
+    }
+}
+
+impl NumberLocale {
+    /// Create a locale with custom separators
+    pub const fn new(decimal_separator: char, group_separator: Option<char>) -> Self {
+        Self {
+            decimal_separator,
+            group_separator,
+        }
+    }
+
+    /// Separators for a BCP 47 or POSIX locale tag such as `de-DE` or `fr_FR.UTF-8`.
+    ///
+    /// Only the language is considered. Unknown languages use `1,234.5`.
+    pub fn from_tag(tag: &str) -> Self {
+        let language = tag
+            .split(['-', '_', '.', '@'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        match language.as_str() {
+            // 1.234,5
+            "de" | "es" | "it" | "nl" | "pt" | "id" | "tr" | "da" | "el" | "ro" | "hr" | "sl"
+            | "vi" => Self::new(',', Some('.')),
+            // 1 234,5 (no-break space)
+            "fr" | "ru" | "pl" | "cs" | "sk" | "sv" | "nb" | "no" | "fi" | "uk" | "hu" | "bg"
+            | "et" | "lt" | "lv" => Self::new(',', Some('\u{a0}')),
+            _ => Self::new('.', Some(',')),
+        }
+    }
+
+    /// Separators for the locale in the `LC_ALL`, `LC_NUMERIC` or `LANG`
+    /// environment variables, falling back to the default
+    pub fn system() -> Self {
+        ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|tag| !tag.is_empty() && tag != "C" && tag != "POSIX")
+            .map_or_else(Self::default, |tag| Self::from_tag(&tag))
+    }
+}
+
+/// Live formatter for the text of a [`NumberInput`].
+///
+/// Keeps a leading minus sign, digits and one decimal separator, and
+/// regroups the integer digits as the user types.
+#[derive(Clone, Copy, Debug)]
+struct NumberFormatter {
+    mode: NumberMode,
+    locale: NumberLocale,
+    allow_negative: bool,
+}
+
+/// Sign, integer digits and fractional digits of typed text
+struct NumberParts {
+    negative: bool,
+    integer: String,
+    fraction: Option<String>,
+}
+
+impl NumberFormatter {
+    /// Split `text` into its parts, dropping everything else, and count
+    /// the kept characters before `cursor`
+    fn parts(&self, text: &str, cursor: usize) -> (NumberParts, usize) {
+        let mut parts = NumberParts {
+            negative: false,
+            integer: String::new(),
+            fraction: None,
+        };
+        let mut kept_before_cursor = 0;
+        for (idx, c) in text.char_indices() {
+            let kept = match c {
+                '-' if self.allow_negative
+                    && !parts.negative
+                    && parts.integer.is_empty()
+                    && parts.fraction.is_none() =>
+                {
+                    parts.negative = true;
+                    true
+                }
+                '0'..='9' => {
+                    parts.fraction.as_mut().unwrap_or(&mut parts.integer).push(c);
+                    true
+                }
+                c if c == self.locale.decimal_separator
+                    && self.mode == NumberMode::Decimal
+                    && parts.fraction.is_none() =>
+                {
+                    parts.fraction = Some(String::new());
+                    true
+                }
+                _ => false,
+            };
+            if kept && idx < cursor {
+                kept_before_cursor += 1;
+            }
+        }
+        (parts, kept_before_cursor)
+    }
+}
+
+impl InputFormatter for NumberFormatter {
+    fn format(&self, text: &str, cursor: usize) -> (String, usize) {
+        let (parts, kept_before_cursor) = self.parts(text, cursor);
+
+        // Characters to emit, and whether each was typed (not a group separator)
+        let mut chars = Vec::new();
+        if parts.negative {
+            chars.push(('-', true));
+        }
+        let digits = parts.integer.len();
+        for (idx, c) in parts.integer.chars().enumerate() {
+            if let Some(group) = self.locale.group_separator {
+                if idx > 0 && (digits - idx) % 3 == 0 {
+                    chars.push((group, false));
+                }
+            }
+            chars.push((c, true));
+        }
+        if let Some(fraction) = &parts.fraction {
+            chars.push((self.locale.decimal_separator, true));
+            chars.extend(fraction.chars().map(|c| (c, true)));
+        }
+
+        let mut formatted = String::new();
+        let mut new_cursor = 0;
+        let mut kept = 0;
+        for (c, typed) in chars {
+            formatted.push(c);
+            if typed {
+                kept += 1;
+                if kept == kept_before_cursor {
+                    new_cursor = formatted.len();
+                }
+            }
+        }
+        (formatted, new_cursor)
+    }
+
+    /// The number with `-` and `.`, ready for `str::parse`
+    fn unformat(&self, text: &str) -> String {
+        let (parts, _) = self.parts(text, 0);
+        let mut raw = String::new();
+        if parts.negative {
+            raw.push('-');
+        }
+        raw.push_str(&parts.integer);
+        if let Some(fraction) = parts.fraction {
+            raw.push('.');
+            raw.push_str(&fraction);
+        }
+        raw
+    }
+}
+
+/// Number of decimal places in the shortest representation of `value`
+fn decimal_places(value: f64) -> usize {
+    let text = value.to_string();
+    text.find('.').map_or(0, |idx| text.len() - idx - 1)
+}
+
+/// Round `value` to `places` decimal places
+fn round_to(value: f64, places: usize) -> f64 {
+    let factor = 10f64.powi(places.min(15) as i32);
+    (value * factor).round() / factor
+}
+
+// ============================================================================
+// Component
+// ============================================================================
+
+/// A numeric input with stepper buttons, built on [`TextInput`]
+///
+/// Values are typed with the locale's decimal separator and digit grouping,
+/// and stepped with the +/- buttons, the up/down arrow keys (Shift or
+/// PageUp/PageDown for ten steps) or the scroll wheel while focused.
+///
+/// Typed values outside the range are shown as invalid and clamped when the
+/// input loses focus or Enter is pressed; unparseable text is reverted.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// let temperature = cx.new(|cx| {
+///     NumberInput::new(cx)
+///         .range(0.0, 2.0)
+///         .step(0.1)
+///         .precision(1)
+///         .value(0.7)
+/// });
+///
+/// let max_tokens = cx.new(|cx| {
+///     NumberInput::new(cx)
+///         .mode(NumberMode::Integer)
+///         .min(1.0)
+///         .step(256.0)
+///         .locale(NumberLocale::system())
+///         .value(4096.0)
+/// });
+///
+/// cx.subscribe(&temperature, |this, input, event: &NumberInputEvent, cx| {
+///     match event {
+///         NumberInputEvent::Change(value) => println!("Temperature: {}", value),
+///     }
+/// });
+/// ```
+pub struct NumberInput {
+    /// Internal text input, created on first render
+    input: Option<Entity<TextInput>>,
+    /// Current committed value
+    value: f64,
+    /// Integer or decimal mode
+    mode: NumberMode,
+    /// Lower bound, if any
+    min: Option<f64>,
+    /// Upper bound, if any
+    max: Option<f64>,
+    /// Amount added or removed by one step
+    step: f64,
+    /// Fixed number of decimal places to display
+    precision: Option<usize>,
+    /// Decimal and group separators
+    locale: NumberLocale,
+    /// Placeholder text when empty
+    placeholder: String,
+    /// Whether the input is disabled
+    disabled: bool,
+    /// Whether the typed text is a number outside the range
+    invalid: bool,
+    /// Scroll distance not yet turned into steps
+    scroll_delta: f32,
+    /// Event subscriptions
+    _subscriptions: Vec<Subscription>,
+}
+
+impl NumberInput {
+    /// Create a new NumberInput with value 0, no bounds and a step of 1
+    pub fn new(_cx: &mut Context<Self>) -> Self {
+        Self {
+            input: None,
+            value: 0.0,
+            mode: NumberMode::Decimal,
+            min: None,
+            max: None,
+            step: 1.0,
+            precision: None,
+            locale: NumberLocale::default(),
+            placeholder: String::new(),
+            disabled: false,
+            invalid: false,
+            scroll_delta: 0.0,
+            _subscriptions: Vec::new(),
+        }
+    }
+
+    /// Set the initial value, clamped to the range
+    pub fn value(mut self, value: f64) -> Self {
+        self.value = self.normalize(value);
+        self
+    }
+
+    /// Accept whole numbers only, or fractions too (the default)
+    pub fn mode(mut self, mode: NumberMode) -> Self {
+        self.mode = mode;
+        self.value = self.normalize(self.value);
+        self
+    }
+
+    /// Set the lower bound. Negative numbers can only be typed if it is below 0.
+    pub fn min(mut self, min: f64) -> Self {
+        self.min = Some(min);
+        self.value = self.normalize(self.value);
+        self
+    }
+
+    /// Set the upper bound
+    pub fn max(mut self, max: f64) -> Self {
+        self.max = Some(max);
+        self.value = self.normalize(self.value);
+        self
+    }
+
+    /// Set both bounds
+    pub fn range(self, min: f64, max: f64) -> Self {
+        self.min(min).max(max)
+    }
+
+    /// Set the amount added or removed by one step (default 1)
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step.abs();
+        self
+    }
+
+    /// Always display this many decimal places, rounding typed values on commit
+    pub fn precision(mut self, precision: usize) -> Self {
+        self.precision = Some(precision);
+        self.value = self.normalize(self.value);
+        self
+    }
+
+    /// Set the decimal and group separators, e.g. [`NumberLocale::system`]
+    pub fn locale(mut self, locale: NumberLocale) -> Self {
+        self.locale = locale;
+        self
+    }
+
+    /// Set the placeholder text shown while the text is empty
+    pub fn placeholder(mut self, placeholder: impl Into<String>) -> Self {
+        self.placeholder = placeholder.into();
+        self
+    }
+
+    /// Set the disabled state
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Get the current value.
+    ///
+    /// While the user is typing this is the last valid number; text outside
+    /// the range is only applied once committed.
+    pub fn get_value(&self) -> f64 {
+        self.value
+    }
+
+    /// Whether the typed text is a number outside the range
+    pub fn is_invalid(&self) -> bool {
+        self.invalid
+    }
+
+    /// Set the value programmatically, clamped to the range.
+    ///
+    /// Emits `Change` if the value changed.
+    pub fn set_value(&mut self, value: f64, cx: &mut Context<Self>) {
+        let value = self.normalize(value);
+        if value != self.value {
+            self.value = value;
+            cx.emit(NumberInputEvent::Change(value));
+        }
+        self.invalid = false;
+        self.sync_text(cx);
+        cx.notify();
+    }
+
+    /// Add `steps` steps to the value (negative to subtract)
+    pub fn step_by(&mut self, steps: f64, cx: &mut Context<Self>) {
+        if self.disabled {
+            return;
+        }
+        // Step from what is typed, so stepping also commits the text
+        let base = self.typed_value(cx).unwrap_or(self.value);
+        let places = decimal_places(self.step).max(decimal_places(base));
+        self.set_value(round_to(base + steps * self.step, places), cx);
+    }
+
+    /// Focus the text field
+    pub fn focus(&self, window: &mut Window, cx: &App) {
+        if let Some(input) = &self.input {
+            input.read(cx).focus(window);
+        }
+    }
+
+    // ------------------------------------------------------------------------
+    // Internals
+    // ------------------------------------------------------------------------
+
+    fn formatter(&self) -> NumberFormatter {
+        NumberFormatter {
+            mode: self.mode,
+            locale: self.locale,
+            allow_negative: self.min.is_none_or(|min| min < 0.0),
+        }
+    }
+
+    /// Display precision: 0 for integers, otherwise the configured precision
+    fn display_precision(&self) -> Option<usize> {
+        match self.mode {
+            NumberMode::Integer => Some(0),
+            NumberMode::Decimal => self.precision,
+        }
+    }
+
+    /// Round to the display precision and clamp to the range
+    fn normalize(&self, value: f64) -> f64 {
+        let mut value = match self.display_precision() {
+            Some(places) => round_to(value, places),
+            None => value,
+        };
+        if let Some(min) = self.min {
+            value = value.max(min);
+        }
+        if let Some(max) = self.max {
+            value = value.min(max);
+        }
+        // Avoid displaying "-0"
+        if value == 0.0 { 0.0 } else { value }
+    }
+
+    fn in_range(&self, value: f64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+
+    /// Localized text for `value`
+    fn format_value(&self, value: f64) -> String {
+        let text = match self.display_precision() {
+            Some(places) => format!("{:.*}", places, value),
+            None => value.to_string(),
+        };
+        let text = text.replace('.', &self.locale.decimal_separator.to_string());
+        self.formatter().format(&text, text.len()).0
+    }
+
+    fn parse(&self, text: &str) -> Option<f64> {
+        self.formatter().unformat(text).parse().ok()
+    }
+
+    /// The number currently typed, if the text parses
+    fn typed_value(&self, cx: &App) -> Option<f64> {
+        let input = self.input.as_ref()?;
+        self.parse(input.read(cx).get_value())
+    }
+
+    /// Show the current value in the text field
+    fn sync_text(&mut self, cx: &mut Context<Self>) {
+        let text = self.format_value(self.value);
+        if let Some(input) = &self.input {
+            input.update(cx, |input, cx| {
+                if input.get_value() != text {
+                    input.set_value(text, cx);
+                }
+            });
+        }
+    }
+
+    /// Apply typed text that is a number within range
+    fn handle_text_change(&mut self, text: &str, cx: &mut Context<Self>) {
+        let typed = self.parse(text);
+        self.invalid = typed.is_some_and(|value| !self.in_range(value));
+        if let Some(value) = typed.filter(|value| self.in_range(*value)) {
+            if value != self.value {
+                self.value = value;
+                cx.emit(NumberInputEvent::Change(value));
+            }
+        }
+        cx.notify();
+    }
+
+    /// Clamp the typed number, or restore the value if the text isn't one
+    fn commit(&mut self, cx: &mut Context<Self>) {
+        let value = self.typed_value(cx).unwrap_or(self.value);
+        self.set_value(value, cx);
+    }
+
+    /// Create the text input and subscribe to it
+    fn ensure_input(&mut self, window: &mut Window, cx: &mut Context<Self>) -> Entity<TextInput> {
+        if let Some(input) = &self.input {
+            return input.clone();
+        }
+
+        let formatter = self.formatter();
+        let text = self.format_value(self.value);
+        let placeholder = self.placeholder.clone();
+        let disabled = self.disabled;
+        let input = cx.new(|cx| {
+            TextInput::new(cx)
+                .formatter(formatter)
+                .value(text)
+                .placeholder(placeholder)
+                .disabled(disabled)
+                .no_border()
+                .transparent()
+        });
+
+        self._subscriptions.push(cx.subscribe_in(&input, window, |this, _input, event: &TextInputEvent, _window, cx| {
+            match event {
+                TextInputEvent::Change(text) => this.handle_text_change(text, cx),
+                TextInputEvent::Submit(_) => this.commit(cx),
+                _ => {}
+            }
+        }));
+        let focus_handle = input.focus_handle(cx);
+        self._subscriptions.push(cx.on_blur(&focus_handle, window, |this, _window, cx| {
+            this.commit(cx);
+        }));
+
+        self.input = Some(input.clone());
+        input
+    }
+
+    fn render_stepper(
+        &self,
+        id: &'static str,
+        icon: IconName,
+        steps: f64,
+        enabled: bool,
+        theme: &Theme,
+        cx: &Context<Self>,
+    ) -> impl IntoElement {
+        let hover_bg = theme.colors.background_hover;
+        div()
+            .id(id)
+            .flex()
+            .flex_none()
+            .items_center()
+            .justify_center()
+            .w(px(STEPPER_WIDTH))
+            .h_full()
+            .border_l_1()
+            .border_color(theme.colors.border)
+            .when(enabled, |this| {
+                this.cursor(CursorStyle::PointingHand)
+                    .hover(move |style| style.bg(hover_bg))
+                    .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event: &MouseDownEvent, window, cx| {
+                        // Keep focus in the text field
+                        window.prevent_default();
+                        cx.stop_propagation();
+                        this.step_by(steps, cx);
+                    }))
+            })
+            .child(
+                Icon::new(icon)
+                    .xsmall()
+                    .color(if enabled {
+                        theme.colors.text_secondary
+                    } else {
+                        theme.colors.text_disabled
+                    })
+            )
+    }
+}
+
+impl FormControl for NumberInput {
+    fn form_value(&self) -> Value {
+        match self.mode {
+            NumberMode::Integer => Value::from(self.value as i64),
+            NumberMode::Decimal => Value::from(self.value),
+        }
+    }
+
+    fn set_form_value(&mut self, value: &Value, cx: &mut Context<Self>) {
+        if let Some(value) = value.as_f64() {
+            self.set_value(value, cx);
+        }
+    }
+
+    fn form_validation_state(&self) -> ValidationState {
+        if !self.invalid {
+            return ValidationState::Unvalidated;
+        }
+        ValidationState::Invalid(match (self.min, self.max) {
+            (Some(min), Some(max)) => format!("Enter a number from {min} to {max}"),
+            (Some(min), None) => format!("Enter a number of at least {min}"),
+            (None, Some(max)) => format!("Enter a number of at most {max}"),
+            (None, None) => "Enter a valid number".to_string(),
+        })
+    }
+
+    fn form_focus_handle(&self, cx: &App) -> Option<FocusHandle> {
+        self.input.as_ref().map(|input| input.focus_handle(cx))
+    }
+}
+
+// ============================================================================
+// Render
+// ============================================================================
+
+impl Render for NumberInput {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme().clone();
+        let input = self.ensure_input(window, cx);
+        let is_focused = input.focus_handle(cx).is_focused(window);
+        let disabled = self.disabled;
+        let can_decrement = !disabled && self.min.is_none_or(|min| self.value > min);
+        let can_increment = !disabled && self.max.is_none_or(|max| self.value < max);
+
+        let border_color = if self.invalid {
+            theme.colors.border_error
+        } else if is_focused {
+            theme.colors.border_focus
+        } else {
+            theme.colors.border
+        };
+
+        div()
+            .id("number-input")
+            .flex()
+            .items_center()
+            .w_full()
+            .h(theme.height(ComponentSize::Medium))
+            .overflow_hidden()
+            .border_1()
+            .border_color(border_color)
+            .rounded(px(theme.radius.md))
+            .bg(if disabled {
+                theme.colors.background_secondary
+            } else {
+                theme.colors.background
+            })
+            .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
+                let shift = event.keystroke.modifiers.shift;
+                let steps = match event.keystroke.key.as_str() {
+                    "up" if shift => 10.0,
+                    "down" if shift => -10.0,
+                    "up" => 1.0,
+                    "down" => -1.0,
+                    "pageup" => 10.0,
+                    "pagedown" => -10.0,
+                    _ => return,
+                };
+                cx.stop_propagation();
+                this.step_by(steps, cx);
+            }))
+            .on_scroll_wheel(cx.listener(move |this, event: &ScrollWheelEvent, _window, cx| {
+                // Only while focused, so scrolling past the input scrolls the page
+                if !is_focused || this.disabled {
+                    return;
+                }
+                cx.stop_propagation();
+                this.scroll_delta += f32::from(event.delta.pixel_delta(px(SCROLL_STEP)).y);
+                let steps = (this.scroll_delta / SCROLL_STEP).trunc();
+                if steps != 0.0 {
+                    this.scroll_delta -= steps * SCROLL_STEP;
+                    this.step_by(f64::from(steps), cx);
+                }
+            }))
+            .child(div().flex_1().min_w_0().child(input))
+            .child(self.render_stepper("number-input-decrement", IconName::Minus, -1.0, can_decrement, &theme, cx))
+            .child(self.render_stepper("number-input-increment", IconName::Plus, 1.0, can_increment, &theme, cx))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decimal_places, round_to, NumberFormatter, NumberLocale, NumberMode};
+    use crate::components::form::input_format::InputFormatter;
+
+    fn formatter(mode: NumberMode, locale: NumberLocale) -> NumberFormatter {
+        NumberFormatter {
+            mode,
+            locale,
+            allow_negative: true,
+        }
+    }
+
+    #[test]
+    fn keeps_sign_digits_and_one_separator() {
+        let decimal = formatter(NumberMode::Decimal, NumberLocale::default());
+        assert_eq!(decimal.format("-12a.3.4", 8), ("-12.34".to_string(), 6));
+        assert_eq!(decimal.format("1-2", 3), ("12".to_string(), 2));
+        assert_eq!(decimal.unformat("-12.34"), "-12.34");
+
+        let integer = formatter(NumberMode::Integer, NumberLocale::default());
+        assert_eq!(integer.format("12.5", 4).0, "125");
+
+        let positive = NumberFormatter {
+            allow_negative: false,
+            ..decimal
+        };
+        assert_eq!(positive.format("-5", 2).0, "5");
+    }
+
+    #[test]
+    fn groups_digits_and_keeps_the_cursor() {
+        let german = formatter(NumberMode::Decimal, NumberLocale::from_tag("de"));
+        // Typing the fourth digit inserts a group separator before the cursor
+        assert_eq!(german.format("1234", 4), ("1.234".to_string(), 5));
+        assert_eq!(german.format("1.2345,5", 6), ("12.345,5".to_string(), 6));
+        // Deleting a digit regroups, the cursor stays after the same digit
+        assert_eq!(german.format("1.23", 1), ("123".to_string(), 1));
+        assert_eq!(german.unformat("-1.234.567,89"), "-1234567.89");
+    }
+
+    #[test]
+    fn locale_tags() {
+        assert_eq!(NumberLocale::from_tag("en-US"), NumberLocale::new('.', Some(',')));
+        assert_eq!(NumberLocale::from_tag("pt_BR.UTF-8"), NumberLocale::new(',', Some('.')));
+        assert_eq!(NumberLocale::from_tag("fr"), NumberLocale::new(',', Some('\u{a0}')));
+        assert_eq!(NumberLocale::from_tag(""), NumberLocale::new('.', Some(',')));
+    }
+
+    #[test]
+    fn step_rounding() {
+        assert_eq!(decimal_places(0.1), 1);
+        assert_eq!(decimal_places(256.0), 0);
+        assert_eq!(decimal_places(0.25), 2);
+        // 0.1 + 0.2 without rounding would be 0.30000000000000004
+        assert_eq!(round_to(0.1 + 0.2, 1), 0.3);
+    }
+}
@@ -1,6 +1,9 @@
 use gpui::prelude::FluentBuilder;
 use gpui::*;
+use serde_json::Value;
 use crate::theme::*;
+use crate::components::form::validation::{render_error_message, validation_methods, ValidateOn, Validation, ValidationState};
+use crate::components::form::form_container::{value_to_strings, FormControl};
 
 // ============================================================================
 // Events
@@ -83,6 +86,14 @@ pub struct RadioGroup {
     direction: RadioGroupDirection,
     /// Custom text color
     custom_text_color: Option<Rgba>,
+    /// Validation rules for the selected value, and the current error
+    validation: Validation<[String]>,
+}
+
+validation_methods! {
+    /// The rule receives the selected value, or an empty slice if none is
+    /// selected. Changing the selection counts as a change and a blur.
+    RadioGroup: [String] = |this| this.selected()
 }
 
 impl RadioGroup {
@@ -95,6 +106,7 @@ impl RadioGroup {
             size: ComponentSize::Medium,
             direction: RadioGroupDirection::Vertical,
             custom_text_color: None,
+            validation: Validation::default(),
         }
     }
 
@@ -146,6 +158,11 @@ impl RadioGroup {
         self
     }
 
+    /// The selected value as a slice for validation
+    fn selected(&self) -> Vec<String> {
+        self.selected_value.iter().cloned().collect()
+    }
+
     /// Select an option
     fn select_option(&mut self, value: String, cx: &mut Context<Self>) {
         if self.disabled {
@@ -153,6 +170,7 @@ impl RadioGroup {
         }
 
         self.selected_value = Some(value.clone());
+        self.validate_for(ValidateOn::Blur, cx);
         cx.emit(RadioGroupEvent::Changed(value));
         cx.notify();
     }
@@ -181,14 +199,14 @@ impl RadioGroup {
         let disabled = self.disabled;
         let radio_size_val = self.radio_size();
         let radio_size = px(radio_size_val);
-        let text_size = self.size.font_size();
+        let text_size = theme.font_size(self.size);
         let text_color = self.custom_text_color.unwrap_or(theme.colors.text);
 
         div()
             .id(("radio-item", index))
             .flex()
             .items_center()
-            .gap_2()
+            .gap(px(theme.spacing.sm))
             .cursor(if disabled { CursorStyle::Arrow } else { CursorStyle::PointingHand })
             .when(disabled, |this| {
                 this.opacity(0.64)
@@ -204,13 +222,15 @@ impl RadioGroup {
                     .border_1()
                     .border_color(if is_selected {
                         theme.colors.primary
+                    } else if self.validation.state().is_invalid() {
+                        theme.colors.border_error
                     } else {
                         theme.colors.border
                     })
                     .bg(if is_selected {
                         theme.colors.primary
                     } else {
-                        rgb(0xFFFFFF)
+                        theme.colors.background
                     })
                     .when(is_selected, |this| {
                         // Inner dot
@@ -218,7 +238,7 @@ impl RadioGroup {
                             div()
                                 .size(px(radio_size_val * 0.5))
                                 .rounded(px(radio_size_val * 0.25))
-                                .bg(rgb(0xFFFFFF))
+                                .bg(theme.colors.text_on_primary)
                         )
                     })
             )
@@ -231,20 +251,46 @@ impl RadioGroup {
     }
 }
 
+impl FormControl for RadioGroup {
+    fn form_value(&self) -> Value {
+        self.selected_value.clone().map_or(Value::Null, Value::String)
+    }
+
+    fn set_form_value(&mut self, value: &Value, cx: &mut Context<Self>) {
+        self.selected_value = value_to_strings(value).into_iter().next();
+        cx.emit(RadioGroupEvent::Changed(self.selected_value.clone().unwrap_or_default()));
+        cx.notify();
+    }
+
+    fn validate_form(&mut self, cx: &mut Context<Self>) -> ValidationState {
+        self.run_validation(cx);
+        self.validation.state().clone()
+    }
+
+    fn form_validation_state(&self) -> ValidationState {
+        self.validation.state().clone()
+    }
+
+    fn clear_form_error(&mut self, cx: &mut Context<Self>) {
+        self.set_error(None, cx);
+    }
+}
+
 // ============================================================================
 // Render
 // ============================================================================
 
 impl Render for RadioGroup {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let theme = Theme::default();
+        let theme = cx.theme().clone();
         let options = self.options.clone();
         let direction = self.direction;
+        let error = self.validation.error().map(str::to_string);
 
-        div()
+        let group = div()
             .id("radio-group")
             .flex()
-            .gap_3()
+            .gap(px(theme.spacing.md))
             .when(matches!(direction, RadioGroupDirection::Vertical), |this| {
                 this.flex_col()
             })
@@ -269,7 +315,15 @@ impl Render for RadioGroup {
                         }))
                         .child(self.render_radio(&option_clone, idx, &theme))
                 }
-            }))
+            }));
+
+        div()
+            .flex()
+            .flex_col()
+            .child(group)
+            .when_some(error, |this, message| {
+                this.child(render_error_message(&message, &theme))
+            })
     }
 }
 
@@ -1,7 +1,15 @@
 use gpui::prelude::FluentBuilder;
 use gpui::*;
+use std::rc::Rc;
+use std::time::Instant;
+use serde_json::Value;
 use crate::theme::*;
 use crate::components::basic::icon::{Icon, IconName};
+use crate::components::form::async_options::{AsyncOptions, OptionsResult};
+use crate::components::form::dropdown_list::{DropdownList, ItemsKey, MenuItems, MenuRow};
+use crate::components::form::list_navigation::{move_highlight, TypeAhead};
+use crate::components::form::validation::{render_error_message, validation_methods, ValidateOn, Validation, ValidationState};
+use crate::components::form::form_container::{value_to_strings, FormControl};
 
 // ============================================================================
 // Events
@@ -77,11 +85,25 @@ impl Default for DropdownWidth {
     }
 }
 
+/// Renders the content of an option row, see [`SelectOption::render`]
+pub type OptionRenderer = Rc<dyn Fn(&SelectOption, &mut Window, &mut App) -> AnyElement>;
+
 /// An option in the select dropdown
-#[derive(Clone, Debug)]
+#[derive(Clone)]
 pub struct SelectOption {
     pub value: String,
     pub label: String,
+    /// Icon before the label
+    pub icon: Option<IconName>,
+    /// Secondary text below the label
+    pub description: Option<String>,
+    /// Right-aligned text, e.g. a badge or keyboard shortcut
+    pub badge: Option<String>,
+    /// Whether the option is shown but can't be selected
+    pub disabled: bool,
+    /// Custom content of the option row, replacing the icon, label,
+    /// description and badge
+    pub renderer: Option<OptionRenderer>,
 }
 
 impl SelectOption {
@@ -89,8 +111,107 @@ impl SelectOption {
         Self {
             value: value.into(),
             label: label.into(),
+            icon: None,
+            description: None,
+            badge: None,
+            disabled: false,
+            renderer: None,
         }
     }
+
+    /// Set the icon shown before the label
+    pub fn icon(mut self, icon: IconName) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    /// Set the secondary text shown below the label
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the right-aligned text, e.g. a badge or keyboard shortcut
+    pub fn badge(mut self, badge: impl Into<String>) -> Self {
+        self.badge = Some(badge.into());
+        self
+    }
+
+    /// Show the option but prevent selecting it
+    pub fn disabled(mut self, disabled: bool) -> Self {
+        self.disabled = disabled;
+        self
+    }
+
+    /// Render the row content with a custom element.
+    ///
+    /// The row keeps its padding, selection and hover styling, and the label
+    /// is still used for filtering and as the selected text.
+    pub fn render(mut self, renderer: impl Fn(&SelectOption, &mut Window, &mut App) -> AnyElement + 'static) -> Self {
+        self.renderer = Some(Rc::new(renderer));
+        self
+    }
+
+    /// Render the row content: the custom renderer's element, or the icon,
+    /// `label`, description and badge
+    pub(crate) fn render_content(
+        &self,
+        label: impl IntoElement,
+        is_selected: bool,
+        theme: &Theme,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> AnyElement {
+        if let Some(renderer) = &self.renderer {
+            return renderer(self, window, cx);
+        }
+
+        // Selected options use the primary background
+        let secondary_color = if is_selected {
+            theme.colors.text_on_primary
+        } else {
+            theme.colors.text_secondary
+        };
+
+        div()
+            .flex()
+            .items_center()
+            .gap(px(theme.spacing.sm))
+            .flex_1()
+            .min_w_0()
+            .when_some(self.icon, |this, icon| {
+                this.child(Icon::new(icon).small().color(secondary_color))
+            })
+            .child(
+                div()
+                    .flex()
+                    .flex_col()
+                    .flex_1()
+                    .min_w_0()
+                    .child(label)
+                    .when_some(self.description.clone(), |this, description| {
+                        this.child(div().text_xs().text_color(secondary_color).child(description))
+                    })
+            )
+            .when_some(self.badge.clone(), |this, badge| {
+                this.child(div().flex_none().text_xs().text_color(secondary_color).child(badge))
+            })
+            .into_any_element()
+    }
+}
+
+impl std::fmt::Debug for SelectOption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SelectOption")
+            .field("value", &self.value)
+            .field("label", &self.label)
+            .field("icon", &self.icon)
+            .field("description", &self.description)
+            .field("badge", &self.badge)
+            .field("disabled", &self.disabled)
+            .field("renderer", &self.renderer.as_ref().map(|_| ".."))
+            .finish()
+    }
 }
 
 /// A group of options in the select dropdown
@@ -160,7 +281,7 @@ pub struct Select {
     dropdown_alignment: DropdownAlignment,
     /// Dropdown width
     dropdown_width: DropdownWidth,
-    /// Custom font size (overrides size.font_size() if set)
+    /// Custom font size (overrides the theme font size if set)
     custom_font_size: Option<Pixels>,
     /// Custom background color
     custom_bg_color: Option<Rgba>,
@@ -178,11 +299,30 @@ pub struct Select {
     multiple: bool,
     /// Flag to prevent closing when clicking inside menu
     clicking_menu: bool,
+    /// Validation rules for the selected values, and the current error
+    validation: Validation<[String]>,
+    /// Focus handle for keyboard navigation, a tab stop
+    focus_handle: FocusHandle,
+    /// Option highlighted by the keyboard, as an index into the menu's options
+    highlighted: Option<usize>,
+    /// Type-ahead search by the first letters of option labels
+    typeahead: TypeAhead,
+    /// Virtualized dropdown rows, only the visible ones are rendered
+    dropdown: DropdownList,
+    /// Source the options are loaded from, instead of `options`
+    async_options: Option<AsyncOptions>,
+}
+
+validation_methods! {
+    /// The rule receives the selected values: none or one for single
+    /// select, all of them for multi select. Changing the selection counts
+    /// as a change and a blur.
+    Select: [String] = |this| this.selected()
 }
 
 impl Select {
     /// Create a new Select
-    pub fn new(_cx: &mut Context<Self>) -> Self {
+    pub fn new(cx: &mut Context<Self>) -> Self {
         Self {
             options: Vec::new(),
             option_groups: Vec::new(),
@@ -205,6 +345,12 @@ impl Select {
             compact: false,
             multiple: false,
             clicking_menu: false,
+            validation: Validation::default(),
+            focus_handle: cx.focus_handle().tab_stop(true),
+            highlighted: None,
+            typeahead: TypeAhead::default(),
+            dropdown: DropdownList::default(),
+            async_options: None,
         }
     }
 
@@ -214,6 +360,43 @@ impl Select {
         self
     }
 
+    /// Load the options from an async source, e.g. a backend, when the
+    /// dropdown first opens.
+    ///
+    /// The source is called with an empty query and a zero-based page number.
+    /// Pages with `has_more` set end in a "Load more" row.
+    ///
+    /// ```rust,ignore
+    /// Select::new(cx).async_options(|_query, page| {
+    ///     let request = api.list_users(page);
+    ///     async move {
+    ///         let users = request.await.map_err(|e| e.to_string())?;
+    ///         Ok(OptionsPage::new(users.items).has_more(users.has_next))
+    ///     }
+    /// })
+    /// ```
+    pub fn async_options<F, Fut>(mut self, load: F) -> Self
+    where
+        F: Fn(&str, usize) -> Fut + 'static,
+        Fut: std::future::Future<Output = OptionsResult> + 'static,
+    {
+        self.async_options = Some(AsyncOptions::new(load));
+        self
+    }
+
+    /// The flat options, loaded ones when an async source is set
+    fn flat_options(&self) -> &[SelectOption] {
+        match &self.async_options {
+            Some(source) => source.options(),
+            None => &self.options,
+        }
+    }
+
+    /// Locates the async options for results of the async source
+    fn async_field(this: &mut Self) -> &mut AsyncOptions {
+        this.async_options.as_mut().expect("async options are set")
+    }
+
     /// Set the option groups
     pub fn option_groups(mut self, groups: Vec<SelectOptionGroup>) -> Self {
         self.option_groups = groups;
@@ -244,6 +427,20 @@ impl Select {
         self
     }
 
+    /// The selected values, for single and multi select alike
+    fn selected(&self) -> Vec<String> {
+        if self.multiple {
+            self.selected_values.clone()
+        } else {
+            self.selected_value.iter().cloned().collect()
+        }
+    }
+
+    /// Validate after the selection changed, which commits the value
+    fn validate_selection(&mut self, cx: &mut Context<Self>) {
+        self.validate_for(ValidateOn::Blur, cx);
+    }
+
     /// Set the size
     pub fn size(mut self, size: ComponentSize) -> Self {
         self.size = size;
@@ -375,7 +572,7 @@ impl Select {
     
     /// Get all options (flat list from both options and groups)
     fn all_options(&self) -> Vec<SelectOption> {
-        let mut all = self.options.clone();
+        let mut all = self.flat_options().to_vec();
         for group in &self.option_groups {
             all.extend(group.options.clone());
         }
@@ -401,13 +598,137 @@ impl Select {
         }
     }
 
+    /// Rebuild the dropdown rows if the options changed since they were built
+    fn sync_menu_items(&mut self) {
+        let key = ItemsKey {
+            query: None,
+            revision: self.async_options.as_ref().map_or(0, |source| source.revision()),
+        };
+        if !self.dropdown.has_items_for(&key) {
+            let items = self.menu_items();
+            self.dropdown.set_items(key, items);
+        }
+    }
+
+    /// Options in display order, and the dropdown rows showing them
+    fn menu_items(&self) -> MenuItems {
+        let items = if self.option_groups.is_empty() {
+            MenuItems::flat(self.flat_options().to_vec())
+        } else {
+            MenuItems::grouped(self.option_groups.clone())
+        };
+        items.with_status(self.async_options.as_ref().is_some_and(|source| source.has_status()))
+    }
+
     /// Toggle dropdown open/closed
-    fn toggle_dropdown(&mut self) {
-        if !self.disabled {
-            self.is_open = !self.is_open;
+    fn toggle_dropdown(&mut self, cx: &mut Context<Self>) {
+        if self.is_open {
+            self.is_open = false;
+        } else {
+            self.open_dropdown(cx);
+        }
+    }
+
+    /// Open the dropdown with the selected option highlighted
+    fn open_dropdown(&mut self, cx: &mut Context<Self>) {
+        if self.disabled || self.is_open {
+            return;
+        }
+        self.is_open = true;
+        if let Some(source) = &mut self.async_options {
+            if !source.has_query("") {
+                source.load("", false, Self::async_field, cx);
+            }
+        }
+        self.sync_menu_items();
+        let options = &self.dropdown.items().options;
+        let selected = if self.multiple {
+            self.selected_values.first()
+        } else {
+            self.selected_value.as_ref()
+        };
+        self.highlighted = selected
+            .and_then(|value| options.iter().position(|opt| &opt.value == value))
+            .or_else(|| options.iter().position(|opt| !opt.disabled));
+        if let Some(index) = self.highlighted {
+            self.dropdown.scroll_to_option(index);
         }
     }
 
+    /// Highlight an option and scroll it into view
+    fn highlight(&mut self, index: Option<usize>) {
+        self.highlighted = index;
+        if let Some(index) = index {
+            self.dropdown.reveal_option(index);
+        }
+    }
+
+    /// Select the highlighted option, or open the dropdown if it is closed
+    fn activate_highlighted(&mut self, cx: &mut Context<Self>) {
+        if !self.is_open {
+            self.open_dropdown(cx);
+            return;
+        }
+        let option = self
+            .highlighted
+            .and_then(|index| self.dropdown.items().options.get(index).cloned())
+            .filter(|option| !option.disabled);
+        if let Some(option) = option {
+            self.select_option(option.value, cx);
+        }
+    }
+
+    fn handle_key_down(&mut self, event: &KeyDownEvent, window: &mut Window, cx: &mut Context<Self>) {
+        if self.disabled {
+            return;
+        }
+        let keystroke = &event.keystroke;
+        let key = keystroke.key.as_str();
+        let now = Instant::now();
+
+        match key {
+            "tab" => {
+                self.is_open = false;
+                if keystroke.modifiers.shift {
+                    window.focus_prev();
+                } else {
+                    window.focus_next();
+                }
+            }
+            "escape" if self.is_open => self.is_open = false,
+            "enter" => self.activate_highlighted(cx),
+            // Space selects, unless it is part of a type-ahead search
+            "space" if !self.typeahead.is_active(now) => self.activate_highlighted(cx),
+            _ => {
+                let options = &self.dropdown.items().options;
+                let enabled = |ix: usize| !options[ix].disabled;
+                if let Some(index) = move_highlight(self.highlighted, options.len(), key, enabled) {
+                    if self.is_open {
+                        self.highlight(index);
+                    } else {
+                        self.open_dropdown(cx);
+                    }
+                } else if let Some(text) = keystroke
+                    .key_char
+                    .as_deref()
+                    .filter(|_| !keystroke.modifiers.control && !keystroke.modifiers.platform)
+                {
+                    self.open_dropdown(cx);
+                    // Disabled options never match
+                    let labels = self.dropdown.items().options.iter()
+                        .map(|opt| if opt.disabled { "" } else { opt.label.as_str() });
+                    if let Some(index) = self.typeahead.search(text, labels, self.highlighted, now) {
+                        self.highlight(Some(index));
+                    }
+                } else {
+                    return;
+                }
+            }
+        }
+        cx.stop_propagation();
+        cx.notify();
+    }
+
     /// Close the dropdown
     fn close_dropdown(&mut self, cx: &mut Context<Self>) {
         // Don't close if we're clicking inside the menu in multi-select mode
@@ -430,11 +751,13 @@ impl Select {
             } else {
                 self.selected_values.push(value);
             }
+            self.validate_selection(cx);
             cx.emit(SelectEvent::MultiChanged(self.selected_values.clone()));
         } else {
             // Single select mode
             self.selected_value = Some(value.clone());
             self.is_open = false;
+            self.validate_selection(cx);
             cx.emit(SelectEvent::Changed(value));
         }
         cx.notify();
@@ -444,6 +767,7 @@ impl Select {
     fn remove_value(&mut self, value: String, cx: &mut Context<Self>) {
         if let Some(pos) = self.selected_values.iter().position(|v| v == &value) {
             self.selected_values.remove(pos);
+            self.validate_selection(cx);
             cx.emit(SelectEvent::MultiChanged(self.selected_values.clone()));
             cx.notify();
         }
@@ -454,11 +778,13 @@ impl Select {
         if self.multiple {
             if !self.selected_values.is_empty() {
                 self.selected_values.clear();
+                self.validate_selection(cx);
                 cx.emit(SelectEvent::MultiChanged(vec![]));
                 cx.notify();
             }
         } else if self.selected_value.is_some() {
             self.selected_value = None;
+            self.validate_selection(cx);
             cx.emit(SelectEvent::Changed("".to_string()));
             cx.notify();
         }
@@ -472,6 +798,7 @@ impl Select {
                 None => self.clear_selection(cx),
                 Some(val) => {
                     self.selected_values = vec![val.clone()];
+                    self.validate_selection(cx);
                     cx.emit(SelectEvent::MultiChanged(self.selected_values.clone()));
                     cx.notify();
                 }
@@ -482,6 +809,7 @@ impl Select {
                 None => self.clear_selection(cx),
                 Some(val) => {
                     self.selected_value = Some(val.clone());
+                    self.validate_selection(cx);
                     cx.emit(SelectEvent::Changed(val));
                     cx.notify();
                 }
@@ -492,16 +820,16 @@ impl Select {
     /// Render the dropdown overlay (positioning layer)
     /// This layer handles the absolute positioning of the dropdown menu
     fn render_dropdown_overlay(&self, cx: &Context<Self>) -> impl IntoElement {
-        let theme = Theme::default();
+        let theme = cx.theme().clone();
 
         div()
             .absolute()
             .map(|this| match self.dropdown_direction {
                 DropdownDirection::Down | DropdownDirection::Auto => {
-                    this.top_full().mt_1()
+                    this.top_full().mt(px(theme.spacing.xxs))
                 }
                 DropdownDirection::Up => {
-                    this.bottom_full().mb_1()
+                    this.bottom_full().mb(px(theme.spacing.xxs))
                 }
             })
             .map(|this| match self.dropdown_alignment {
@@ -528,9 +856,7 @@ impl Select {
     /// Render the dropdown menu (content and styles layer)
     /// This layer handles the visual appearance and content of the dropdown
     fn render_dropdown_menu(&self, theme: &Theme, cx: &Context<Self>) -> impl IntoElement {
-        let has_groups = !self.option_groups.is_empty();
-
-        let mut menu = div()
+        div()
             .occlude()
             .id("select-popup")
             .map(|this| match self.dropdown_width {
@@ -551,88 +877,84 @@ impl Select {
             .when(matches!(self.dropdown_width, DropdownWidth::MatchTrigger), |this| {
                 this.min_w(px(180.))  // Default minimum width
             })
-            .max_h(px(300.))
-            .overflow_y_scroll()
-            .rounded(px(BorderRadius::LG))
+            .rounded(px(theme.radius.lg))
             .border_1()
             .border_color(theme.colors.border)
             .bg(theme.colors.background)
             .when(self.show_shadow, |this| {
-                this.shadow(vec![
-                    BoxShadow {
-                        color: rgba(0x00000010).into(),
-                        offset: point(px(0.), px(4.)),
-                        blur_radius: px(16.),
-                        spread_radius: px(-2.),
-                    },
-                    BoxShadow {
-                        color: rgba(0x00000008).into(),
-                        offset: point(px(0.), px(2.)),
-                        blur_radius: px(8.),
-                        spread_radius: px(0.),
-                    },
-                ])
+                this.shadow(theme.shadow_lg())
             })
-            .p(px(6.));
+            .p(px(theme.spacing.xs))
+            // Only the visible rows are rendered, see `render_row`
+            .child(self.dropdown.render(px(288.), cx, Self::render_row))
+    }
 
-        // Render grouped or flat options
-        if has_groups {
-            let mut item_counter: usize = 0;
-            menu = menu.children(self.option_groups.iter().enumerate().map(|(group_idx, group)| {
-                div()
-                    .flex()
-                    .flex_col()
-                    .map(|this| {
-                        // Add top margin for groups after the first one
-                        if group_idx > 0 {
-                            this.mt(if self.compact { px(2.) } else { px(4.) })
-                        } else {
-                            this
-                        }
-                    })
-                    .child({
-                        // Group label with clear, bold styling
-                        let label_py = if self.compact { px(4.) } else { px(8.) };
-                        let label_px = if self.compact { px(8.) } else { px(12.) };
+    /// Render a row of the dropdown menu: a group label, an option, a
+    /// separator or the status of an async source
+    fn render_row(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) -> AnyElement {
+        let theme = cx.theme().clone();
+        let items = self.dropdown.items();
 
-                        div()
-                            .px(label_px)
-                            .py(label_py)
-                            .text_sm()
-                            .font_weight(FontWeight::BOLD)
-                            .text_color(theme.colors.text)
-                            .child(group.label.clone())
+        match self.dropdown.row(ix) {
+            Some(MenuRow::GroupLabel(group_ix)) => {
+                // Group label with clear, bold styling
+                let label_py = if self.compact { px(theme.spacing.xxs) } else { px(theme.spacing.sm) };
+                let label_px = if self.compact { px(theme.spacing.sm) } else { px(theme.spacing.md) };
+
+                div()
+                    // Add top margin for groups after the first one
+                    .when(group_ix > 0, |this| {
+                        this.mt(if self.compact { px(2.) } else { px(4.) })
                     })
-                    .children(group.options.iter().map(|option| {
-                        let id = ("select-group-item", item_counter);
-                        item_counter += 1;
-                        // Wrap option with indentation for grouped items
-                        div()
-                            .pl(px(8.))
-                            .child(self.render_option(option, id, theme, cx))
-                    }))
-                    .child(
-                        // Separator line below the last option in this group
-                        div()
-                            .h(px(1.))
-                            .bg(theme.colors.border)
-                            .mx(px(12.))
-                            .mt(if self.compact { px(1.) } else { px(2.) })
-                    )
-            }));
-        } else {
-            menu = menu.children(self.options.iter().enumerate().map(|(idx, option)| {
-                self.render_option(option, ("select-item", idx), theme, cx)
-            }));
+                    .px(label_px)
+                    .py(label_py)
+                    .text_sm()
+                    .font_weight(FontWeight(theme.typography.font_weight_bold))
+                    .text_color(theme.colors.text)
+                    .child(items.group_labels[group_ix].clone())
+                    .into_any_element()
+            }
+            Some(MenuRow::Option(option_ix)) => {
+                let highlighted = self.highlighted == Some(option_ix);
+                let option = self.render_option(&items.options[option_ix], ("select-item", option_ix), highlighted, &theme, window, cx);
+                if items.is_grouped() {
+                    // Wrap option with indentation for grouped items
+                    div().pl(px(theme.spacing.sm)).child(option).into_any_element()
+                } else {
+                    option.into_any_element()
+                }
+            }
+            Some(MenuRow::Separator) => {
+                // Separator line below the last option in a group
+                div()
+                    .h(px(1.))
+                    .bg(theme.colors.border)
+                    .mx(px(theme.spacing.md))
+                    .mt(if self.compact { px(1.) } else { px(2.) })
+                    .into_any_element()
+            }
+            Some(MenuRow::Status) => self
+                .async_options
+                .as_ref()
+                .and_then(|source| source.render_status(Self::async_field, &theme, cx))
+                .unwrap_or_else(|| div().into_any_element()),
+            // Only a creatable Combobox has a create row
+            Some(MenuRow::Create) | None => div().into_any_element(),
         }
-
-        menu
     }
 
     /// Render a single option item
-    fn render_option(&self, option: &SelectOption, id: impl Into<ElementId>, theme: &Theme, cx: &Context<Self>) -> impl IntoElement {
+    fn render_option(
+        &self,
+        option: &SelectOption,
+        id: impl Into<ElementId>,
+        highlighted: bool,
+        theme: &Theme,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
         let value = option.value.clone();
-        let label = option.label.clone();
+        let disabled = option.disabled;
         let multiple = self.multiple;
         let size = self.size;
 
@@ -641,10 +963,11 @@ impl Select {
         } else {
             self.selected_value.as_ref() == Some(&value)
         };
+        let content = option.render_content(option.label.clone(), is_selected && !multiple, theme, window, cx);
 
         // Use compact spacing if enabled
-        let padding_y = if self.compact { px(3.) } else { px(8.) };
-        let padding_x = if self.compact { px(8.) } else { px(12.) };
+        let padding_y = if self.compact { px(theme.spacing.xxs) } else { px(theme.spacing.sm) };
+        let padding_x = if self.compact { px(theme.spacing.sm) } else { px(theme.spacing.md) };
 
         div()
             .id(id)
@@ -656,36 +979,38 @@ impl Select {
             .px(padding_x)
             .py(padding_y)
             .min_h(px(32.)) // 设置最小高度
-            .cursor(CursorStyle::PointingHand)
-            .text_size(self.custom_font_size.unwrap_or(size.font_size()))
-            .rounded(px(BorderRadius::SM))
+            .text_size(self.custom_font_size.unwrap_or(theme.font_size(size)))
+            .rounded(px(theme.radius.sm))
             .map(|this| {
                 if is_selected && !multiple {
                     this.bg(theme.colors.primary)
-                        .text_color(rgb(0xFFFFFF))
+                        .text_color(theme.colors.text_on_primary)
+                } else if disabled {
+                    this.text_color(self.custom_text_color.unwrap_or(theme.colors.text))
                 } else {
                     this.text_color(self.custom_text_color.unwrap_or(theme.colors.text))
+                        .when(highlighted, |this| this.bg(theme.colors.background_hover))
                         .hover(|style| style.bg(theme.colors.background_hover))
                 }
             })
-            .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event: &MouseDownEvent, _window, cx| {
-                this.select_option(value.clone(), cx);
-            }))
+            .when(disabled, |this| this.opacity(0.5))
+            .when(!disabled, |this| {
+                this.cursor(CursorStyle::PointingHand)
+                    .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event: &MouseDownEvent, _window, cx| {
+                        this.select_option(value.clone(), cx);
+                    }))
+            })
             .child(
                 div()
                     .flex()
                     .items_start() // 改为 start 以支持多行文本
-                    .gap_2()
+                    .gap(px(theme.spacing.sm))
                     .w_full()
                     // Checkbox for multi-select
                     .when(multiple, |this| {
                         this.child(self.render_checkbox(is_selected, theme))
                     })
-                    .child(
-                        div()
-                            .flex_1()
-                            .child(label)
-                    )
+                    .child(content)
             )
             // Show checkmark for single select
             .when(is_selected && !multiple, |this| {
@@ -705,15 +1030,15 @@ impl Select {
             .justify_center()
             .w(px(16.))
             .h(px(16.))
-            .rounded(px(4.))
+            .rounded(px(theme.radius.sm))
             .border_1()
             .border_color(if checked { theme.colors.primary } else { theme.colors.border })
-            .bg(if checked { theme.colors.primary } else { rgb(0xFFFFFF) })
+            .bg(if checked { theme.colors.primary } else { theme.colors.background })
             .when(checked, |this| {
                 this.child(
                     div()
                         .text_xs()
-                        .text_color(rgb(0xFFFFFF))
+                        .text_color(theme.colors.text_on_primary)
                         .child("✓")
                 )
             })
@@ -722,6 +1047,7 @@ impl Select {
     /// Render selected tags for multi-select
     fn render_selected_tags(&self, theme: &Theme, cx: &Context<Self>) -> Vec<impl IntoElement> {
         let all_options = self.all_options();
+        let remove_hover_bg = crate::utils::with_alpha(theme.colors.text_on_primary, 0.125);
 
         self.selected_values.iter().map(|value| {
             let label = all_options
@@ -735,12 +1061,12 @@ impl Select {
             div()
                 .flex()
                 .items_center()
-                .gap_1()
-                .px(px(8.))
-                .py(px(4.))
-                .rounded(px(6.))
+                .gap(px(theme.spacing.xxs))
+                .px(px(theme.spacing.sm))
+                .py(px(theme.spacing.xxs))
+                .rounded(px(theme.radius.md))
                 .bg(theme.colors.primary)
-                .text_color(rgb(0xFFFFFF))
+                .text_color(theme.colors.text_on_primary)
                 .text_xs()
                 .child(label)
                 .child(
@@ -753,7 +1079,7 @@ impl Select {
                         .h(px(14.))
                         .rounded(px(7.))
                         .cursor(CursorStyle::PointingHand)
-                        .hover(|style| style.bg(rgba(0xFFFFFF20)))
+                        .hover(move |style| style.bg(remove_hover_bg))
                         .on_mouse_down(MouseButton::Left, cx.listener(move |this, _event: &MouseDownEvent, _window, cx| {
                             this.remove_value(value_for_remove.clone(), cx);
                         }))
@@ -767,26 +1093,84 @@ impl Select {
     }
 }
 
+impl FormControl for Select {
+    fn form_value(&self) -> Value {
+        if self.multiple {
+            Value::from(self.selected_values.clone())
+        } else {
+            self.selected_value.clone().map_or(Value::Null, Value::String)
+        }
+    }
+
+    fn set_form_value(&mut self, value: &Value, cx: &mut Context<Self>) {
+        let values = value_to_strings(value);
+        if self.multiple {
+            self.selected_values = values;
+            cx.emit(SelectEvent::MultiChanged(self.selected_values.clone()));
+        } else {
+            self.selected_value = values.into_iter().next();
+            cx.emit(SelectEvent::Changed(self.selected_value.clone().unwrap_or_default()));
+        }
+        cx.notify();
+    }
+
+    fn validate_form(&mut self, cx: &mut Context<Self>) -> ValidationState {
+        self.run_validation(cx);
+        self.validation.state().clone()
+    }
+
+    fn form_validation_state(&self) -> ValidationState {
+        self.validation.state().clone()
+    }
+
+    fn clear_form_error(&mut self, cx: &mut Context<Self>) {
+        self.set_error(None, cx);
+    }
+}
+
+impl Focusable for Select {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
 // ============================================================================
 // Render
 // ============================================================================
 
 impl Render for Select {
-    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let theme = Theme::default();
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme().clone();
         let disabled = self.disabled;
         let is_open = self.is_open;
         let multiple = self.multiple;
-        let (padding_y, padding_x) = self.size.padding();
+        let is_focused = self.focus_handle.is_focused(window);
+        let (padding_y, padding_x) = theme.padding(self.size);
+        if is_open {
+            // Pick up options changed since the dropdown opened, e.g. loaded ones
+            self.sync_menu_items();
+        }
 
         let is_placeholder = if multiple {
             self.selected_values.is_empty()
         } else {
             self.selected_value.is_none()
         };
+        let error = self.validation.error().map(str::to_string);
+        // An error overrides the border color of every variant
+        let border_color = match error {
+            Some(_) => theme.colors.border_error,
+            None => self.custom_border_color.unwrap_or(if is_focused {
+                theme.colors.border_focus
+            } else {
+                theme.colors.border
+            }),
+        };
 
         div()
             .id("select-wrapper")
+            .track_focus(&self.focus_handle)
+            .on_key_down(cx.listener(Self::handle_key_down))
             .w_full()
             // Close dropdown when clicking outside (only for single select, multi-select uses backdrop)
             .when(is_open && !multiple, |this| {
@@ -807,13 +1191,13 @@ impl Render for Select {
                             .flex()
                             .w_full()
                             .items_center()
-                            .gap_2() // Remove justify_between to keep content and icon close together
+                            .gap(px(theme.spacing.sm)) // Remove justify_between to keep content and icon close together
                             .py(padding_y)
                             .px(padding_x)
-                            .rounded(px(BorderRadius::LG))
+                            .rounded(px(theme.radius.lg))
                             .when(self.show_border && self.variant != SelectVariant::Ghost, |this| {
                                 this.border_1()
-                                    .border_color(self.custom_border_color.unwrap_or(theme.colors.border))
+                                    .border_color(border_color)
                             })
                             .map(|this| match self.variant {
                                 SelectVariant::Default => {
@@ -825,17 +1209,12 @@ impl Render for Select {
                                 SelectVariant::Outline => {
                                     this.bg(self.custom_bg_color.unwrap_or(rgba(0x00000000)))
                                         .border_1()
-                                        .border_color(self.custom_border_color.unwrap_or(theme.colors.border))
+                                        .border_color(border_color)
                                 }
                             })
-                            .text_size(self.custom_font_size.unwrap_or(self.size.font_size()))
+                            .text_size(self.custom_font_size.unwrap_or(theme.font_size(self.size)))
                             .when(self.show_shadow, |this| {
-                                this.shadow(vec![BoxShadow {
-                                    color: rgba(0x0000000A).into(),
-                                    offset: point(px(0.), px(1.)),
-                                    blur_radius: px(2.),
-                                    spread_radius: px(0.),
-                                }])
+                                this.shadow(theme.shadow_sm())
                             })
                             .when(!disabled, |this| {
                                 this.cursor(CursorStyle::PointingHand)
@@ -843,8 +1222,9 @@ impl Render for Select {
                             .when(disabled, |this| {
                                 this.opacity(0.64)
                             })
-                            .on_mouse_down(MouseButton::Left, cx.listener(|this, _event: &MouseDownEvent, _window, cx| {
-                                this.toggle_dropdown();
+                            .on_mouse_down(MouseButton::Left, cx.listener(|this, _event: &MouseDownEvent, window, cx| {
+                                this.focus_handle.focus(window);
+                                this.toggle_dropdown(cx);
                                 cx.notify();
                             }))
                             .child(
@@ -852,7 +1232,7 @@ impl Render for Select {
                                 div()
                                     .flex()
                                     .items_center()
-                                    .gap_2() // Small gap between text and icon
+                                    .gap(px(theme.spacing.sm)) // Small gap between text and icon
                                     .when(multiple && !self.selected_values.is_empty(), |this| {
                                         // Show tags for multi-select
                                         this.children(self.render_selected_tags(&theme, cx))
@@ -878,7 +1258,7 @@ impl Render for Select {
                                         // Chevron up/down icon - directly after text with small gap
                                         Icon::new(IconName::ChevronUpDown)
                                             .small()
-                                            .color(rgb(0x666666))
+                                            .color(theme.colors.text_secondary)
                                     )
                             )
                     )
@@ -891,5 +1271,8 @@ impl Render for Select {
                         None
                     })
             )
+            .when_some(error, |this, message| {
+                this.child(render_error_message(&message, &theme))
+            })
     }
 }
@@ -1,12 +1,35 @@
 use gpui::*;
 use gpui::prelude::FluentBuilder;
+use serde_json::Value;
 use std::sync::{Arc, Mutex};
-
-// Element state for storing layout information per line
-#[derive(Clone)]
+use std::time::Duration;
+use arboard::Clipboard;
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::form_container::{value_to_string, FormControl};
+use super::text_buffer::TextBuffer;
+use super::validation::{render_error_message, validation_methods, ValidateOn, Validation, ValidationState};
+use crate::theme::*;
+use crate::utils::{floor_grapheme_boundary, offset_to_utf16, previous_grapheme_boundary};
+
+// Element state for storing layout information per visual line
+#[derive(Clone, Default)]
 struct TextAreaLineLayout {
     bounds: Bounds<Pixels>,
     shaped_line: ShapedLine,
+    /// Byte offset of the first character on this line
+    start: usize,
+}
+
+// A visual line: a hard line, or a soft-wrapped segment of one
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct VisualLine {
+    /// Byte offset of the first character
+    start: usize,
+    /// Byte offset after the last character (excluding the newline)
+    end: usize,
+    /// Whether this line continues the previous one after a soft wrap
+    wrapped: bool,
 }
 
 // Custom element wrapper to register input handler
@@ -75,6 +98,21 @@ impl Element for TextAreaElement {
             ElementInputHandler::new(bounds, self.entity.clone()),
             cx,
         );
+
+        // Track drags at the window level so selection and scrollbar drags
+        // keep working when the mouse leaves the text area
+        let entity = self.entity.clone();
+        window.on_mouse_event(move |event: &MouseMoveEvent, phase, _window, cx| {
+            if phase == DispatchPhase::Bubble {
+                entity.update(cx, |this, cx| this.drag_to(event.position, cx));
+            }
+        });
+        let entity = self.entity.clone();
+        window.on_mouse_event(move |event: &MouseUpEvent, phase, _window, cx| {
+            if phase == DispatchPhase::Bubble && event.button == MouseButton::Left {
+                entity.update(cx, |this, cx| this.end_drag(cx));
+            }
+        });
     }
 }
 
@@ -103,22 +141,14 @@ pub enum TextAreaEvent {
 
 /// A multi-line text area component
 pub struct TextArea {
-    /// Current text value
-    value: String,
-    /// Cursor position (character index)
-    cursor_pos: usize,
-    /// Selection start position (character index, None if no selection)
-    selection_start: Option<usize>,
-    /// Selection end position (character index, None if no selection)
-    selection_end: Option<usize>,
+    /// Value, cursor, selection, IME composition and undo history
+    buffer: TextBuffer,
     /// Placeholder text when empty
     placeholder: String,
     /// Focus handle for keyboard input
     focus_handle: FocusHandle,
     /// Whether the textarea is disabled
     disabled: bool,
-    /// Maximum length (None for unlimited)
-    max_length: Option<usize>,
     /// Minimum height in pixels
     min_height: f32,
     /// Maximum height in pixels (None for unlimited)
@@ -139,24 +169,50 @@ pub struct TextArea {
     _blink_task: Option<Task<()>>,
     /// Whether mouse is currently dragging for selection
     is_dragging: bool,
-    /// Last layout info for mouse position calculation (per line)
-    last_layout: Vec<TextAreaLineLayout>,
-    /// Marked text range for IME (Input Method Editor) composition
-    marked_range: Option<std::ops::Range<usize>>,
+    /// Layout info for each visual line, filled during paint
+    line_layouts: Arc<Mutex<Vec<TextAreaLineLayout>>>,
+    /// Visual lines from the last render, after soft wrapping
+    visual_lines: Vec<VisualLine>,
+    /// Text width measured during the last paint, used for soft wrapping
+    wrap_width: Arc<Mutex<Option<Pixels>>>,
+    /// Vertical scroll offset of the text, in pixels
+    scroll_top: Pixels,
+    /// Height of the visible text area, updated on render
+    viewport_height: Pixels,
+    /// Height of all visual lines, updated on render
+    content_height: Pixels,
+    /// Whether the next render should scroll the cursor into view
+    autoscroll_pending: bool,
+    /// Bounds of the visible text area, measured during paint
+    viewport_bounds: Arc<Mutex<Option<Bounds<Pixels>>>>,
+    /// Last mouse position while drag-selecting
+    drag_position: Option<Point<Pixels>>,
+    /// Task scrolling the text while drag-selecting past the edge
+    _drag_scroll_task: Option<Task<()>>,
+    /// Scrollbar thumb drag: mouse y and scroll offset when the drag started
+    scrollbar_drag: Option<(Pixels, Pixels)>,
+    /// Line height from the last render
+    line_height: Pixels,
+    /// Goal x for up/down movement, with the cursor position it applies to
+    goal_x: Option<(usize, Pixels)>,
+    /// Validation rules and the current error
+    validation: Validation<str>,
+    /// Runs blur validation, registered on first render
+    _blur_subscription: Option<Subscription>,
+}
+
+validation_methods! {
+    TextArea: str = |this| this.buffer.text().to_string()
 }
 
 impl TextArea {
     /// Create a new TextArea
     pub fn new(cx: &mut Context<Self>) -> Self {
         Self {
-            value: String::new(),
-            cursor_pos: 0,
-            selection_start: None,
-            selection_end: None,
+            buffer: TextBuffer::default(),
             placeholder: String::new(),
             focus_handle: cx.focus_handle(),
             disabled: false,
-            max_length: None,
             min_height: 100.0,
             max_height: None,
             bg_color: None,
@@ -167,94 +223,151 @@ impl TextArea {
             cursor_visible: true,
             _blink_task: None,
             is_dragging: false,
-            last_layout: Vec::new(),
-            marked_range: None,
+            line_layouts: Arc::new(Mutex::new(Vec::new())),
+            visual_lines: Vec::new(),
+            wrap_width: Arc::new(Mutex::new(None)),
+            scroll_top: px(0.),
+            viewport_height: px(0.),
+            content_height: px(0.),
+            autoscroll_pending: false,
+            viewport_bounds: Arc::new(Mutex::new(None)),
+            drag_position: None,
+            _drag_scroll_task: None,
+            scrollbar_drag: None,
+            line_height: px(20.),
+            goal_x: None,
+            validation: Validation::default(),
+            _blur_subscription: None,
         }
     }
 
     /// Check if text contains Chinese/Japanese/Korean characters
     fn contains_cjk(text: &str) -> bool {
-        text.chars().any(|c| {
-            let cp = c as u32;
-            (0x4E00..=0x9FFF).contains(&cp) || // CJK统一汉字
-            (0x3400..=0x4DBF).contains(&cp) || // CJK扩展A
-            (0x20000..=0x2A6DF).contains(&cp) || // CJK扩展B
-            (0x2A700..=0x2B73F).contains(&cp) || // CJK扩展C
-            (0x2B740..=0x2B81F).contains(&cp) || // CJK扩展D
-            (0x2B820..=0x2CEAF).contains(&cp) || // CJK扩展E
-            (0x2CEB0..=0x2EBEF).contains(&cp) || // CJK扩展F
-            (0x3000..=0x303F).contains(&cp) || // CJK符号和标点
-            (0xFF00..=0xFFEF).contains(&cp)    // 全角ASCII、全角标点
-        })
+        text.chars().any(Self::is_cjk)
     }
 
-    /// Ensure cursor position is within valid bounds
-    fn validate_cursor_position(&mut self) {
-        let char_count = self.value.chars().count();
-        if self.cursor_pos > char_count {
-            self.cursor_pos = char_count;
-        }
+    /// Check if a character is a Chinese/Japanese/Korean character
+    fn is_cjk(c: char) -> bool {
+        let cp = c as u32;
+        (0x4E00..=0x9FFF).contains(&cp) || // CJK统一汉字
+        (0x3400..=0x4DBF).contains(&cp) || // CJK扩展A
+        (0x20000..=0x2A6DF).contains(&cp) || // CJK扩展B
+        (0x2A700..=0x2B73F).contains(&cp) || // CJK扩展C
+        (0x2B740..=0x2B81F).contains(&cp) || // CJK扩展D
+        (0x2B820..=0x2CEAF).contains(&cp) || // CJK扩展E
+        (0x2CEB0..=0x2EBEF).contains(&cp) || // CJK扩展F
+        (0x3000..=0x303F).contains(&cp) || // CJK符号和标点
+        (0xFF00..=0xFFEF).contains(&cp)    // 全角ASCII、全角标点
     }
 
-    /// Safely remove character at the given position
-    fn remove_char_at(&mut self, pos: usize) -> bool {
-        let chars: Vec<char> = self.value.chars().collect();
-        if pos < chars.len() {
-            let mut new_chars = Vec::new();
-            new_chars.extend_from_slice(&chars[..pos]);
-            new_chars.extend_from_slice(&chars[pos + 1..]);
-            self.value = new_chars.into_iter().collect();
-            true
-        } else {
-            false
-        }
-    }
+    /// Find soft wrap positions (byte offsets where continuation lines start)
+    /// for a single hard line.
+    ///
+    /// Latin text breaks after whitespace, CJK text between any two characters.
+    /// A word wider than `width` is broken between characters. Grapheme
+    /// clusters are never split.
+    fn wrap_boundaries(line: &str, shaped_line: &ShapedLine, width: Pixels) -> Vec<usize> {
+        let mut boundaries = Vec::new();
+        let mut line_start = 0;
+        let mut last_opportunity = None;
+        let mut prev: Option<char> = None;
+
+        for (idx, grapheme) in line.grapheme_indices(true) {
+            let c = grapheme.chars().next().unwrap_or_default();
+            if let Some(prev) = prev {
+                if idx > line_start
+                    && ((prev.is_whitespace() && !c.is_whitespace()) || Self::is_cjk(prev) || Self::is_cjk(c))
+                {
+                    last_opportunity = Some(idx);
+                }
+            }
+            prev = grapheme.chars().last();
 
-    /// Convert character range to byte range
-    fn char_range_to_byte_range(&self, char_range: &std::ops::Range<usize>) -> std::ops::Range<usize> {
-        // Handle empty string case
-        if self.value.is_empty() {
-            return 0..0;
-        }
+            // Trailing whitespace may hang past the edge
+            if c.is_whitespace() {
+                continue;
+            }
 
-        // Handle cursor position (empty range)
-        if char_range.start == char_range.end {
-            // Find the byte position for the character index
-            for (char_count, (byte_idx, _)) in self.value.char_indices().enumerate() {
-                if char_count == char_range.start {
-                    return byte_idx..byte_idx;
-                }
+            let end = idx + grapheme.len();
+            if idx > line_start
+                && shaped_line.x_for_index(end) - shaped_line.x_for_index(line_start) > width
+            {
+                let at = last_opportunity.filter(|at| *at > line_start).unwrap_or(idx);
+                boundaries.push(at);
+                line_start = at;
+                last_opportunity = None;
             }
-            // If we reach here, the cursor is at the end
-            return self.value.len()..self.value.len();
         }
 
-        let mut byte_start = 0;
-        let mut byte_end = self.value.len();
-        let mut found_start = false;
+        boundaries
+    }
 
-        for (char_count, (byte_idx, _)) in self.value.char_indices().enumerate() {
-            if char_count == char_range.start && !found_start {
-                byte_start = byte_idx;
-                found_start = true;
-            }
-            if char_count == char_range.end {
-                byte_end = byte_idx;
-                break;
+    /// Split the value into visual lines, soft-wrapping each hard line to `wrap_width`
+    fn layout_visual_lines(
+        &self,
+        font: &Font,
+        font_size: Pixels,
+        wrap_width: Option<Pixels>,
+        window: &mut Window,
+    ) -> Vec<VisualLine> {
+        let mut visual_lines = Vec::new();
+        let mut line_start = 0;
+
+        for line in self.buffer.text().split('\n') {
+            let boundaries = match wrap_width {
+                Some(width) if !line.is_empty() => {
+                    let runs = [TextRun {
+                        len: line.len(),
+                        font: font.clone(),
+                        color: Hsla::default(),
+                        background_color: None,
+                        underline: None,
+                        strikethrough: None,
+                    }];
+                    let shaped_line = window.text_system().shape_line(
+                        line.to_string().into(),
+                        font_size,
+                        &runs,
+                        None,
+                    );
+                    if shaped_line.width > width {
+                        Self::wrap_boundaries(line, &shaped_line, width)
+                    } else {
+                        Vec::new()
+                    }
+                }
+                _ => Vec::new(),
+            };
+
+            let mut start = 0;
+            for boundary in boundaries {
+                visual_lines.push(VisualLine {
+                    start: line_start + start,
+                    end: line_start + boundary,
+                    wrapped: start > 0,
+                });
+                start = boundary;
             }
-        }
+            visual_lines.push(VisualLine {
+                start: line_start + start,
+                end: line_start + line.len(),
+                wrapped: start > 0,
+            });
 
-        // Handle case where range.start is at the very beginning
-        if char_range.start == 0 {
-            byte_start = 0;
+            line_start += line.len() + 1; // +1 for newline
         }
 
-        // Handle case where range.end is at the very end
-        if char_range.end >= self.value.chars().count() {
-            byte_end = self.value.len();
-        }
+        visual_lines
+    }
 
-        byte_start..byte_end
+    /// Index of the visual line containing the given byte offset.
+    ///
+    /// A position at a soft wrap belongs to the continuation line.
+    fn visual_line_index(&self, pos: usize) -> usize {
+        self.visual_lines
+            .iter()
+            .rposition(|line| line.start <= pos)
+            .unwrap_or(0)
     }
 
     /// Set the placeholder text
@@ -265,7 +378,7 @@ impl TextArea {
 
     /// Set the initial value
     pub fn value(mut self, value: impl Into<String>) -> Self {
-        self.value = value.into();
+        self.buffer.reset(value);
         self
     }
 
@@ -275,9 +388,9 @@ impl TextArea {
         self
     }
 
-    /// Set maximum length
+    /// Set maximum length, counted in user-perceived characters (grapheme clusters)
     pub fn max_length(mut self, max_length: usize) -> Self {
-        self.max_length = Some(max_length);
+        self.buffer.set_max_length(Some(max_length));
         self
     }
 
@@ -319,28 +432,21 @@ impl TextArea {
 
     /// Get the current value
     pub fn get_value(&self) -> &str {
-        &self.value
+        self.buffer.text()
     }
 
     /// Set the value programmatically
     pub fn set_value(&mut self, value: String, cx: &mut Context<Self>) {
-        if let Some(max_len) = self.max_length {
-            if value.len() > max_len {
-                return;
-            }
+        if self.buffer.set_text(value) {
+            self.emit_change(cx);
+            cx.notify();
         }
-
-        self.cursor_pos = value.len();
-        self.value = value.clone();
-        cx.emit(TextAreaEvent::Change(value));
-        cx.notify();
     }
 
     /// Clear the textarea
     pub fn clear(&mut self, cx: &mut Context<Self>) {
-        self.value.clear();
-        self.cursor_pos = 0;
-        cx.emit(TextAreaEvent::Change(String::new()));
+        self.buffer.clear();
+        self.emit_change(cx);
         cx.notify();
     }
 
@@ -351,112 +457,67 @@ impl TextArea {
     
     /// Select all text
     pub fn select_all(&mut self, cx: &mut Context<Self>) {
-        if !self.value.is_empty() {
-            self.selection_start = Some(0);
-            self.selection_end = Some(self.value.chars().count());
+        if !self.buffer.text().is_empty() {
+            self.buffer.select_all();
             cx.notify();
         }
     }
-    
-    /// Clear selection
-    fn clear_selection(&mut self) {
-        self.selection_start = None;
-        self.selection_end = None;
-    }
-    
-    /// Check if there is an active selection
-    fn has_selection(&self) -> bool {
-        self.selection_start.is_some() && self.selection_end.is_some()
+
+    /// Undo the last edit (Cmd/Ctrl+Z)
+    pub fn undo(&mut self, cx: &mut Context<Self>) {
+        let changed = self.buffer.undo();
+        self.after_edit(changed, cx);
     }
-    
-    /// Delete selected text and return the new cursor position
-    fn delete_selection(&mut self) -> usize {
-        if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
-            let (sel_start, sel_end) = if start <= end {
-                (start, end)
-            } else {
-                (end, start)
-            };
-            
-            let before = self.value.chars().take(sel_start).collect::<String>();
-            let after = self.value.chars().skip(sel_end).collect::<String>();
-            self.value = format!("{}{}", before, after);
-            self.clear_selection();
-            sel_start
-        } else {
-            self.cursor_pos
-        }
+
+    /// Redo the last undone edit (Cmd/Ctrl+Shift+Z, or Ctrl+Y outside macOS)
+    pub fn redo(&mut self, cx: &mut Context<Self>) {
+        let changed = self.buffer.redo();
+        self.after_edit(changed, cx);
     }
 
-    /// Get the current selection range
-    fn selected_range(&self) -> std::ops::Range<usize> {
-        if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
-            if start <= end {
-                start..end
-            } else {
-                end..start
-            }
-        } else {
-            self.cursor_pos..self.cursor_pos
-        }
+    /// Whether there is an edit to undo
+    pub fn can_undo(&self) -> bool {
+        self.buffer.can_undo()
     }
 
-    /// Convert UTF-16 range to UTF-8 range
-    fn range_from_utf16(&self, range_utf16: &std::ops::Range<usize>) -> std::ops::Range<usize> {
-        let mut utf16_count = 0;
-        let mut start = None;
-        let mut end = None;
+    /// Whether there is an undone edit to redo
+    pub fn can_redo(&self) -> bool {
+        self.buffer.can_redo()
+    }
 
-        for (byte_idx, ch) in self.value.char_indices() {
-            if start.is_none() && utf16_count >= range_utf16.start {
-                start = Some(byte_idx);
-            }
-            
-            utf16_count += ch.len_utf16();
-            
-            if end.is_none() && utf16_count >= range_utf16.end {
-                end = Some(byte_idx + ch.len_utf8());
-                break;
-            }
+    /// Show the cursor and emit `Change` if an edit changed the value
+    fn after_edit(&mut self, changed: bool, cx: &mut Context<Self>) {
+        if changed {
+            self.pause_blinking(cx);
+            self.emit_change(cx);
         }
-
-        let start = start.unwrap_or(self.value.len());
-        let end = end.unwrap_or(self.value.len());
-        
-        start..end
+        cx.notify();
     }
 
-    /// Convert character range to UTF-16 range
-    fn char_range_to_utf16(&self, char_range: &std::ops::Range<usize>) -> std::ops::Range<usize> {
-        let mut utf16_start = 0;
-        let mut utf16_end = 0;
+    /// Emit `Change` and validate the new value
+    fn emit_change(&mut self, cx: &mut Context<Self>) {
+        let text = self.buffer.text().to_string();
+        self.validate_for(ValidateOn::Change, cx);
+        cx.emit(TextAreaEvent::Change(text));
+    }
 
-        for (char_count, ch) in self.value.chars().enumerate() {
-            if char_count == char_range.start {
-                utf16_start = utf16_end;
-            }
-            utf16_end += ch.len_utf16();
-            if char_count == char_range.end {
-                break;
-            }
-        }
+    fn handle_blur(&mut self, cx: &mut Context<Self>) {
+        self.validate_for(ValidateOn::Blur, cx);
+        cx.emit(TextAreaEvent::Blur);
+        cx.notify();
+    }
 
-        // Handle case where range.end is at the very end
-        if char_range.end >= self.value.chars().count() {
-            utf16_end = self.value.encode_utf16().count();
+    /// Like `after_edit`, also emitting `Ime` when CJK text was entered
+    fn after_input(&mut self, changed: bool, text: &str, cx: &mut Context<Self>) {
+        if changed && Self::contains_cjk(text) {
+            cx.emit(TextAreaEvent::Ime(self.buffer.text().to_string()));
         }
-
-        utf16_start..utf16_end
+        self.after_edit(changed, cx);
     }
-
-    /// Convert byte range to UTF-16 range
-    fn byte_range_to_utf16(&self, range: &std::ops::Range<usize>) -> std::ops::Range<usize> {
-        let start_idx = range.start.min(self.value.len());
-        let end_idx = range.end.min(self.value.len());
-
-        let start = self.value[..start_idx].encode_utf16().count();
-        let end = self.value[..end_idx].encode_utf16().count();
-        start..end
+    
+    /// Check if there is an active selection
+    fn has_selection(&self) -> bool {
+        self.buffer.has_selection()
     }
 
     fn handle_input(&mut self, text: &str, cx: &mut Context<Self>) {
@@ -464,225 +525,244 @@ impl TextArea {
             return;
         }
 
-        // If there's a selection, delete it first
-        if self.has_selection() {
-            self.cursor_pos = self.delete_selection();
-        }
-
-        // Insert text at cursor position
-        let before = self.value.chars().take(self.cursor_pos).collect::<String>();
-        let after = self.value.chars().skip(self.cursor_pos).collect::<String>();
-        let new_value = format!("{}{}{}", before, text, after);
+        // Replace the selection (or insert at the cursor)
+        let changed = self.buffer.insert(text);
+        self.after_input(changed, text, cx);
+    }
 
-        // Check max length
-        if let Some(max_len) = self.max_length {
-            if new_value.chars().count() > max_len {
-                return;
-            }
+    /// Copy the selection to the clipboard, or all text if nothing is selected
+    fn copy(&mut self) {
+        let text = if self.has_selection() {
+            self.buffer.selected_text()
+        } else {
+            self.buffer.text()
+        };
+        if text.is_empty() {
+            return;
         }
-
-        self.value = new_value.clone();
-        self.cursor_pos += text.chars().count();
-        self.pause_blinking(cx);
-        
-        // Check for CJK characters and emit IME event if detected
-        if Self::contains_cjk(text) {
-            cx.emit(TextAreaEvent::Ime(self.value.clone()));
+        if let Ok(mut clipboard) = Clipboard::new() {
+            let _ = clipboard.set_text(text);
         }
-        
-        cx.emit(TextAreaEvent::Change(new_value));
-        cx.notify();
     }
 
-    fn handle_backspace(&mut self, cx: &mut Context<Self>) {
+    /// Copy the selection to the clipboard and delete it
+    fn cut(&mut self, cx: &mut Context<Self>) {
         if self.disabled {
             return;
         }
-
-        // If there's marked text (IME composition), clear it
-        if self.marked_range.is_some() {
-            self.marked_range = None;
-            cx.notify();
-            return;
+        if let Some(text) = self.buffer.cut() {
+            if let Ok(mut clipboard) = Clipboard::new() {
+                let _ = clipboard.set_text(&text);
+            }
+            self.after_edit(true, cx);
         }
+    }
 
-        // If there's a selection, delete it
-        if self.has_selection() {
-            self.cursor_pos = self.delete_selection();
-            self.pause_blinking(cx);
-            cx.emit(TextAreaEvent::Change(self.value.clone()));
-            cx.notify();
+    /// Paste clipboard text at the cursor, replacing the selection.
+    ///
+    /// Line endings are normalized to `\n`, and the text is truncated to fit
+    /// within `max_length`.
+    fn paste(&mut self, cx: &mut Context<Self>) {
+        if self.disabled {
             return;
         }
-
-        if self.cursor_pos == 0 {
+        let Ok(mut clipboard) = Clipboard::new() else {
             return;
-        }
-
-        // Ensure cursor position is valid
-        self.validate_cursor_position();
-
-        if self.cursor_pos == 0 {
+        };
+        let Ok(text) = clipboard.get_text() else {
             return;
-        }
-
-        // Safely remove character before cursor
-        if self.remove_char_at(self.cursor_pos - 1) {
-            self.cursor_pos -= 1;
-        }
+        };
 
-        self.pause_blinking(cx);
-        cx.emit(TextAreaEvent::Change(self.value.clone()));
-        cx.notify();
+        let text = text.replace("\r\n", "\n").replace('\r', "\n");
+        let changed = self.buffer.paste(&text);
+        self.after_input(changed, &text, cx);
     }
 
-    /// Extend or start selection to the cursor position
-    fn extend_selection_to(&mut self, pos: usize) {
-        // This is called when extending existing selection
-        if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
-            let (sel_start, sel_end) = if start <= end {
-                (start, end)
-            } else {
-                (end, start)
-            };
-            
-            // Extend from the end that's closer to the new position
-            if (pos as i32 - sel_start as i32).abs() < (pos as i32 - sel_end as i32).abs() {
-                // Extend from start
-                self.selection_start = Some(pos);
-                self.selection_end = Some(sel_end);
-            } else {
-                // Extend from end
-                self.selection_start = Some(sel_start);
-                self.selection_end = Some(pos);
-            }
+    fn handle_backspace(&mut self, cx: &mut Context<Self>) {
+        if self.disabled {
+            return;
         }
+        let changed = self.buffer.backspace();
+        self.after_edit(changed, cx);
     }
     
     fn move_cursor_left(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
-        if self.cursor_pos > 0 {
-            let old_pos = self.cursor_pos;
-            self.cursor_pos -= 1;
-            if extend_selection {
-                if !self.has_selection() {
-                    // Start new selection: old position is anchor, new position is end
-                    self.selection_start = Some(old_pos);
-                    self.selection_end = Some(self.cursor_pos);
-                } else {
-                    self.extend_selection_to(self.cursor_pos);
-                }
-            } else {
-                self.clear_selection();
-            }
-            self.pause_blinking(cx);
-            cx.notify();
-        }
+        self.buffer.move_left(extend_selection);
+        self.pause_blinking(cx);
+        cx.notify();
     }
     
     fn move_cursor_right(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
-        let char_count = self.value.chars().count();
-        if self.cursor_pos < char_count {
-            let old_pos = self.cursor_pos;
-            self.cursor_pos += 1;
-            if extend_selection {
-                if !self.has_selection() {
-                    // Start new selection: old position is anchor, new position is end
-                    self.selection_start = Some(old_pos);
-                    self.selection_end = Some(self.cursor_pos);
-                } else {
-                    self.extend_selection_to(self.cursor_pos);
-                }
-            } else {
-                self.clear_selection();
-            }
-            self.pause_blinking(cx);
-            cx.notify();
-        }
+        self.buffer.move_right(extend_selection);
+        self.pause_blinking(cx);
+        cx.notify();
     }
     
     fn move_cursor_home(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
-        // For now, move to start of text (could be enhanced to move to start of line)
-        let old_pos = self.cursor_pos;
-        self.cursor_pos = 0;
-        if extend_selection {
-            if !self.has_selection() {
-                self.selection_start = Some(old_pos);
-                self.selection_end = Some(0);
-            } else {
-                self.extend_selection_to(0);
-            }
-        } else {
-            self.clear_selection();
-        }
-        self.pause_blinking(cx);
-        cx.notify();
+        // Move to the start of the visual line
+        let pos = self
+            .visual_lines
+            .get(self.visual_line_index(self.buffer.cursor()))
+            .map_or(0, |line| line.start);
+        self.move_cursor_to(pos, extend_selection, cx);
     }
     
     fn move_cursor_end(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
-        // For now, move to end of text (could be enhanced to move to end of line)
-        let old_pos = self.cursor_pos;
-        self.cursor_pos = self.value.chars().count();
-        if extend_selection {
-            if !self.has_selection() {
-                self.selection_start = Some(old_pos);
-                self.selection_end = Some(self.cursor_pos);
-            } else {
-                self.extend_selection_to(self.cursor_pos);
+        // Move to the end of the visual line. On a soft-wrapped line, stop
+        // before the wrap so the cursor stays on this line.
+        let line_idx = self.visual_line_index(self.buffer.cursor());
+        let pos = match self.visual_lines.get(line_idx) {
+            Some(line) => {
+                let continues = self
+                    .visual_lines
+                    .get(line_idx + 1)
+                    .is_some_and(|next| next.wrapped);
+                if continues && line.end > line.start {
+                    previous_grapheme_boundary(self.buffer.text(), line.end)
+                } else {
+                    line.end
+                }
             }
-        } else {
-            self.clear_selection();
+            None => self.buffer.text().len(),
+        };
+        self.move_cursor_to(pos, extend_selection, cx);
+    }
+
+    /// Move the cursor to `pos`, extending the selection from its anchor if requested
+    fn move_cursor_to(&mut self, pos: usize, extend_selection: bool, cx: &mut Context<Self>) {
+        self.buffer.move_to(pos, extend_selection);
+        self.pause_blinking(cx);
+        cx.notify();
+    }
+
+    /// X offset of a byte offset within its visual line, if laid out
+    fn x_for_position(&self, pos: usize) -> Option<Pixels> {
+        let layouts = self.line_layouts.lock().ok()?;
+        let layout = layouts.get(self.visual_line_index(pos))?;
+        let index = pos.checked_sub(layout.start)?;
+        Some(layout.shaped_line.x_for_index(index.min(layout.shaped_line.text.len())))
+    }
+
+    /// Byte offset closest to `x` on the given visual line
+    fn position_for_x(&self, line_idx: usize, x: Pixels) -> Option<usize> {
+        let line = self.visual_lines.get(line_idx)?;
+        let layouts = self.line_layouts.lock().ok()?;
+        let layout = layouts.get(line_idx).filter(|layout| layout.start == line.start)?;
+        let index = layout.shaped_line.closest_index_for_x(x).min(layout.shaped_line.text.len());
+        let mut pos = line.start + index;
+
+        // Stay before a soft wrap so the cursor remains on the target line
+        let text = self.buffer.text();
+        let continues = self
+            .visual_lines
+            .get(line_idx + 1)
+            .is_some_and(|next| next.wrapped);
+        if continues && pos >= line.end && line.end > line.start {
+            pos = previous_grapheme_boundary(text, line.end);
         }
+        Some(floor_grapheme_boundary(text, pos.min(line.end)))
+    }
+
+    fn move_word_left(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
+        self.buffer.move_word_left(extend_selection);
         self.pause_blinking(cx);
         cx.notify();
     }
 
-    fn handle_enter(&mut self, shift_pressed: bool, cx: &mut Context<Self>) {
+    fn move_word_right(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
+        self.buffer.move_word_right(extend_selection);
+        self.pause_blinking(cx);
+        cx.notify();
+    }
+
+    fn delete_word_backward(&mut self, cx: &mut Context<Self>) {
         if self.disabled {
             return;
         }
-
-        if shift_pressed {
-            // Shift+Enter: insert newline
-            self.handle_input("\n", cx);
-        } else {
-            // Enter: submit
-            cx.emit(TextAreaEvent::Submit(self.value.clone()));
-        }
+        let changed = self.buffer.delete_word_backward();
+        self.after_edit(changed, cx);
     }
-    
-    fn handle_delete(&mut self, cx: &mut Context<Self>) {
+
+    fn delete_word_forward(&mut self, cx: &mut Context<Self>) {
         if self.disabled {
             return;
         }
+        let changed = self.buffer.delete_word_forward();
+        self.after_edit(changed, cx);
+    }
 
-        // If there's marked text (IME composition), clear it
-        if self.marked_range.is_some() {
-            self.marked_range = None;
-            cx.notify();
+    /// Move the cursor up (negative) or down (positive) by visual lines,
+    /// keeping the horizontal goal position across lines of different lengths
+    fn move_cursor_vertically(&mut self, lines: isize, extend_selection: bool, cx: &mut Context<Self>) {
+        if self.visual_lines.is_empty() {
             return;
         }
 
-        // If there's a selection, delete it
-        if self.has_selection() {
-            self.cursor_pos = self.delete_selection();
-            cx.emit(TextAreaEvent::Change(self.value.clone()));
-            cx.notify();
+        let cursor = self.buffer.cursor();
+        let line_idx = self.visual_line_index(cursor);
+        let last_line = self.visual_lines.len() - 1;
+        let target_idx = (line_idx as isize + lines).clamp(0, last_line as isize) as usize;
+
+        // Past the first or last line: go to the start or end of the text
+        if target_idx == line_idx {
+            let pos = if lines < 0 { 0 } else { self.buffer.text().len() };
+            self.goal_x = None;
+            self.move_cursor_to(pos, extend_selection, cx);
             return;
         }
 
-        // Ensure cursor position is valid
-        self.validate_cursor_position();
+        let goal_x = match self.goal_x {
+            Some((pos, x)) if pos == cursor => Some(x),
+            _ => self.x_for_position(cursor),
+        };
+        let pos = match goal_x.and_then(|x| self.position_for_x(target_idx, x)) {
+            Some(pos) => pos,
+            None => {
+                // Not laid out yet: keep the column instead
+                let col = cursor - self.visual_lines[line_idx].start;
+                let target = self.visual_lines[target_idx];
+                (target.start + col).min(target.end)
+            }
+        };
+
+        self.move_cursor_to(pos, extend_selection, cx);
+        self.goal_x = goal_x.map(|x| (self.buffer.cursor(), x));
+    }
+
+    /// Move the cursor by a page of visual lines, scrolling the view with it
+    fn move_cursor_page(&mut self, direction: isize, extend_selection: bool, cx: &mut Context<Self>) {
+        let page_lines = if self.line_height > px(0.) {
+            ((self.viewport_height / self.line_height).floor() as isize).max(1)
+        } else {
+            1
+        };
+        self.scroll_by(self.line_height * (page_lines * direction) as f32, cx);
+        self.move_cursor_vertically(page_lines * direction, extend_selection, cx);
+    }
 
-        let char_count = self.value.chars().count();
-        if self.cursor_pos >= char_count {
+    fn handle_enter(&mut self, shift_pressed: bool, cx: &mut Context<Self>) {
+        if self.disabled {
             return;
         }
 
-        // Safely remove character at cursor
-        self.remove_char_at(self.cursor_pos);
-
-        cx.emit(TextAreaEvent::Change(self.value.clone()));
-        cx.notify();
+        if shift_pressed {
+            // Shift+Enter: insert newline
+            self.handle_input("\n", cx);
+        } else {
+            // Enter: submit
+            let text = self.buffer.text().to_string();
+            self.validate_for(ValidateOn::Submit, cx);
+            cx.emit(TextAreaEvent::Submit(text));
+            cx.notify();
+        }
+    }
+    
+    fn handle_delete(&mut self, cx: &mut Context<Self>) {
+        if self.disabled {
+            return;
+        }
+        let changed = self.buffer.delete();
+        self.after_edit(changed, cx);
     }
     
     /// Increment blink epoch (used to cancel old blink tasks)
@@ -715,6 +795,9 @@ impl TextArea {
 
     /// Pause blinking and show cursor (called on user input)
     fn pause_blinking(&mut self, cx: &mut Context<Self>) {
+        // User input moved the cursor: keep it in view
+        self.autoscroll_pending = true;
+
         // Show cursor immediately
         self.cursor_visible = true;
         cx.notify();
@@ -732,82 +815,191 @@ impl TextArea {
         self._blink_task = Some(task);
     }
     
-    /// Calculate character index from mouse position (for multi-line text)
+    /// Calculate the byte offset under the mouse (for multi-line text)
     fn index_for_mouse_position(&self, position: Point<Pixels>) -> usize {
-        if self.value.is_empty() {
+        let text = self.buffer.text();
+        if text.is_empty() {
             return 0;
         }
 
-        if self.last_layout.is_empty() {
-            return self.value.chars().count();
+        let Ok(layouts) = self.line_layouts.lock() else {
+            return text.len();
+        };
+        if layouts.is_empty() {
+            return text.len();
         }
 
-        let lines: Vec<&str> = self.value.split('\n').collect();
-        
-        // Find which line the click is on
-        let mut target_line_idx = 0;
-        
-        for (idx, layout) in self.last_layout.iter().enumerate() {
-            if position.y < layout.bounds.bottom() {
-                target_line_idx = idx;
-                break;
-            }
-        }
-        
-        // If click is below all lines, return end of text
-        if target_line_idx >= lines.len() {
-            return self.value.chars().count();
-        }
-        
-        // Get the layout for the target line
-        let Some(line_layout) = self.last_layout.get(target_line_idx) else {
-            return self.value.chars().count();
-        };
-        
-        // Calculate relative x position within the line
-        let relative_x = position.x - line_layout.bounds.left();
-        
-        // Use ShapedLine to find closest character index
-        let byte_index = line_layout.shaped_line.closest_index_for_x(relative_x);
-        
-        // Calculate character index before this line
-        let mut chars_before_line = 0;
-        for (idx, line) in lines.iter().enumerate() {
-            if idx < target_line_idx {
-                chars_before_line += line.chars().count() + 1; // +1 for newline
+        // Find which visual line the point is on; below all lines means the last line
+        let line_idx = layouts
+            .iter()
+            .position(|layout| position.y < layout.bounds.bottom())
+            .unwrap_or(layouts.len() - 1);
+        let layout = &layouts[line_idx];
+
+        // Use ShapedLine to find the closest byte index within the line
+        let relative_x = position.x - layout.bounds.left();
+        let byte_index = layout
+            .shaped_line
+            .closest_index_for_x(relative_x)
+            .min(layout.shaped_line.text.len());
+        let mut index = layout.start + byte_index;
+
+        // Past the end of a soft-wrapped line, stay on that line rather than
+        // jumping to the start of the continuation line
+        if let Some(line) = self.visual_lines.get(line_idx) {
+            let continues = self
+                .visual_lines
+                .get(line_idx + 1)
+                .is_some_and(|next| next.wrapped);
+            if continues && index >= line.end && line.end > line.start {
+                index = previous_grapheme_boundary(text, line.end);
             }
         }
-        
-        // Convert byte index to character index and add offset
-        let line = lines[target_line_idx];
-        let chars_in_line = line.chars().count();
-        let char_index_in_line = byte_index.min(chars_in_line);
-        
-        chars_before_line + char_index_in_line
+
+        // Never place the cursor inside a grapheme cluster
+        floor_grapheme_boundary(text, index.min(text.len()))
     }
 
     fn count_lines(&self) -> usize {
-        if self.value.is_empty() {
-            1
-        } else {
-            self.value.lines().count().max(1)
-        }
+        self.visual_lines.len().max(1)
     }
 
-    fn calculate_height(&self) -> f32 {
+    /// Vertical space taken by padding and border
+    fn vertical_padding(&self, theme: &Theme) -> f32 {
+        // Container padding on top and bottom, plus a 1px border on each side
+        2.0 * theme.spacing.md + if self.show_border { 2.0 } else { 0.0 }
+    }
+
+    fn calculate_height(&self, theme: &Theme) -> f32 {
+        let line_height = theme.typography.line_height;
         let line_count = self.count_lines();
-        let line_height = 20.0; // Approximate line height
-        let padding = 12.0; // Vertical padding
+        let padding = self.vertical_padding(theme);
         let calculated = (line_count as f32 * line_height) + padding;
 
         // Apply min and max constraints
         let height = calculated.max(self.min_height);
         if let Some(max_h) = self.max_height {
-            height.min(max_h)
+            // Always leave room for at least one line
+            height.min(max_h.max(line_height + padding))
         } else {
             height
         }
     }
+
+    /// Largest scroll offset for the current content
+    fn max_scroll(&self) -> Pixels {
+        (self.content_height - self.viewport_height).max(px(0.))
+    }
+
+    /// Scroll by `delta` pixels, returning whether the offset changed
+    fn scroll_by(&mut self, delta: Pixels, cx: &mut Context<Self>) -> bool {
+        let scroll_top = (self.scroll_top + delta).clamp(px(0.), self.max_scroll());
+        if scroll_top == self.scroll_top {
+            return false;
+        }
+        self.scroll_top = scroll_top;
+        cx.notify();
+        true
+    }
+
+    /// Scroll the minimum amount needed to show the cursor's line
+    fn scroll_to_cursor(&mut self, line_height: Pixels) {
+        let line_idx = self.visual_line_index(self.buffer.cursor());
+        let top = line_height * line_idx as f32;
+        let bottom = top + line_height;
+        if top < self.scroll_top {
+            self.scroll_top = top;
+        } else if bottom > self.scroll_top + self.viewport_height {
+            self.scroll_top = bottom - self.viewport_height;
+        }
+    }
+
+    /// Extend the drag selection to the character under `position`
+    fn select_to_position(&mut self, position: Point<Pixels>) {
+        let index = self.index_for_mouse_position(position);
+        self.buffer.move_to(index, true);
+    }
+
+    /// Handle a mouse move anywhere in the window during a drag
+    fn drag_to(&mut self, position: Point<Pixels>, cx: &mut Context<Self>) {
+        // Scrollbar thumb drag: move proportionally to the content
+        if let Some((start_y, start_scroll)) = self.scrollbar_drag {
+            if self.viewport_height > px(0.) {
+                let ratio = self.content_height / self.viewport_height;
+                let target = start_scroll + (position.y - start_y) * ratio;
+                self.scroll_by(target - self.scroll_top, cx);
+            }
+            return;
+        }
+
+        if !self.is_dragging {
+            return;
+        }
+
+        self.drag_position = Some(position);
+        self.select_to_position(position);
+        cx.notify();
+
+        // Keep scrolling while the mouse is held past the top or bottom edge
+        if self._drag_scroll_task.is_none() && self.drag_overshoot().is_some() {
+            self._drag_scroll_task = Some(cx.spawn(async move |this, cx| {
+                loop {
+                    cx.background_executor().timer(Duration::from_millis(16)).await;
+                    let scrolling = this
+                        .update(cx, |this, cx| this.drag_scroll_step(cx))
+                        .unwrap_or(false);
+                    if !scrolling {
+                        break;
+                    }
+                }
+            }));
+        }
+    }
+
+    /// How far the drag position is above (negative) or below (positive) the viewport
+    fn drag_overshoot(&self) -> Option<Pixels> {
+        let position = self.drag_position?;
+        let viewport = self.viewport_bounds.lock().ok().and_then(|bounds| *bounds)?;
+        if position.y < viewport.top() {
+            Some(position.y - viewport.top())
+        } else if position.y > viewport.bottom() {
+            Some(position.y - viewport.bottom())
+        } else {
+            None
+        }
+    }
+
+    /// One tick of drag auto-scroll; returns whether to keep scrolling
+    fn drag_scroll_step(&mut self, cx: &mut Context<Self>) -> bool {
+        let overshoot = match self.drag_overshoot() {
+            Some(overshoot) if self.is_dragging => overshoot,
+            _ => {
+                self._drag_scroll_task = None;
+                return false;
+            }
+        };
+
+        // Scroll faster the further the mouse is past the edge
+        let step = (overshoot / 4.).clamp(px(-40.), px(40.));
+        let step = if step.abs() < px(2.) { px(2.) * step.signum() } else { step };
+        self.scroll_by(step, cx);
+        if let Some(position) = self.drag_position {
+            self.select_to_position(position);
+        }
+        cx.notify();
+        true
+    }
+
+    /// End a selection or scrollbar drag
+    fn end_drag(&mut self, cx: &mut Context<Self>) {
+        if self.is_dragging || self.scrollbar_drag.is_some() {
+            self.is_dragging = false;
+            self.scrollbar_drag = None;
+            self.drag_position = None;
+            self._drag_scroll_task = None;
+            cx.notify();
+        }
+    }
 }
 
 impl EventEmitter<TextAreaEvent> for TextArea {}
@@ -818,6 +1010,33 @@ impl Focusable for TextArea {
     }
 }
 
+impl FormControl for TextArea {
+    fn form_value(&self) -> Value {
+        Value::String(self.buffer.text().to_string())
+    }
+
+    fn set_form_value(&mut self, value: &Value, cx: &mut Context<Self>) {
+        self.set_value(value_to_string(value), cx);
+    }
+
+    fn validate_form(&mut self, cx: &mut Context<Self>) -> ValidationState {
+        self.run_validation(cx);
+        self.validation.state().clone()
+    }
+
+    fn form_validation_state(&self) -> ValidationState {
+        self.validation.state().clone()
+    }
+
+    fn clear_form_error(&mut self, cx: &mut Context<Self>) {
+        self.set_error(None, cx);
+    }
+
+    fn form_focus_handle(&self, _cx: &App) -> Option<FocusHandle> {
+        Some(self.focus_handle.clone())
+    }
+}
+
 impl Render for TextArea {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let is_focused = self.focus_handle.is_focused(window);
@@ -828,32 +1047,78 @@ impl Render for TextArea {
             self.start_blinking(epoch, cx);
         }
         
-        let show_placeholder = self.value.is_empty() && !is_focused;
+        if self._blur_subscription.is_none() {
+            self._blur_subscription = Some(cx.on_blur(&self.focus_handle, window, |this, _window, cx| {
+                this.handle_blur(cx);
+            }));
+        }
+        let error = self.validation.error().map(str::to_string);
+
+        let show_placeholder = self.buffer.text().is_empty() && !is_focused;
         let disabled = self.disabled;
         let placeholder = self.placeholder.clone();
-        let value = self.value.clone();
-        let cursor_pos = self.cursor_pos;
-        let height = self.calculate_height();
-        let selection_start = self.selection_start;
-        let selection_end = self.selection_end;
+        let value = self.buffer.text().to_string();
+        let cursor_pos = self.buffer.cursor();
+        let theme = cx.theme().clone();
+        let font = theme.typography.font();
+        let font_size = theme.font_size(ComponentSize::Medium);
+        let line_height = px(theme.typography.line_height);
+
+        // Soft wrap to the width measured during the last paint
+        let wrap_width = self.wrap_width.lock().ok().and_then(|width| *width);
+        self.visual_lines = self.layout_visual_lines(&font, font_size, wrap_width, window);
+        let visual_lines = self.visual_lines.clone();
+
+        let height = self.calculate_height(&theme);
+
+        // Scroll metrics; keep the cursor in view after user input
+        self.line_height = line_height;
+        self.viewport_height = px(height - self.vertical_padding(&theme));
+        self.content_height = line_height * self.count_lines() as f32;
+        if self.autoscroll_pending {
+            self.autoscroll_pending = false;
+            self.scroll_to_cursor(line_height);
+        }
+        self.scroll_top = self.scroll_top.clamp(px(0.), self.max_scroll());
+        let scroll_top = self.scroll_top;
+
+        // Scrollbar thumb size and position, when the content overflows
+        let scrollbar = (self.max_scroll() > px(0.)).then(|| {
+            let track = self.viewport_height;
+            let thumb_height = (track * (self.viewport_height / self.content_height)).max(px(20.));
+            let thumb_top = (track - thumb_height) * (scroll_top / self.max_scroll());
+            (thumb_top, thumb_height)
+        });
+        let viewport_bounds = self.viewport_bounds.clone();
+
+        let selection = self.buffer.selection();
         
-        // Create a shared container for layout info that will be filled during paint
-        let layout_container: Arc<Mutex<Vec<TextAreaLineLayout>>> = Arc::new(Mutex::new(Vec::new()));
+        // Shared container for per-line layout info, filled during paint
+        let layout_container = self.line_layouts.clone();
+        if let Ok(mut layouts) = layout_container.lock() {
+            layouts.resize(visual_lines.len(), TextAreaLineLayout::default());
+        }
+        let wrap_width_container = self.wrap_width.clone();
         
         // Determine colors based on customization or defaults
         let bg_color = if disabled {
-            self.bg_color.unwrap_or(rgb(0xF5F5F5))
+            self.bg_color.unwrap_or(theme.colors.background_secondary)
         } else {
-            self.bg_color.unwrap_or(rgb(0xFFFFFF))
+            self.bg_color.unwrap_or(theme.colors.background)
         };
         
-        let border_color = if is_focused {
+        let border_color = if error.is_some() {
+            theme.colors.border_error
+        } else if is_focused {
             self.focus_border_color
                 .or(self.custom_border_color)
-                .unwrap_or(rgb(0x696FC7))
+                .unwrap_or(theme.colors.border_focus)
         } else {
-            self.custom_border_color.unwrap_or(rgb(0xE0E0E0))
+            self.custom_border_color.unwrap_or(theme.colors.border)
         };
+        let text_color = theme.colors.text;
+        let selection_color = theme.colors.selection;
+        let selection_text_color = theme.colors.selection_text;
 
         let content = div()
             .id("text-area")
@@ -881,11 +1146,66 @@ impl Render for TextArea {
                     return;
                 }
 
+                // Check for Cmd/Ctrl + C (Copy)
+                if is_cmd_or_ctrl && event.keystroke.key.as_str().eq_ignore_ascii_case("c") {
+                    this.copy();
+                    return;
+                }
+
+                // Check for Cmd/Ctrl + X (Cut)
+                if is_cmd_or_ctrl && event.keystroke.key.as_str().eq_ignore_ascii_case("x") {
+                    this.cut(cx);
+                    return;
+                }
+
+                // Check for Cmd/Ctrl + V (Paste)
+                if is_cmd_or_ctrl && event.keystroke.key.as_str().eq_ignore_ascii_case("v") {
+                    this.paste(cx);
+                    return;
+                }
+
+                // Check for Cmd/Ctrl + Z (Undo) and Cmd/Ctrl + Shift + Z (Redo)
+                if is_cmd_or_ctrl && event.keystroke.key.as_str().eq_ignore_ascii_case("z") {
+                    if modifiers.shift {
+                        this.redo(cx);
+                    } else {
+                        this.undo(cx);
+                    }
+                    return;
+                }
+
+                // Check for Ctrl + Y (Redo) outside macOS
+                if !cfg!(target_os = "macos")
+                    && is_cmd_or_ctrl
+                    && event.keystroke.key.as_str().eq_ignore_ascii_case("y")
+                {
+                    this.redo(cx);
+                    return;
+                }
+
                 // Check for Shift key to extend selection
                 let shift_pressed = modifiers.shift;
+                // Word motion uses Alt on macOS and Ctrl elsewhere
+                let word_modifier = if cfg!(target_os = "macos") {
+                    modifiers.alt
+                } else {
+                    modifiers.control
+                };
 
                 // Handle special keys
                 match event.keystroke.key.as_str() {
+                    "backspace" if word_modifier => {
+                        this.delete_word_backward(cx);
+                    }
+                    "delete" if word_modifier => {
+                        this.delete_word_forward(cx);
+                    }
+                    "left" if word_modifier => {
+                        this.move_word_left(shift_pressed, cx);
+                    }
+                    "right" if word_modifier => {
+                        this.move_word_right(shift_pressed, cx);
+                    }
                     "backspace" => {
                         this.handle_backspace(cx);
                     }
@@ -898,12 +1218,39 @@ impl Render for TextArea {
                     "right" => {
                         this.move_cursor_right(shift_pressed, cx);
                     }
+                    "home" if is_cmd_or_ctrl => {
+                        this.move_cursor_to(0, shift_pressed, cx);
+                    }
+                    "end" if is_cmd_or_ctrl => {
+                        let end = this.buffer.text().len();
+                        this.move_cursor_to(end, shift_pressed, cx);
+                    }
                     "home" => {
                         this.move_cursor_home(shift_pressed, cx);
                     }
                     "end" => {
                         this.move_cursor_end(shift_pressed, cx);
                     }
+                    // Cmd+Up/Down jump to the start/end of the text on macOS
+                    "up" if cfg!(target_os = "macos") && modifiers.platform => {
+                        this.move_cursor_to(0, shift_pressed, cx);
+                    }
+                    "down" if cfg!(target_os = "macos") && modifiers.platform => {
+                        let end = this.buffer.text().len();
+                        this.move_cursor_to(end, shift_pressed, cx);
+                    }
+                    "up" => {
+                        this.move_cursor_vertically(-1, shift_pressed, cx);
+                    }
+                    "down" => {
+                        this.move_cursor_vertically(1, shift_pressed, cx);
+                    }
+                    "pageup" => {
+                        this.move_cursor_page(-1, shift_pressed, cx);
+                    }
+                    "pagedown" => {
+                        this.move_cursor_page(1, shift_pressed, cx);
+                    }
                     "enter" => {
                         let shift_pressed = event.keystroke.modifiers.shift;
                         this.handle_enter(shift_pressed, cx);
@@ -915,19 +1262,18 @@ impl Render for TextArea {
                 }
             }))
             .on_mouse_down(MouseButton::Left, {
-                let layout_container = layout_container.clone();
                 cx.listener(move |this, event: &MouseDownEvent, window, cx| {
                     cx.emit(TextAreaEvent::Focus);
                     cx.focus_self(window);
                     
-                    // Update last_layout from the container
-                    if let Ok(layout) = layout_container.lock() {
-                        this.last_layout = layout.clone();
-                    }
-                    
-                    // Check for double-click to select all
-                    if event.click_count == 2 && !this.disabled {
-                        this.select_all(cx);
+                    // Double-click selects a word, triple-click selects a line
+                    if event.click_count >= 2 && !this.disabled {
+                        let index = this.index_for_mouse_position(event.position);
+                        if event.click_count == 2 {
+                            this.buffer.select_word_at(index);
+                        } else {
+                            this.buffer.select_line_at(index);
+                        }
                         this.pause_blinking(cx);
                     } else if !this.disabled {
                         // Start dragging
@@ -936,72 +1282,82 @@ impl Render for TextArea {
                         // Calculate click position
                         let index = this.index_for_mouse_position(event.position);
                         
-                        if event.modifiers.shift {
-                            // Shift+click extends selection
-                            if !this.has_selection() {
-                                this.selection_start = Some(this.cursor_pos);
-                                this.selection_end = Some(index);
-                            } else {
-                                // Extend existing selection
-                                this.selection_end = Some(index);
-                            }
-                        } else {
-                            // Normal click - clear selection and set cursor
-                            this.clear_selection();
-                        }
-                        
-                        this.cursor_pos = index;
+                        // Shift+click extends the selection, a normal click clears it
+                        this.buffer.move_to(index, event.modifiers.shift);
                         this.pause_blinking(cx);
                     }
                     
                     cx.notify();
                 })
             })
-            .on_mouse_up(MouseButton::Left, cx.listener(|this, _event: &MouseUpEvent, _window, cx| {
-                this.is_dragging = false;
-                cx.notify();
-            }))
-            .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
-                if this.is_dragging {
-                    // Update selection while dragging
-                    let index = this.index_for_mouse_position(event.position);
-                    
-                    if !this.has_selection() {
-                        // Start new selection from cursor position
-                        this.selection_start = Some(this.cursor_pos);
-                        this.selection_end = Some(index);
-                    } else {
-                        // Update selection end
-                        this.selection_end = Some(index);
-                    }
-                    
-                    this.cursor_pos = index;
-                    cx.notify();
+            .on_scroll_wheel(cx.listener(move |this, event: &ScrollWheelEvent, _window, cx| {
+                // Scroll the text, letting the parent scroll once we hit an edge
+                let delta = event.delta.pixel_delta(line_height);
+                if this.scroll_by(-delta.y, cx) {
+                    cx.stop_propagation();
                 }
             }))
+            .relative()
             .flex()
             .flex_col()
             .w_full()
-            .min_h(px(height))
-            .p_3()
+            .h(px(height))
+            .p(px(theme.spacing.md))
             .bg(bg_color)
             .when(self.show_border, |this| {
                 this.border_1().border_color(border_color)
             })
-            .rounded(px(6.))
+            .rounded(px(theme.radius.md))
             .when(!disabled, |this| {
                 this.cursor(CursorStyle::IBeam)
             })
+            .when_some(scrollbar, |this, (thumb_top, thumb_height)| {
+                // Scrollbar thumb in the right padding
+                this.child(
+                    div()
+                        .id("text-area-scrollbar")
+                        .absolute()
+                        .right(px(3.))
+                        .top(px(theme.spacing.md) + thumb_top)
+                        .w(px(6.))
+                        .h(thumb_height)
+                        .rounded(px(theme.radius.full))
+                        .bg(theme.colors.border)
+                        .hover(|style| style.bg(theme.colors.text_disabled))
+                        .cursor(CursorStyle::Arrow)
+                        .on_mouse_down(MouseButton::Left, cx.listener(|this, event: &MouseDownEvent, _window, cx| {
+                            this.scrollbar_drag = Some((event.position.y, this.scroll_top));
+                            cx.stop_propagation();
+                        }))
+                )
+            })
             .children([
-                // Render text with cursor
+                // Render text with cursor, clipped to the visible area
                 div()
+                    .relative()
                     .flex()
                     .flex_row()
                     .items_start()
                     .w_full()
-                    .text_sm()
+                    .flex_1()
+                    .min_h(px(0.))
+                    .overflow_hidden()
+                    .text_size(font_size)
+                    .child(
+                        // Measure the visible area for drag auto-scroll
+                        canvas(
+                            move |bounds, _, _cx| {
+                                if let Ok(mut viewport) = viewport_bounds.lock() {
+                                    *viewport = Some(bounds);
+                                }
+                            },
+                            |_, _, _, _| {},
+                        )
+                        .absolute()
+                        .size_full()
+                    )
                     .when(show_placeholder, |this| {
-                        this.text_color(rgb(0x999999))
+                        this.text_color(theme.colors.text_disabled)
                             .child(placeholder)
                     })
                     .when(!show_placeholder && is_focused && value.is_empty(), |this| {
@@ -1011,8 +1367,8 @@ impl Render for TextArea {
                             this.child(
                                 div()
                                     .w(px(2.))
-                                    .h(px(20.))
-                                    .bg(rgb(0x333333))
+                                    .h(px(theme.typography.line_height))
+                                    .bg(text_color)
                             )
                         } else {
                             this // Empty div when cursor hidden
@@ -1020,53 +1376,33 @@ impl Render for TextArea {
                     })
                     .when(!show_placeholder && !value.is_empty(), |this| {
                         // Use TextRun API for rendering - prevents width jitter
-                        let has_selection = selection_start.is_some() && selection_end.is_some();
-                        let font = gpui::Font {
-                            family: ".SystemUIFont".into(),
-                            features: Default::default(),
-                            weight: Default::default(),
-                            style: Default::default(),
-                            fallbacks: None,
-                        };
-                        let font_size = px(14.);
-                        let line_height = px(20.);
+                        let has_selection = selection.is_some();
                         let cursor_visible = self.cursor_visible;
                         
-                        // Split text into lines
-                        let lines: Vec<&str> = value.split('\n').collect();
-                        
-                        // Calculate character offsets for each line
-                        let mut line_offsets = Vec::new();
-                        let mut chars_counted = 0;
-                        for line in &lines {
-                            let line_start = chars_counted;
-                            line_offsets.push(line_start);
-                            chars_counted += line.chars().count() + 1; // +1 for newline
-                        }
+                        // Text of each visual line
+                        let lines: Vec<String> = visual_lines
+                            .iter()
+                            .map(|line| value[line.start..line.end].to_string())
+                            .collect();
+                        let line_offsets: Vec<usize> = visual_lines.iter().map(|line| line.start).collect();
                         
                         // Find cursor line
-                        let mut cursor_line_idx = 0;
-                        let mut cursor_col = cursor_pos;
-                        for (idx, offset) in line_offsets.iter().enumerate() {
-                            let line_len = lines[idx].chars().count();
-                            if cursor_pos <= *offset + line_len {
-                                cursor_line_idx = idx;
-                                cursor_col = cursor_pos - *offset;
-                                break;
-                            }
-                        }
+                        let cursor_line_idx = self.visual_line_index(cursor_pos);
+                        let cursor_col = cursor_pos.saturating_sub(line_offsets.get(cursor_line_idx).copied().unwrap_or(0));
                         
                         // Clone layout container for each line
                         let layout_container_clone = layout_container.clone();
                         
                         this.child(
                             div()
+                                .relative()
+                                .top(-scroll_top)
                                 .flex()
                                 .flex_col()
                                 .items_start()
                                 .w_full()
                                 .children(
-                                    lines.iter().enumerate().map({
+                                    lines.into_iter().enumerate().map({
                                         let font_clone = font.clone();
                                         let font_size_clone = font_size;
                                         let line_height_clone = line_height;
@@ -1074,38 +1410,20 @@ impl Render for TextArea {
                                         let cursor_line_idx_clone = cursor_line_idx;
                                         let cursor_col_clone = cursor_col;
                                         let layout_container_for_line = layout_container_clone.clone();
+                                        let wrap_width_for_line = wrap_width_container.clone();
                                         let line_offsets_clone = line_offsets.clone();
                                         let has_selection_clone = has_selection;
-                                        let selection_start_clone = selection_start;
-                                        let selection_end_clone = selection_end;
+                                        let selection_clone = selection.clone();
                                         
                                         move |(line_idx, line)| {
                                             let line_start = line_offsets_clone[line_idx];
-                                            let line_str = line.to_string();
-                                            let line_end = line_start + line_str.chars().count();
+                                            let line_str = line;
+                                            let line_end = line_start + line_str.len();
                                             
                                             // Build TextRun for this line
-                                            let (display_text, text_runs) = if !has_selection_clone {
-                                                // No selection: single text run
-                                                (line_str.clone(), vec![TextRun {
-                                                    len: line_str.len(),
-                                                    font: font_clone.clone(),
-                                                    color: rgb(0x333333).into(),
-                                                    background_color: None,
-                                                    underline: None,
-                                                    strikethrough: None,
-                                                }])
-                                            } else {
+                                            let (display_text, text_runs) = if let Some(selection) = &selection_clone {
                                                 // Check if this line has selection
-                                                let (sel_start, sel_end) = if let (Some(start), Some(end)) = (selection_start_clone, selection_end_clone) {
-                                                    if start <= end {
-                                                        (start, end)
-                                                    } else {
-                                                        (end, start)
-                                                    }
-                                                } else {
-                                                    (0, 0)
-                                                };
+                                                let (sel_start, sel_end) = (selection.start, selection.end);
                                                 
                                                 let line_has_selection = !(sel_end <= line_start || sel_start >= line_end);
                                                 
@@ -1114,7 +1432,7 @@ impl Render for TextArea {
                                                     (line_str.clone(), vec![TextRun {
                                                         len: line_str.len(),
                                                         font: font_clone.clone(),
-                                                        color: rgb(0x333333).into(),
+                                                        color: text_color.into(),
                                                         background_color: None,
                                                         underline: None,
                                                         strikethrough: None,
@@ -1122,24 +1440,16 @@ impl Render for TextArea {
                                                 } else {
                                                     // This line has selection
                                                     let sel_start_in_line = sel_start.saturating_sub(line_start);
-                                                    let sel_end_in_line = if sel_end < line_end {
-                                                        sel_end - line_start
-                                                    } else {
-                                                        line_str.chars().count()
-                                                    };
+                                                    let sel_end_in_line = sel_end.min(line_end) - line_start;
                                                     
                                                     let mut runs = Vec::new();
 
-                                                    // Convert character indices to byte indices for TextRun
-                                                    let line_chars: Vec<char> = line_str.chars().collect();
-
                                                     // Text before selection
                                                     if sel_start_in_line > 0 {
-                                                        let before_text: String = line_chars[..sel_start_in_line].iter().collect();
                                                         runs.push(TextRun {
-                                                            len: before_text.len(),
+                                                            len: sel_start_in_line,
                                                             font: font_clone.clone(),
-                                                            color: rgb(0x333333).into(),
+                                                            color: text_color.into(),
                                                             background_color: None,
                                                             underline: None,
                                                             strikethrough: None,
@@ -1148,24 +1458,22 @@ impl Render for TextArea {
 
                                                     // Selected text
                                                     if sel_end_in_line > sel_start_in_line {
-                                                        let selected_text: String = line_chars[sel_start_in_line..sel_end_in_line].iter().collect();
                                                         runs.push(TextRun {
-                                                            len: selected_text.len(),
+                                                            len: sel_end_in_line - sel_start_in_line,
                                                             font: font_clone.clone(),
-                                                            color: rgb(0xFFFFFF).into(),
-                                                            background_color: Some(rgb(0x4A90E2).into()),
+                                                            color: selection_text_color.into(),
+                                                            background_color: Some(selection_color.into()),
                                                             underline: None,
                                                             strikethrough: None,
                                                         });
                                                     }
 
                                                     // Text after selection
-                                                    if sel_end_in_line < line_chars.len() {
-                                                        let after_text: String = line_chars[sel_end_in_line..].iter().collect();
+                                                    if sel_end_in_line < line_str.len() {
                                                         runs.push(TextRun {
-                                                            len: after_text.len(),
+                                                            len: line_str.len() - sel_end_in_line,
                                                             font: font_clone.clone(),
-                                                            color: rgb(0x333333).into(),
+                                                            color: text_color.into(),
                                                             background_color: None,
                                                             underline: None,
                                                             strikethrough: None,
@@ -1174,6 +1482,16 @@ impl Render for TextArea {
                                                     
                                                     (line_str.clone(), runs)
                                                 }
+                                            } else {
+                                                // No selection: single text run
+                                                (line_str.clone(), vec![TextRun {
+                                                    len: line_str.len(),
+                                                    font: font_clone.clone(),
+                                                    color: text_color.into(),
+                                                    background_color: None,
+                                                    underline: None,
+                                                    strikethrough: None,
+                                                }])
                                             };
                                             
                                             let is_cursor_line = line_idx == cursor_line_idx_clone && !has_selection_clone;
@@ -1195,32 +1513,37 @@ impl Render for TextArea {
                                                             let text_runs_clone = text_runs.clone();
                                                             let line_idx_clone = line_idx;
                                                             let layout_container_for_paint = layout_container_for_line.clone();
+                                                            let wrap_width_for_paint = wrap_width_for_line.clone();
                                                             
                                                             move |bounds, _, window, _cx| {
-                                                                if !display_text_clone.is_empty() {
-                                                                    // Shape the line using TextRun
-                                                                    let shaped_line = window.text_system().shape_line(
-                                                                        display_text_clone.clone().into(),
-                                                                        font_size_clone,
-                                                                        &text_runs_clone,
-                                                                        None,
-                                                                    );
-                                                                    
-                                                                    // Save layout info for mouse position calculation
-                                                                    if let Ok(mut layouts) = layout_container_for_paint.lock() {
-                                                                        // Ensure vector is large enough
-                                                                        while layouts.len() <= line_idx_clone {
-                                                                            layouts.push(TextAreaLineLayout {
-                                                                                bounds: gpui::Bounds::default(),
-                                                                                shaped_line: shaped_line.clone(),
-                                                                            });
-                                                                        }
-                                                                        layouts[line_idx_clone] = TextAreaLineLayout {
+                                                                // Re-wrap on the next frame if the available width changed
+                                                                if let Ok(mut wrap_width) = wrap_width_for_paint.lock() {
+                                                                    if *wrap_width != Some(bounds.size.width) {
+                                                                        *wrap_width = Some(bounds.size.width);
+                                                                        window.request_animation_frame();
+                                                                    }
+                                                                }
+
+                                                                // Shape the line using TextRun
+                                                                let shaped_line = window.text_system().shape_line(
+                                                                    display_text_clone.clone().into(),
+                                                                    font_size_clone,
+                                                                    &text_runs_clone,
+                                                                    None,
+                                                                );
+                                                                
+                                                                // Save layout info for mouse position calculation
+                                                                if let Ok(mut layouts) = layout_container_for_paint.lock() {
+                                                                    if let Some(layout) = layouts.get_mut(line_idx_clone) {
+                                                                        *layout = TextAreaLineLayout {
                                                                             bounds,
                                                                             shaped_line: shaped_line.clone(),
+                                                                            start: line_start,
                                                                         };
                                                                     }
-                                                                    
+                                                                }
+                                                                
+                                                                if !display_text_clone.is_empty() {
                                                                     let origin = bounds.origin;
                                                                     
                                                                     // Paint background first (for selection)
@@ -1250,14 +1573,14 @@ impl Render for TextArea {
                                                                 let cursor_x = if cursor_col_clone == 0 || line_str_clone.is_empty() {
                                                                     px(0.)
                                                                 } else {
-                                                                    let text_before = line_str_clone.chars().take(cursor_col_clone).collect::<String>();
+                                                                    let text_before = line_str_clone[..cursor_col_clone.min(line_str_clone.len())].to_string();
                                                                     if text_before.is_empty() {
                                                                         px(0.)
                                                                     } else {
                                                                         let temp_runs = vec![TextRun {
                                                                             len: text_before.len(),
                                                                             font: font_clone_for_cursor.clone(),
-                                                                            color: rgb(0x333333).into(),
+                                                                            color: text_color.into(),
                                                                             background_color: None,
                                                                             underline: None,
                                                                             strikethrough: None,
@@ -1275,9 +1598,9 @@ impl Render for TextArea {
                                                                 // Draw cursor
                                                                 let cursor_bounds = gpui::Bounds {
                                                                     origin: bounds.origin + gpui::point(cursor_x, px(1.)),
-                                                                    size: gpui::size(px(2.), px(18.)),
+                                                                    size: gpui::size(px(2.), line_height_clone - px(2.)),
                                                                 };
-                                                                window.paint_quad(gpui::fill(cursor_bounds, rgb(0x000000)));
+                                                                window.paint_quad(gpui::fill(cursor_bounds, text_color));
                                                             },
                                                         )
                                                         .absolute()
@@ -1294,7 +1617,14 @@ impl Render for TextArea {
                     })
             ]);
 
-        content
+        div()
+            .flex()
+            .flex_col()
+            .w_full()
+            .child(content)
+            .when_some(error, |this, message| {
+                this.child(render_error_message(&message, &theme))
+            })
     }
 }
 
@@ -1307,9 +1637,9 @@ impl EntityInputHandler for TextArea {
         _window: &mut Window,
         _cx: &mut Context<Self>,
     ) -> Option<String> {
-        let range = self.range_from_utf16(&range_utf16);
-        actual_range.replace(self.byte_range_to_utf16(&range));
-        Some(self.value[range].to_string())
+        let range = self.buffer.range_from_utf16(&range_utf16);
+        actual_range.replace(self.buffer.range_to_utf16(&range));
+        Some(self.buffer.text()[range].to_string())
     }
 
     fn selected_text_range(
@@ -1318,10 +1648,9 @@ impl EntityInputHandler for TextArea {
         _window: &mut Window,
         _cx: &mut Context<Self>,
     ) -> Option<UTF16Selection> {
-        let range = self.selected_range();
         Some(UTF16Selection {
-            range: self.char_range_to_utf16(&range),
-            reversed: false,
+            range: self.buffer.range_to_utf16(&self.buffer.selected_range()),
+            reversed: self.buffer.is_selection_reversed(),
         })
     }
 
@@ -1330,13 +1659,13 @@ impl EntityInputHandler for TextArea {
         _window: &mut Window,
         _cx: &mut Context<Self>,
     ) -> Option<std::ops::Range<usize>> {
-        self.marked_range
-            .as_ref()
-            .map(|range| self.byte_range_to_utf16(range))
+        self.buffer
+            .marked_range()
+            .map(|range| self.buffer.range_to_utf16(&range))
     }
 
     fn unmark_text(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
-        self.marked_range = None;
+        self.buffer.unmark_text();
     }
 
     fn replace_text_in_range(
@@ -1349,157 +1678,51 @@ impl EntityInputHandler for TextArea {
         if self.disabled {
             return;
         }
-
-        let mut range = range_utf16
-            .as_ref()
-            .map(|range_utf16| self.range_from_utf16(range_utf16))
-            .or(self.marked_range.clone())
-            .unwrap_or_else(|| {
-                // Convert character selection to byte range
-                let char_range = self.selected_range();
-                self.char_range_to_byte_range(&char_range)
-            });
-
-        // Ensure range is within bounds and on character boundaries
-        range.start = range.start.min(self.value.len());
-        range.end = range.end.min(self.value.len());
-
-        while range.start > 0 && !self.value.is_char_boundary(range.start) {
-            range.start -= 1;
-        }
-        while range.end > 0 && range.end < self.value.len() && !self.value.is_char_boundary(range.end) {
-            range.end += 1;
-        }
-        if range.end > self.value.len() {
-            range.end = self.value.len();
-        }
-
-        // Replace text in range
-        let mut new_value = String::new();
-        new_value.push_str(&self.value[..range.start]);
-        new_value.push_str(new_text);
-        new_value.push_str(&self.value[range.end..]);
-
-        // Check max length
-        if let Some(max_len) = self.max_length {
-            if new_value.chars().count() > max_len {
-                return;
-            }
-        }
-
-        // Convert byte position to character position for cursor
-        let char_pos_before = self.value[..range.start].chars().count();
-
-        // Apply the change
-        self.value = new_value;
-        self.cursor_pos = char_pos_before + new_text.chars().count();
-        self.selection_start = None;
-        self.selection_end = None;
-        self.marked_range = None;
-
-
-
-        self.pause_blinking(cx);
-
-        // Check for CJK characters and emit IME event if detected
-        if Self::contains_cjk(new_text) {
-            cx.emit(TextAreaEvent::Ime(self.value.clone()));
-        }
-
-        cx.emit(TextAreaEvent::Change(self.value.clone()));
-        cx.notify();
+        let range = range_utf16.map(|range_utf16| self.buffer.range_from_utf16(&range_utf16));
+        let changed = self.buffer.replace_text_in_range(range, new_text);
+        self.after_input(changed, new_text, cx);
     }
 
     fn replace_and_mark_text_in_range(
         &mut self,
         range_utf16: Option<std::ops::Range<usize>>,
         new_text: &str,
-        _new_selected_range_utf16: Option<std::ops::Range<usize>>,
+        new_selected_range_utf16: Option<std::ops::Range<usize>>,
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
         if self.disabled {
             return;
         }
-        
-        // Get the range to replace
-        let byte_range = if let Some(range_utf16) = range_utf16 {
-            // Convert UTF-16 range to byte range
-            self.range_from_utf16(&range_utf16)
-        } else if let Some(marked_range) = &self.marked_range {
-            // Use marked range (already in bytes)
-            marked_range.clone()
-        } else {
-            // Use current selection/cursor position
-            let selected_range = self.selected_range(); // This is in character indices
-            // Convert character range to byte range
-            self.char_range_to_byte_range(&selected_range)
-        };
-
-        // Ensure range is within bounds and on character boundaries
-        let mut range = byte_range;
-        range.start = range.start.min(self.value.len());
-        range.end = range.end.min(self.value.len());
-
-        while range.start > 0 && !self.value.is_char_boundary(range.start) {
-            range.start -= 1;
-        }
-        while range.end > 0 && range.end < self.value.len() && !self.value.is_char_boundary(range.end) {
-            range.end += 1;
-        }
-        if range.end > self.value.len() {
-            range.end = self.value.len();
-        }
-
-        // Replace text in range
-        let mut new_value = String::new();
-        new_value.push_str(&self.value[..range.start]);
-        new_value.push_str(new_text);
-        new_value.push_str(&self.value[range.end..]);
-
-        // Check max length
-        if let Some(max_len) = self.max_length {
-            if new_value.chars().count() > max_len {
-                return;
-            }
-        }
-
-        // Convert byte position back to character position for cursor
-        let char_pos_before = self.value[..range.start].chars().count();
-
-        // Apply the change
-        self.value = new_value;
-        self.cursor_pos = char_pos_before + new_text.chars().count();
-        self.selection_start = None;
-        self.selection_end = None;
-
-        // Set marked range for IME composition (in bytes)
-        if !new_text.is_empty() {
-            self.marked_range = Some(range.start..(range.start + new_text.len()));
-        } else {
-            self.marked_range = None;
-        }
-        
-        self.pause_blinking(cx);
-        
-        // Check for CJK characters and emit IME event if detected
-        if Self::contains_cjk(new_text) {
-            cx.emit(TextAreaEvent::Ime(self.value.clone()));
-        }
-        
-        cx.emit(TextAreaEvent::Change(self.value.clone()));
-        cx.notify();
+        let range = range_utf16.map(|range_utf16| self.buffer.range_from_utf16(&range_utf16));
+        let changed = self.buffer.replace_and_mark_text_in_range(range, new_text, new_selected_range_utf16);
+        self.after_input(changed, new_text, cx);
     }
 
     fn bounds_for_range(
         &mut self,
-        _range_utf16: std::ops::Range<usize>,
+        range_utf16: std::ops::Range<usize>,
         bounds: Bounds<Pixels>,
         _window: &mut Window,
         _cx: &mut Context<Self>,
     ) -> Option<Bounds<Pixels>> {
-        // Return the input bounds for IME candidate window positioning
-        Some(bounds)
+        // Position the IME candidate window at the start of the range,
+        // on whichever visual line it falls after wrapping
+        let range = self.buffer.range_from_utf16(&range_utf16);
+        let line_idx = self.visual_line_index(range.start);
+
+        let layouts = self.line_layouts.lock().ok()?;
+        let Some(layout) = layouts.get(line_idx).filter(|layout| layout.bounds.size.height > px(0.)) else {
+            // Not painted yet: fall back to the input bounds
+            return Some(bounds);
+        };
+
+        let index = range.start.saturating_sub(layout.start).min(layout.shaped_line.text.len());
+        let x = layout.shaped_line.x_for_index(index);
+        Some(Bounds::new(
+            point(layout.bounds.left() + x, layout.bounds.top()),
+            size(px(2.), layout.bounds.size.height),
+        ))
     }
 
     fn character_index_for_point(
@@ -1508,10 +1731,9 @@ impl EntityInputHandler for TextArea {
         _window: &mut Window,
         _cx: &mut Context<Self>,
     ) -> Option<usize> {
-        // Convert pixel position to character index
-        let byte_index = self.index_for_mouse_position(point);
-        // Convert byte index to UTF-16 code units
-        Some(self.value[..byte_index].encode_utf16().count())
+        // Convert pixel position to a byte offset, then to UTF-16 code units
+        let index = self.index_for_mouse_position(point);
+        Some(offset_to_utf16(self.buffer.text(), index))
     }
 }
 
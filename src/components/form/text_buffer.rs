@@ -0,0 +1,725 @@
+// Headless editing core shared by TextInput and TextArea
+
+use std::ops::Range;
+use std::sync::Arc;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use super::history::{EditHistory, EditKind, EditSnapshot};
+use super::input_format::InputFormatter;
+use crate::utils::{
+    floor_grapheme_boundary, grapheme_count, next_grapheme_boundary, next_word_end,
+    offset_from_utf16, offset_to_utf16, previous_grapheme_boundary, previous_word_start,
+    word_range_at,
+};
+
+/// Callback deciding whether a new value is accepted
+pub(crate) type Validator = Arc<dyn Fn(&str) -> bool>;
+
+/// Text, cursor, selection, IME composition and undo history of an editor.
+///
+/// All positions are byte offsets into the text. The cursor only stops on
+/// grapheme cluster boundaries, except inside an IME composition where the
+/// platform places it. The buffer knows nothing about layout or rendering:
+/// components map mouse positions and visual lines to offsets, call the
+/// editing methods, and emit events when a method reports a change.
+///
+/// With a formatter, every edit is passed through it and the buffer holds
+/// the formatted text.
+#[derive(Default)]
+pub(crate) struct TextBuffer {
+    text: String,
+    cursor: usize,
+    /// Fixed end of the selection; the selection spans anchor..cursor
+    anchor: Option<usize>,
+    /// Text being composed by an input method
+    marked_range: Option<Range<usize>>,
+    /// Maximum length in grapheme clusters
+    max_length: Option<usize>,
+    validator: Option<Validator>,
+    formatter: Option<Arc<dyn InputFormatter>>,
+    history: EditHistory,
+}
+
+impl TextBuffer {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The text without formatting, see [`InputFormatter::unformat`]
+    pub fn raw_text(&self) -> String {
+        match &self.formatter {
+            Some(formatter) => formatter.unformat(&self.text),
+            None => self.text.clone(),
+        }
+    }
+
+    pub fn formatter(&self) -> Option<&Arc<dyn InputFormatter>> {
+        self.formatter.as_ref()
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The selected range, if any text is selected
+    pub fn selection(&self) -> Option<Range<usize>> {
+        let anchor = self.anchor?;
+        (anchor != self.cursor).then(|| anchor.min(self.cursor)..anchor.max(self.cursor))
+    }
+
+    pub fn has_selection(&self) -> bool {
+        self.selection().is_some()
+    }
+
+    /// The selection, or an empty range at the cursor
+    pub fn selected_range(&self) -> Range<usize> {
+        self.selection().unwrap_or(self.cursor..self.cursor)
+    }
+
+    pub fn selected_text(&self) -> &str {
+        &self.text[self.selected_range()]
+    }
+
+    /// Whether the selection was made backwards, with the cursor at its start
+    pub fn is_selection_reversed(&self) -> bool {
+        self.has_selection() && self.anchor > Some(self.cursor)
+    }
+
+    pub fn marked_range(&self) -> Option<Range<usize>> {
+        self.marked_range.clone()
+    }
+
+    pub fn set_max_length(&mut self, max_length: Option<usize>) {
+        self.max_length = max_length;
+    }
+
+    pub fn set_validator(&mut self, validator: Option<Validator>) {
+        self.validator = validator;
+    }
+
+    /// Set the formatter and reformat the current text
+    pub fn set_formatter(&mut self, formatter: Option<Arc<dyn InputFormatter>>) {
+        self.formatter = formatter;
+        let text = std::mem::take(&mut self.text);
+        self.reset(text);
+    }
+
+    /// Replace the text without recording an undo step, e.g. for an initial value
+    pub fn reset(&mut self, text: impl Into<String>) {
+        let text = text.into();
+        let end = text.len();
+        (self.text, self.cursor) = self.format(text, end);
+        self.anchor = None;
+        self.marked_range = None;
+    }
+
+    // ------------------------------------------------------------------------
+    // Cursor and selection
+    // ------------------------------------------------------------------------
+
+    /// Move the cursor to `offset`, extending the selection from its anchor
+    /// if requested
+    pub fn move_to(&mut self, offset: usize, extend_selection: bool) {
+        if extend_selection {
+            self.anchor.get_or_insert(self.cursor);
+        } else {
+            self.anchor = None;
+        }
+        self.cursor = floor_grapheme_boundary(&self.text, offset);
+    }
+
+    /// Move one grapheme cluster left, or to the start of the selection
+    pub fn move_left(&mut self, extend_selection: bool) {
+        match self.selection() {
+            Some(selection) if !extend_selection => self.move_to(selection.start, false),
+            _ => self.move_to(previous_grapheme_boundary(&self.text, self.cursor), extend_selection),
+        }
+    }
+
+    /// Move one grapheme cluster right, or to the end of the selection
+    pub fn move_right(&mut self, extend_selection: bool) {
+        match self.selection() {
+            Some(selection) if !extend_selection => self.move_to(selection.end, false),
+            _ => self.move_to(next_grapheme_boundary(&self.text, self.cursor), extend_selection),
+        }
+    }
+
+    pub fn move_word_left(&mut self, extend_selection: bool) {
+        self.move_to(previous_word_start(&self.text, self.cursor), extend_selection);
+    }
+
+    pub fn move_word_right(&mut self, extend_selection: bool) {
+        self.move_to(next_word_end(&self.text, self.cursor), extend_selection);
+    }
+
+    /// Select `range`, leaving the cursor at its end
+    pub fn select_range(&mut self, range: Range<usize>) {
+        let range = self.clamp_range(range);
+        self.anchor = Some(range.start);
+        self.cursor = range.end;
+    }
+
+    pub fn select_all(&mut self) {
+        self.select_range(0..self.text.len());
+    }
+
+    /// Select the word at `offset`, for double-click
+    pub fn select_word_at(&mut self, offset: usize) {
+        self.select_range(word_range_at(&self.text, offset));
+    }
+
+    /// Select the hard line containing `offset`, without its newline
+    pub fn select_line_at(&mut self, offset: usize) {
+        let offset = self.clamp_range(offset..offset).start;
+        let start = self.text[..offset].rfind('\n').map_or(0, |idx| idx + 1);
+        let end = self.text[offset..].find('\n').map_or(self.text.len(), |idx| offset + idx);
+        self.select_range(start..end);
+    }
+
+    // ------------------------------------------------------------------------
+    // Editing
+    //
+    // Each method returns whether the text changed.
+    // ------------------------------------------------------------------------
+
+    /// Replace all text as a single undo step, moving the cursor to the end.
+    ///
+    /// Rejected if it exceeds the maximum length or fails validation.
+    pub fn set_text(&mut self, text: impl Into<String>) -> bool {
+        let text = text.into();
+        let end = text.len();
+        let (text, cursor) = self.format(text, end);
+        if !self.accepts(&text) {
+            return false;
+        }
+        self.record(EditKind::Other, &text, cursor);
+        self.text = text;
+        self.cursor = cursor;
+        self.anchor = None;
+        self.marked_range = None;
+        true
+    }
+
+    /// Remove all text as a single undo step
+    pub fn clear(&mut self) -> bool {
+        if self.text.is_empty() {
+            return false;
+        }
+        self.record(EditKind::Other, "", 0);
+        self.reset(String::new());
+        true
+    }
+
+    /// Insert typed text, replacing the selection
+    pub fn insert(&mut self, text: &str) -> bool {
+        self.replace_text_in_range(None, text)
+    }
+
+    /// Insert pasted text as its own undo step, truncated to fit the maximum length
+    pub fn paste(&mut self, text: &str) -> bool {
+        let mut text = text;
+        if let Some(max_length) = self.max_length {
+            let kept = grapheme_count(&self.text) - grapheme_count(self.selected_text());
+            let capacity = max_length.saturating_sub(kept);
+            if let Some((end, _)) = text.grapheme_indices(true).nth(capacity) {
+                text = &text[..end];
+            }
+        }
+        if text.is_empty() {
+            return false;
+        }
+
+        self.marked_range = None;
+        self.history.break_group();
+        let changed = self.insert(text);
+        self.history.break_group();
+        changed
+    }
+
+    /// Delete the selection and return its text
+    pub fn cut(&mut self) -> Option<String> {
+        let range = self.selection()?;
+        let text = self.text[range.clone()].to_string();
+        self.delete_range(range);
+        Some(text)
+    }
+
+    /// Delete `range` as its own undo step
+    pub fn delete_range(&mut self, range: Range<usize>) -> bool {
+        let range = self.clamp_range(range);
+        if range.is_empty() {
+            return false;
+        }
+        self.edit(EditKind::Other, range, "")
+    }
+
+    /// Delete the selection, or the grapheme cluster before the cursor.
+    ///
+    /// During IME composition this only ends the composition.
+    pub fn backspace(&mut self) -> bool {
+        if self.marked_range.take().is_some() {
+            return false;
+        }
+        if let Some(selection) = self.selection() {
+            return self.delete_range(selection);
+        }
+        let start = previous_grapheme_boundary(&self.text, self.cursor);
+        if start == self.cursor {
+            return false;
+        }
+        self.edit(EditKind::Delete, start..self.cursor, "")
+    }
+
+    /// Delete the selection, or the grapheme cluster after the cursor
+    pub fn delete(&mut self) -> bool {
+        if self.marked_range.take().is_some() {
+            return false;
+        }
+        if let Some(selection) = self.selection() {
+            return self.delete_range(selection);
+        }
+        let end = next_grapheme_boundary(&self.text, self.cursor);
+        if end == self.cursor {
+            return false;
+        }
+        self.edit(EditKind::Delete, self.cursor..end, "")
+    }
+
+    /// Delete back to the start of the previous word.
+    ///
+    /// With a selection or during composition this behaves like backspace.
+    pub fn delete_word_backward(&mut self) -> bool {
+        if self.marked_range.is_some() || self.has_selection() {
+            return self.backspace();
+        }
+        self.delete_range(previous_word_start(&self.text, self.cursor)..self.cursor)
+    }
+
+    /// Delete forward to the end of the next word
+    pub fn delete_word_forward(&mut self) -> bool {
+        if self.marked_range.is_some() || self.has_selection() {
+            return self.delete();
+        }
+        self.delete_range(self.cursor..next_word_end(&self.text, self.cursor))
+    }
+
+    // ------------------------------------------------------------------------
+    // Undo and redo
+    // ------------------------------------------------------------------------
+
+    pub fn undo(&mut self) -> bool {
+        self.marked_range = None;
+        match self.history.undo(self.snapshot()) {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn redo(&mut self) -> bool {
+        self.marked_range = None;
+        match self.history.redo(self.snapshot()) {
+            Some(snapshot) => {
+                self.restore(snapshot);
+                true
+            }
+            None => false,
+        }
+    }
+
+    pub fn can_undo(&self) -> bool {
+        self.history.can_undo()
+    }
+
+    pub fn can_redo(&self) -> bool {
+        self.history.can_redo()
+    }
+
+    // ------------------------------------------------------------------------
+    // Input method support
+    // ------------------------------------------------------------------------
+
+    /// Commit text from typing or an input method.
+    ///
+    /// Replaces `range` if given, otherwise the marked text, otherwise the
+    /// selection. Committing a composition belongs to the undo step recorded
+    /// when the composition started; the formatter only sees committed text.
+    pub fn replace_text_in_range(&mut self, range: Option<Range<usize>>, text: &str) -> bool {
+        let range = self.replacement_range(range);
+        if !self.edit(EditKind::Insert, range, text) {
+            return false;
+        }
+        if self.marked_range.take().is_some() {
+            self.history.discard_if_unchanged(&self.text);
+        }
+        true
+    }
+
+    /// Update the text being composed by an input method and mark it.
+    ///
+    /// `selected_utf16` is the cursor range within `text` reported by the
+    /// platform, in UTF-16 code units. The whole composition is one undo step.
+    pub fn replace_and_mark_text_in_range(
+        &mut self,
+        range: Option<Range<usize>>,
+        text: &str,
+        selected_utf16: Option<Range<usize>>,
+    ) -> bool {
+        let range = self.replacement_range(range);
+        let mut new_text = self.text.clone();
+        new_text.replace_range(range.clone(), text);
+        if !self.accepts(&new_text) {
+            return false;
+        }
+
+        let marked_end = range.start + text.len();
+        if self.marked_range.is_none() {
+            self.record(EditKind::Compose, text, marked_end);
+        } else {
+            self.history.touch(marked_end);
+        }
+
+        self.text = new_text;
+        self.anchor = None;
+        self.cursor = match selected_utf16 {
+            Some(selected) => range.start + offset_from_utf16(text, selected.end),
+            None => marked_end,
+        };
+        if text.is_empty() {
+            self.marked_range = None;
+            self.history.discard_if_unchanged(&self.text);
+        } else {
+            self.marked_range = Some(range.start..marked_end);
+        }
+        true
+    }
+
+    pub fn unmark_text(&mut self) {
+        self.marked_range = None;
+    }
+
+    /// Convert a byte range to UTF-16 code units
+    pub fn range_to_utf16(&self, range: &Range<usize>) -> Range<usize> {
+        offset_to_utf16(&self.text, range.start)..offset_to_utf16(&self.text, range.end)
+    }
+
+    /// Convert a UTF-16 code unit range to bytes
+    pub fn range_from_utf16(&self, range_utf16: &Range<usize>) -> Range<usize> {
+        offset_from_utf16(&self.text, range_utf16.start)..offset_from_utf16(&self.text, range_utf16.end)
+    }
+
+    // ------------------------------------------------------------------------
+    // Internals
+    // ------------------------------------------------------------------------
+
+    /// Whether a new value fits the maximum length and passes validation
+    fn accepts(&self, text: &str) -> bool {
+        self.max_length.is_none_or(|max_length| grapheme_count(text) <= max_length)
+            && self.validator.as_ref().is_none_or(|validator| validator(text))
+    }
+
+    /// Clamp a range to the text and widen it to character boundaries
+    fn clamp_range(&self, range: Range<usize>) -> Range<usize> {
+        let mut start = range.start.min(self.text.len());
+        let mut end = range.end.clamp(start, self.text.len());
+        while !self.text.is_char_boundary(start) {
+            start -= 1;
+        }
+        while !self.text.is_char_boundary(end) {
+            end += 1;
+        }
+        start..end
+    }
+
+    /// The range replaced by typed or composed text
+    fn replacement_range(&self, range: Option<Range<usize>>) -> Range<usize> {
+        let range = range
+            .or_else(|| self.marked_range.clone())
+            .unwrap_or_else(|| self.selected_range());
+        self.clamp_range(range)
+    }
+
+    /// Run `text` through the formatter, mapping the cursor into the result
+    fn format(&self, text: String, cursor: usize) -> (String, usize) {
+        match &self.formatter {
+            Some(formatter) => formatter.format(&text, cursor),
+            None => (text, cursor),
+        }
+    }
+
+    /// Replace `range` with `inserted`, format, validate and record the edit.
+    ///
+    /// An edit the formatter undoes, such as deleting a mask literal, only
+    /// moves the cursor. Deletions skip validation so a value can always be
+    /// cleared.
+    fn edit(&mut self, kind: EditKind, range: Range<usize>, inserted: &str) -> bool {
+        let mut new_text = self.text.clone();
+        new_text.replace_range(range.clone(), inserted);
+        let (new_text, cursor_after) = self.format(new_text, range.start + inserted.len());
+        if new_text == self.text && self.marked_range.is_none() {
+            self.move_to(cursor_after, false);
+            return false;
+        }
+        if !inserted.is_empty() && !self.accepts(&new_text) {
+            return false;
+        }
+
+        if self.marked_range.is_some() {
+            self.history.touch(cursor_after);
+        } else {
+            self.record(kind, inserted, cursor_after);
+        }
+        self.text = new_text;
+        self.cursor = cursor_after;
+        self.anchor = None;
+        true
+    }
+
+    fn snapshot(&self) -> EditSnapshot {
+        EditSnapshot {
+            value: self.text.clone(),
+            cursor: self.cursor,
+            selection: self.selection().map(|_| (self.anchor.unwrap_or(self.cursor), self.cursor)),
+        }
+    }
+
+    fn restore(&mut self, snapshot: EditSnapshot) {
+        self.text = snapshot.value;
+        self.cursor = snapshot.cursor.min(self.text.len());
+        self.anchor = snapshot.selection.map(|(anchor, _)| anchor.min(self.text.len()));
+    }
+
+    /// Record the current state before an edit is applied
+    fn record(&mut self, kind: EditKind, inserted: &str, cursor_after: usize) {
+        let before = self.snapshot();
+        self.history.record(kind, before, inserted, cursor_after);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::TextBuffer;
+    use crate::components::form::InputMask;
+
+    fn buffer(text: &str) -> TextBuffer {
+        let mut buffer = TextBuffer::default();
+        buffer.reset(text);
+        buffer
+    }
+
+    fn type_text(buffer: &mut TextBuffer, text: &str) {
+        for c in text.chars() {
+            buffer.insert(c.encode_utf8(&mut [0; 4]));
+        }
+    }
+
+    #[test]
+    fn typing_replaces_the_selection() {
+        let mut buffer = buffer("hello world");
+        buffer.select_word_at(8);
+        assert_eq!(buffer.selected_text(), "world");
+
+        type_text(&mut buffer, "there");
+        assert_eq!(buffer.text(), "hello there");
+        assert_eq!(buffer.cursor(), 11);
+        assert!(!buffer.has_selection());
+    }
+
+    #[test]
+    fn selection_extends_from_the_anchor() {
+        let mut buffer = buffer("abcdef");
+        buffer.move_to(3, false);
+        buffer.move_right(true);
+        buffer.move_right(true);
+        assert_eq!(buffer.selection(), Some(3..5));
+
+        // Moving back past the anchor reverses the selection
+        buffer.move_left(true);
+        buffer.move_left(true);
+        buffer.move_left(true);
+        assert_eq!(buffer.selection(), Some(2..3));
+        assert!(buffer.is_selection_reversed());
+
+        // Without Shift, arrows collapse to the selection edge
+        buffer.move_right(false);
+        assert_eq!(buffer.cursor(), 3);
+        assert!(!buffer.has_selection());
+    }
+
+    #[test]
+    fn backspace_and_delete_remove_grapheme_clusters() {
+        let mut buffer = buffer("a👨‍👩‍👧b🇯🇵");
+        buffer.backspace();
+        assert_eq!(buffer.text(), "a👨‍👩‍👧b");
+
+        buffer.move_to(1, false);
+        buffer.delete();
+        assert_eq!(buffer.text(), "ab");
+
+        buffer.move_right(false);
+        assert_eq!(buffer.cursor(), 2);
+    }
+
+    #[test]
+    fn cursor_never_lands_inside_a_cluster() {
+        let mut buffer = buffer("e\u{301}x");
+        buffer.move_to(2, false);
+        assert_eq!(buffer.cursor(), 0);
+        buffer.move_right(false);
+        assert_eq!(buffer.cursor(), 3);
+    }
+
+    #[test]
+    fn word_motion_and_deletion() {
+        let mut buffer = buffer("hello brave new world");
+        buffer.move_word_left(false);
+        assert_eq!(buffer.cursor(), 16);
+        buffer.move_word_left(true);
+        assert_eq!(buffer.selected_text(), "new ");
+
+        buffer.move_to(11, false);
+        buffer.delete_word_backward();
+        assert_eq!(buffer.text(), "hello  new world");
+        buffer.delete_word_forward();
+        assert_eq!(buffer.text(), "hello  world");
+    }
+
+    #[test]
+    fn select_line_excludes_the_newline() {
+        let mut buffer = buffer("first\nsecond\nthird");
+        buffer.select_line_at(8);
+        assert_eq!(buffer.selected_text(), "second");
+        buffer.select_line_at(0);
+        assert_eq!(buffer.selected_text(), "first");
+    }
+
+    #[test]
+    fn typing_is_undone_as_one_step() {
+        let mut buffer = buffer("");
+        type_text(&mut buffer, "hello");
+        buffer.backspace();
+        buffer.backspace();
+        assert_eq!(buffer.text(), "hel");
+
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "hello");
+        assert!(buffer.undo());
+        assert_eq!(buffer.text(), "");
+        assert!(!buffer.can_undo());
+
+        assert!(buffer.redo());
+        assert_eq!(buffer.text(), "hello");
+        assert_eq!(buffer.cursor(), 5);
+    }
+
+    #[test]
+    fn undo_restores_the_selection() {
+        let mut buffer = buffer("hello world");
+        buffer.select_range(6..11);
+        assert_eq!(buffer.cut().as_deref(), Some("world"));
+        assert_eq!(buffer.text(), "hello ");
+
+        buffer.undo();
+        assert_eq!(buffer.text(), "hello world");
+        assert_eq!(buffer.selection(), Some(6..11));
+    }
+
+    #[test]
+    fn paste_is_its_own_step_and_respects_max_length() {
+        let mut buffer = buffer("");
+        buffer.set_max_length(Some(5));
+        type_text(&mut buffer, "ab");
+        buffer.paste("c🇯🇵d🇰🇷e");
+        assert_eq!(buffer.text(), "abc🇯🇵d");
+
+        // Typing after a paste starts a new step
+        buffer.undo();
+        assert_eq!(buffer.text(), "ab");
+    }
+
+    #[test]
+    fn validator_rejects_edits() {
+        let mut buffer = buffer("12");
+        buffer.set_validator(Some(Arc::new(|text: &str| text.chars().all(|c| c.is_ascii_digit()))));
+        assert!(!buffer.insert("x"));
+        assert!(buffer.insert("3"));
+        assert!(!buffer.set_text("abc"));
+        assert_eq!(buffer.text(), "123");
+    }
+
+    #[test]
+    fn composition_commits_as_one_step() {
+        let mut buffer = buffer("a");
+        assert!(buffer.replace_and_mark_text_in_range(None, "n", None));
+        assert!(buffer.replace_and_mark_text_in_range(None, "ni", None));
+        assert_eq!(buffer.marked_range(), Some(1..3));
+
+        // The IME commits the composed text, replacing the marked range
+        assert!(buffer.replace_text_in_range(None, "你"));
+        assert_eq!(buffer.text(), "a你");
+        assert_eq!(buffer.marked_range(), None);
+        assert_eq!(buffer.cursor(), 4);
+
+        buffer.undo();
+        assert_eq!(buffer.text(), "a");
+    }
+
+    #[test]
+    fn cancelled_composition_leaves_no_undo_step() {
+        let mut buffer = buffer("");
+        buffer.replace_and_mark_text_in_range(None, "k", None);
+        buffer.replace_and_mark_text_in_range(None, "", None);
+        assert_eq!(buffer.text(), "");
+        assert!(!buffer.can_undo());
+    }
+
+    #[test]
+    fn mask_formats_every_edit() {
+        let mut buffer = buffer("");
+        buffer.set_formatter(Some(Arc::new(InputMask::phone())));
+        type_text(&mut buffer, "555x1");
+        assert_eq!(buffer.text(), "(555) 1__-____");
+        assert_eq!(buffer.cursor(), 7);
+
+        // Backspace over a literal only moves the cursor
+        buffer.backspace();
+        buffer.move_to(6, false);
+        assert!(!buffer.backspace());
+        assert_eq!(buffer.text(), "(555) ___-____");
+        assert_eq!(buffer.cursor(), 5);
+
+        // Typed digits form one undo step
+        buffer.undo();
+        assert_eq!(buffer.text(), "(555) 1__-____");
+        buffer.undo();
+        assert_eq!(buffer.text(), "");
+    }
+
+    #[test]
+    fn mask_reformats_set_and_initial_text() {
+        let mut buffer = buffer("2024123");
+        buffer.set_formatter(Some(Arc::new(InputMask::date())));
+        assert_eq!(buffer.text(), "2024-12-3_");
+        assert_eq!(buffer.cursor(), 9);
+
+        assert!(buffer.set_text("20250101"));
+        assert_eq!(buffer.text(), "2025-01-01");
+        assert_eq!(buffer.raw_text(), "20250101");
+        buffer.select_all();
+        assert!(buffer.backspace());
+        assert_eq!(buffer.text(), "");
+    }
+
+    #[test]
+    fn utf16_ranges_round_trip() {
+        let buffer = buffer("a😀b");
+        assert_eq!(buffer.range_to_utf16(&(1..5)), 1..3);
+        assert_eq!(buffer.range_from_utf16(&(1..3)), 1..5);
+        assert_eq!(buffer.range_from_utf16(&(0..10)), 0..6);
+    }
+}
@@ -1,8 +1,16 @@
 use gpui::*;
 use gpui::prelude::FluentBuilder;
+use serde_json::Value;
 use std::sync::{Arc, Mutex};
 use arboard::Clipboard;
 
+use super::input_format::{InputFormatter, InputMask};
+use super::form_container::{value_to_string, FormControl};
+use super::text_buffer::TextBuffer;
+use super::validation::{render_error_message, validation_methods, ValidateOn, Validation, ValidationState};
+use crate::theme::*;
+use crate::utils::{floor_grapheme_boundary, next_word_end, offset_to_utf16, previous_word_start};
+
 // Element state for storing layout information
 #[derive(Clone)]
 struct TextInputLayout {
@@ -388,19 +396,43 @@ pub enum TextInputEvent {
 /// # }
 /// ```
 ///
+/// # Validation
+///
+/// Unlike [`validator`](Self::validator), a validation rule never rejects an
+/// edit: it returns `Err(message)` and the message is shown below the input
+/// with an error border.
+///
+/// ```rust,no_run
+/// use fluix::TextInput;
+/// use gpui::*;
+///
+/// # async fn is_taken(_name: &str) -> bool { false }
+/// # fn example(cx: &mut Context<TextInput>) {
+/// let email = TextInput::new(cx)
+///     .placeholder("Email")
+///     .validate(|value| {
+///         if value.is_empty() { Err("Email is required".into()) } else { Ok(()) }
+///     })
+///     .validate(|value| {
+///         if value.contains('@') { Ok(()) } else { Err("Enter a valid email".into()) }
+///     });
+///
+/// let username = TextInput::new(cx).validate_async(|value| {
+///     let name = value.to_string();
+///     async move {
+///         if is_taken(&name).await { Err("Username is taken".into()) } else { Ok(()) }
+///     }
+/// });
+/// # }
+/// ```
+///
 /// # See Also
 ///
 /// - [`PasswordMaskMode`] - Password masking modes
 /// - [`TextInputEvent`] - Events emitted by TextInput
 pub struct TextInput {
-    /// Current input value
-    value: String,
-    /// Cursor position (byte offset in the string)
-    cursor_position: usize,
-    /// Selection start position (byte offset, None if no selection)
-    selection_start: Option<usize>,
-    /// Selection end position (byte offset, None if no selection)
-    selection_end: Option<usize>,
+    /// Value, cursor, selection, IME composition and undo history
+    buffer: TextBuffer,
     /// Placeholder text when empty
     placeholder: String,
     /// Focus handle for keyboard input
@@ -413,10 +445,6 @@ pub struct TextInput {
     password_visible: bool,
     /// Password masking mode
     password_mask_mode: PasswordMaskMode,
-    /// Maximum length (None for unlimited)
-    max_length: Option<usize>,
-    /// Custom validation function
-    validator: Option<Arc<dyn Fn(&str) -> bool>>,
     /// Blink epoch - increments when cursor should reset to visible
     blink_epoch: usize,
     /// Whether cursor is currently visible (for blinking)
@@ -427,8 +455,6 @@ pub struct TextInput {
     is_dragging: bool,
     /// Last layout info for mouse position calculation
     last_layout: Option<TextInputLayout>,
-    /// Marked text range for IME (Input Method Editor) composition
-    marked_range: Option<std::ops::Range<usize>>,
     /// Whether to show border (for embedded use cases like combobox)
     show_border: bool,
     /// Custom background color (None uses default)
@@ -437,6 +463,14 @@ pub struct TextInput {
     custom_border_color: Option<Rgba>,
     /// Custom right padding (None uses default px_3 right padding)
     custom_right_padding: Option<f32>,
+    /// Validation rules and the current error
+    validation: Validation<str>,
+    /// Runs blur validation, registered on first render
+    _blur_subscription: Option<Subscription>,
+}
+
+validation_methods! {
+    TextInput: str = |this| this.buffer.text().to_string()
 }
 
 impl TextInput {
@@ -480,28 +514,24 @@ impl TextInput {
     /// ```
     pub fn new(cx: &mut Context<Self>) -> Self {
         Self {
-            value: String::new(),
-            cursor_position: 0,
-            selection_start: None,
-            selection_end: None,
+            buffer: TextBuffer::default(),
             placeholder: String::new(),
             focus_handle: cx.focus_handle(),
             disabled: false,
             is_password: false,
             password_visible: false,
             password_mask_mode: PasswordMaskMode::All,
-            max_length: None,
-            validator: None,
             blink_epoch: 0,
             cursor_visible: true,
             _blink_task: None,
             is_dragging: false,
             last_layout: None,
-            marked_range: None,
             show_border: true,
             custom_bg_color: None,
             custom_border_color: None,
             custom_right_padding: None,
+            validation: Validation::default(),
+            _blur_subscription: None,
         }
     }
 
@@ -554,10 +584,7 @@ impl TextInput {
     /// # }
     /// ```
     pub fn value(mut self, value: impl Into<String>) -> Self {
-        self.value = value.into();
-        self.cursor_position = self.value.len();
-        self.selection_start = None;
-        self.selection_end = None;
+        self.buffer.reset(value);
         self
     }
 
@@ -772,7 +799,9 @@ impl TextInput {
     /// Set the maximum allowed length for the input value.
     ///
     /// This method limits the number of characters that can be entered.
-    /// Attempts to exceed this limit will be rejected.
+    /// Attempts to exceed this limit will be rejected. Characters are counted
+    /// as user-perceived characters (grapheme clusters), so an emoji such as
+    /// 👨‍👩‍👧 or a flag counts as one.
     ///
     /// # Arguments
     ///
@@ -794,7 +823,7 @@ impl TextInput {
     /// # }
     /// ```
     pub fn max_length(mut self, max_length: usize) -> Self {
-        self.max_length = Some(max_length);
+        self.buffer.set_max_length(Some(max_length));
         self
     }
 
@@ -802,7 +831,8 @@ impl TextInput {
     ///
     /// This method sets a custom validator function that is called whenever
     /// the input value changes. If the validator returns `false`, the change
-    /// is rejected and the value remains unchanged.
+    /// is rejected and the value remains unchanged. With a [`mask`](Self::mask)
+    /// or [`formatter`](Self::formatter), the validator sees the formatted value.
     ///
     /// # Arguments
     ///
@@ -835,7 +865,66 @@ impl TextInput {
     /// # }
     /// ```
     pub fn validator(mut self, validator: impl Fn(&str) -> bool + 'static) -> Self {
-        self.validator = Some(Arc::new(validator));
+        self.buffer.set_validator(Some(Arc::new(validator)));
+        self
+    }
+
+    /// Restrict the input to a fixed format such as a phone number or date.
+    ///
+    /// Literals are inserted automatically as the user types, characters
+    /// that don't fit the next slot are rejected, and unfilled slots show a
+    /// placeholder character. Use [`raw_value`](Self::raw_value) to read the
+    /// entered characters without the literals.
+    ///
+    /// If no placeholder is set, the mask template (e.g. `(___) ___-____`)
+    /// is shown while the input is empty.
+    ///
+    /// # Arguments
+    ///
+    /// * `mask` - The mask, see [`InputMask`] for the pattern syntax
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use fluix::{InputMask, TextInput};
+    /// use gpui::*;
+    ///
+    /// # fn example(cx: &mut Context<TextInput>) {
+    /// let phone = TextInput::new(cx).mask(InputMask::phone());
+    ///
+    /// // Vehicle plate: three letters, a dash, four digits
+    /// let plate = TextInput::new(cx).mask(InputMask::new("aaa-9999"));
+    /// # }
+    /// ```
+    pub fn mask(self, mask: InputMask) -> Self {
+        self.formatter(mask)
+    }
+
+    /// Reformat the value live while the user types.
+    ///
+    /// The formatter runs after every edit and keeps the cursor next to the
+    /// character that was just typed or deleted. [`get_value`](Self::get_value)
+    /// and `Change` events carry the formatted text;
+    /// [`raw_value`](Self::raw_value) returns the formatter's unformatted value.
+    ///
+    /// # Arguments
+    ///
+    /// * `formatter` - Any [`InputFormatter`], e.g. [`Ipv4Formatter`](super::Ipv4Formatter)
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use fluix::{Ipv4Formatter, TextInput};
+    /// use gpui::*;
+    ///
+    /// # fn example(cx: &mut Context<TextInput>) {
+    /// let address = TextInput::new(cx)
+    ///     .formatter(Ipv4Formatter)
+    ///     .placeholder("0.0.0.0");
+    /// # }
+    /// ```
+    pub fn formatter(mut self, formatter: impl InputFormatter) -> Self {
+        self.buffer.set_formatter(Some(Arc::new(formatter)));
         self
     }
 
@@ -991,7 +1080,34 @@ impl TextInput {
     /// # }
     /// ```
     pub fn get_value(&self) -> &str {
-        &self.value
+        self.buffer.text()
+    }
+
+    /// Get the value without formatting.
+    ///
+    /// With a [`mask`](Self::mask) or [`formatter`](Self::formatter) this is
+    /// the unformatted value, e.g. `5551234567` for `(555) 123-4567`.
+    /// Otherwise it's the same as [`get_value`](Self::get_value).
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use fluix::{InputMask, TextInput};
+    /// use gpui::*;
+    ///
+    /// # fn example(cx: &mut Context<TextInput>) {
+    /// let input = cx.new(|cx| {
+    ///     TextInput::new(cx)
+    ///         .mask(InputMask::date())
+    ///         .value("20241231")
+    /// });
+    ///
+    /// assert_eq!(input.read(cx).get_value(), "2024-12-31");
+    /// assert_eq!(input.read(cx).raw_value(), "20241231");
+    /// # }
+    /// ```
+    pub fn raw_value(&self) -> String {
+        self.buffer.raw_text()
     }
 
     /// Set the value programmatically.
@@ -1045,24 +1161,10 @@ impl TextInput {
     /// # }
     /// ```
     pub fn set_value(&mut self, value: String, cx: &mut Context<Self>) {
-        if let Some(ref validator) = self.validator {
-            if !validator(&value) {
-                return;
-            }
-        }
-
-        if let Some(max_len) = self.max_length {
-            if value.len() > max_len {
-                return;
-            }
+        if self.buffer.set_text(value) {
+            self.emit_change(cx);
+            cx.notify();
         }
-
-        self.value = value.clone();
-        self.cursor_position = self.value.len();
-        self.selection_start = None;
-        self.selection_end = None;
-        cx.emit(TextInputEvent::Change(value));
-        cx.notify();
     }
 
     /// Clear all text from the input.
@@ -1097,11 +1199,8 @@ impl TextInput {
     /// # }
     /// ```
     pub fn clear(&mut self, cx: &mut Context<Self>) {
-        self.value.clear();
-        self.cursor_position = 0;
-        self.selection_start = None;
-        self.selection_end = None;
-        cx.emit(TextInputEvent::Change(String::new()));
+        self.buffer.clear();
+        self.emit_change(cx);
         cx.notify();
     }
 
@@ -1182,7 +1281,7 @@ impl TextInput {
     /// # Behavior
     ///
     /// * If the input is empty, this method has no effect
-    /// * Sets `selection_start` to 0 and `selection_end` to the end
+    /// * Selects from the start to the end of the value
     /// * Triggers a re-render to show the selection highlight
     ///
     /// # Examples
@@ -1206,22 +1305,92 @@ impl TextInput {
     /// # }
     /// ```
     pub fn select_all(&mut self, cx: &mut Context<Self>) {
-        if !self.value.is_empty() {
-            self.selection_start = Some(0);
-            self.selection_end = Some(self.value.len());
+        if !self.buffer.text().is_empty() {
+            self.buffer.select_all();
             cx.notify();
         }
     }
 
-    /// Clear selection
-    fn clear_selection(&mut self) {
-        self.selection_start = None;
-        self.selection_end = None;
+    /// Undo the last edit.
+    ///
+    /// Consecutive typing is grouped into a single undo step, while paste,
+    /// cut and deleting a selection are each their own step. The cursor and
+    /// selection are restored to where they were before the edit.
+    /// Bound to Cmd/Ctrl+Z.
+    ///
+    /// # Arguments
+    ///
+    /// * `cx` - The component context
+    ///
+    /// # Examples
+    ///
+    /// ```rust,no_run
+    /// use fluix::TextInput;
+    /// use gpui::*;
+    ///
+    /// # fn example(cx: &mut Context<TextInput>) {
+    /// let input = cx.new(|cx| TextInput::new(cx));
+    ///
+    /// input.update(cx, |input, cx| {
+    ///     input.set_value("Hello".to_string(), cx);
+    ///     input.undo(cx);
+    /// });
+    ///
+    /// assert_eq!(input.read(cx).get_value(), "");
+    /// # }
+    /// ```
+    pub fn undo(&mut self, cx: &mut Context<Self>) {
+        let changed = self.buffer.undo();
+        self.after_edit(changed, cx);
+    }
+
+    /// Redo the last undone edit.
+    ///
+    /// Bound to Cmd/Ctrl+Shift+Z, and Ctrl+Y outside macOS.
+    ///
+    /// # Arguments
+    ///
+    /// * `cx` - The component context
+    pub fn redo(&mut self, cx: &mut Context<Self>) {
+        let changed = self.buffer.redo();
+        self.after_edit(changed, cx);
+    }
+
+    /// Whether there is an edit to undo
+    pub fn can_undo(&self) -> bool {
+        self.buffer.can_undo()
+    }
+
+    /// Whether there is an undone edit to redo
+    pub fn can_redo(&self) -> bool {
+        self.buffer.can_redo()
+    }
+
+    /// Show the cursor and emit `Change` if an edit changed the value
+    fn after_edit(&mut self, changed: bool, cx: &mut Context<Self>) {
+        if changed {
+            self.pause_blinking(cx);
+            self.emit_change(cx);
+        }
+        cx.notify();
+    }
+
+    /// Emit `Change` and validate the new value
+    fn emit_change(&mut self, cx: &mut Context<Self>) {
+        let text = self.buffer.text().to_string();
+        self.validate_for(ValidateOn::Change, cx);
+        cx.emit(TextInputEvent::Change(text));
+    }
+
+    fn handle_blur(&mut self, cx: &mut Context<Self>) {
+        self.validate_for(ValidateOn::Blur, cx);
+        cx.emit(TextInputEvent::Blur);
+        cx.notify();
     }
 
     /// Check if there is an active selection
     fn has_selection(&self) -> bool {
-        self.selection_start.is_some() && self.selection_end.is_some()
+        self.buffer.has_selection()
     }
 
     /// Increment blink epoch (used to cancel old blink tasks)
@@ -1273,12 +1442,13 @@ impl TextInput {
 
     /// Calculate character index from mouse position
     fn index_for_mouse_position(&self, position: Point<Pixels>) -> usize {
-        if self.value.is_empty() {
+        let value = self.buffer.text();
+        if value.is_empty() {
             return 0;
         }
 
         let Some(layout) = &self.last_layout else {
-            return self.value.len();
+            return value.len();
         };
 
         // Check if click is within bounds
@@ -1286,7 +1456,7 @@ impl TextInput {
             return 0;
         }
         if position.y > layout.bounds.bottom() {
-            return self.value.len();
+            return value.len();
         }
 
         // Calculate relative x position
@@ -1296,25 +1466,25 @@ impl TextInput {
         let byte_index = layout.shaped_line.closest_index_for_x(relative_x);
         
         // For password mode, we need to convert from display text index to actual value index
-        if self.is_password && !self.password_visible {
+        let index = if self.is_password && !self.password_visible {
             match self.password_mask_mode {
                 PasswordMaskMode::All => {
                     // Each bullet is 3 bytes, each character in value is variable bytes
                     let bullet_len = "•".len(); // 3 bytes
                     let char_index = byte_index / bullet_len;
                     // Convert character index to byte index in actual value
-                    self.value.char_indices().nth(char_index).map(|(i, _)| i).unwrap_or(self.value.len())
+                    value.char_indices().nth(char_index).map(|(i, _)| i).unwrap_or(value.len())
                 }
                 PasswordMaskMode::Partial { prefix_len, suffix_len } => {
                     // For partial mode, map display byte position to value byte position
-                    let chars: Vec<char> = self.value.chars().collect();
+                    let chars: Vec<char> = value.chars().collect();
                     let len = chars.len();
                     
                     if len <= prefix_len + suffix_len {
                         // Too short, treat as all masked
                         let bullet_len = "•".len();
                         let char_index = byte_index / bullet_len;
-                        self.value.char_indices().nth(char_index).map(|(i, _)| i).unwrap_or(self.value.len())
+                        value.char_indices().nth(char_index).map(|(i, _)| i).unwrap_or(value.len())
                     } else {
                         // Map display byte position to value byte position
                         let mut display_pos = 0;
@@ -1348,65 +1518,41 @@ impl TextInput {
                             }
                         }
                         
-                        value_pos.min(self.value.len())
+                        value_pos.min(value.len())
                     }
                 }
             }
         } else {
-            byte_index.min(self.value.len())
-        }
+            byte_index.min(value.len())
+        };
+
+        // Never place the cursor inside a grapheme cluster
+        floor_grapheme_boundary(value, index)
     }
 
     /// Build TextRun array for rendering with selection support
-    fn build_text_runs(&self, font: Font, _font_size: Pixels) -> (String, Vec<TextRun>) {
-        let display_text = if self.is_password && !self.password_visible {
-            self.build_password_display_text()
-        } else {
-            self.value.clone()
-        };
+    fn build_text_runs(&self, font: Font, _font_size: Pixels, colors: &ColorPalette) -> (String, Vec<TextRun>) {
+        let value = self.buffer.text();
+        let display_text = self.render_display_text();
 
-        if !self.has_selection() {
+        let Some(selection) = self.buffer.selection() else {
             // No selection: single text run
             return (
                 display_text.clone(),
                 vec![TextRun {
                     len: display_text.len(),
                     font,
-                    color: rgb(0x333333).into(),
+                    color: colors.text.into(),
                     background_color: None,
                     underline: None,
                     strikethrough: None,
                 }],
             );
-        }
+        };
 
         // Has selection: build three text runs
         // IMPORTANT: Convert byte indices to character indices for password mode
-        let (mut sel_start, mut sel_end) = if let (Some(start), Some(end)) =
-            (self.selection_start, self.selection_end)
-        {
-            if start <= end {
-                (start, end)
-            } else {
-                (end, start)
-            }
-        } else {
-            (0, 0)
-        };
-
-        // Ensure indices are within bounds and on character boundaries
-        sel_start = sel_start.min(self.value.len());
-        sel_end = sel_end.min(self.value.len());
-        
-        while sel_start > 0 && !self.value.is_char_boundary(sel_start) {
-            sel_start -= 1;
-        }
-        while sel_end > 0 && sel_end < self.value.len() && !self.value.is_char_boundary(sel_end) {
-            sel_end += 1;
-        }
-        if sel_end > self.value.len() {
-            sel_end = self.value.len();
-        }
+        let (sel_start, sel_end) = (selection.start, selection.end);
 
         // For password mode, we need to calculate positions in the display text
         // Each character in value becomes one bullet point (•) in display_text
@@ -1414,21 +1560,21 @@ impl TextInput {
             match self.password_mask_mode {
                 PasswordMaskMode::All => {
                     // Count characters, not bytes
-                    let char_start = self.value[..sel_start].chars().count();
-                    let char_end = self.value[..sel_end].chars().count();
+                    let char_start = value[..sel_start].chars().count();
+                    let char_end = value[..sel_end].chars().count();
                     // Each bullet point is "•".len() = 3 bytes
                     let bullet_len = "•".len();
                     (char_start * bullet_len, char_end * bullet_len)
                 }
                 PasswordMaskMode::Partial { prefix_len, suffix_len } => {
                     // For partial mode, we need to map byte positions to display positions
-                    let chars: Vec<char> = self.value.chars().collect();
+                    let chars: Vec<char> = value.chars().collect();
                     let len = chars.len();
                     
                     if len <= prefix_len + suffix_len {
                         // Too short, treat as all masked
-                        let char_start = self.value[..sel_start].chars().count();
-                        let char_end = self.value[..sel_end].chars().count();
+                        let char_start = value[..sel_start].chars().count();
+                        let char_end = value[..sel_end].chars().count();
                         let bullet_len = "•".len();
                         (char_start * bullet_len, char_end * bullet_len)
                     } else {
@@ -1437,8 +1583,8 @@ impl TextInput {
                         let mut display_end = 0;
                         
                         // Convert byte positions to character indices
-                        let sel_start_chars = self.value[..sel_start.min(self.value.len())].chars().count();
-                        let sel_end_chars = self.value[..sel_end.min(self.value.len())].chars().count();
+                        let sel_start_chars = value[..sel_start.min(value.len())].chars().count();
+                        let sel_end_chars = value[..sel_end.min(value.len())].chars().count();
                         
                         for (char_idx, ch) in chars.iter().enumerate() {
                             if char_idx < sel_start_chars {
@@ -1479,7 +1625,7 @@ impl TextInput {
             runs.push(TextRun {
                 len: display_sel_start,
                 font: font.clone(),
-                color: rgb(0x333333).into(),
+                color: colors.text.into(),
                 background_color: None,
                 underline: None,
                 strikethrough: None,
@@ -1491,8 +1637,8 @@ impl TextInput {
             runs.push(TextRun {
                 len: display_sel_end - display_sel_start,
                 font: font.clone(),
-                color: rgb(0xFFFFFF).into(),
-                background_color: Some(rgb(0x4A90E2).into()),
+                color: colors.selection_text.into(),
+                background_color: Some(colors.selection.into()),
                 underline: None,
                 strikethrough: None,
             });
@@ -1503,7 +1649,7 @@ impl TextInput {
             runs.push(TextRun {
                 len: display_text.len() - display_sel_end,
                 font: font.clone(),
-                color: rgb(0x333333).into(),
+                color: colors.text.into(),
                 background_color: None,
                 underline: None,
                 strikethrough: None,
@@ -1513,276 +1659,107 @@ impl TextInput {
         (display_text, runs)
     }
 
-    /// Delete selected text and return the new cursor position
-    fn delete_selection(&mut self) -> usize {
-        if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
-            let (mut sel_start, mut sel_end) = if start <= end {
-                (start, end)
-            } else {
-                (end, start)
-            };
-
-            // Ensure indices are within bounds
-            sel_start = sel_start.min(self.value.len());
-            sel_end = sel_end.min(self.value.len());
-
-            // Ensure indices are on character boundaries
-            while sel_start > 0 && !self.value.is_char_boundary(sel_start) {
-                sel_start -= 1;
-            }
-            while sel_end > 0 && sel_end < self.value.len() && !self.value.is_char_boundary(sel_end) {
-                sel_end += 1;
-            }
-
-            if sel_start < sel_end && sel_end <= self.value.len() {
-                let before = self.value[..sel_start].to_string();
-                let after = self.value[sel_end..].to_string();
-                self.value = format!("{}{}", before, after);
-            }
-            self.clear_selection();
-            sel_start
-        } else {
-            self.cursor_position
-        }
-    }
-
     fn handle_backspace(&mut self, cx: &mut Context<Self>) {
         if self.disabled {
             return;
         }
-
-        // If there's marked text (IME composition), clear it
-        if self.marked_range.is_some() {
-            self.marked_range = None;
-            cx.notify();
-            return;
-        }
-
-        // If there's a selection, delete it
-        if self.has_selection() {
-            self.cursor_position = self.delete_selection();
-            self.pause_blinking(cx);
-            cx.emit(TextInputEvent::Change(self.value.clone()));
-            cx.notify();
-            return;
-        }
-
-        if self.cursor_position == 0 {
-            return;
-        }
-
-        // Ensure cursor position is within bounds and on a character boundary
-        if self.cursor_position > self.value.len() {
-            self.cursor_position = self.value.len();
-        }
-        if !self.value.is_char_boundary(self.cursor_position) {
-            // Find the nearest valid boundary
-            while self.cursor_position > 0 && !self.value.is_char_boundary(self.cursor_position) {
-                self.cursor_position -= 1;
-            }
-        }
-
-        // Find the previous character boundary (handles multi-byte characters)
-        let mut prev_pos = self.cursor_position;
-        while prev_pos > 0 {
-            prev_pos -= 1;
-            if self.value.is_char_boundary(prev_pos) {
-                break;
-            }
-        }
-
-        // Remove character before cursor
-        let mut new_value = String::new();
-        new_value.push_str(&self.value[..prev_pos]);
-        if self.cursor_position <= self.value.len() {
-            new_value.push_str(&self.value[self.cursor_position..]);
-        }
-
-        self.value = new_value.clone();
-        self.cursor_position = prev_pos;
-        self.pause_blinking(cx);
-        cx.emit(TextInputEvent::Change(new_value));
-        cx.notify();
+        let changed = self.buffer.backspace();
+        self.after_edit(changed, cx);
     }
 
     fn handle_delete(&mut self, cx: &mut Context<Self>) {
         if self.disabled {
             return;
         }
+        let changed = self.buffer.delete();
+        self.after_edit(changed, cx);
+    }
 
-        // If there's marked text (IME composition), clear it
-        if self.marked_range.is_some() {
-            self.marked_range = None;
-            cx.notify();
-            return;
-        }
-
-        // If there's a selection, delete it
-        if self.has_selection() {
-            self.cursor_position = self.delete_selection();
-            self.pause_blinking(cx);
-            cx.emit(TextInputEvent::Change(self.value.clone()));
-            cx.notify();
-            return;
-        }
+    fn move_cursor_left(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
+        self.buffer.move_left(extend_selection);
+        self.pause_blinking(cx);
+        cx.notify();
+    }
 
-        // Ensure cursor position is within bounds and on a character boundary
-        if self.cursor_position > self.value.len() {
-            self.cursor_position = self.value.len();
-        }
-        if self.cursor_position >= self.value.len() {
-            return;
-        }
-        if !self.value.is_char_boundary(self.cursor_position) {
-            // Find the nearest valid boundary
-            while self.cursor_position > 0 && !self.value.is_char_boundary(self.cursor_position) {
-                self.cursor_position -= 1;
-            }
-        }
+    fn move_cursor_right(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
+        self.buffer.move_right(extend_selection);
+        self.pause_blinking(cx);
+        cx.notify();
+    }
 
-        // Find the next character boundary (handles multi-byte characters)
-        let mut next_pos = self.cursor_position + 1;
-        while next_pos < self.value.len() && !self.value.is_char_boundary(next_pos) {
-            next_pos += 1;
-        }
+    fn move_cursor_home(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
+        self.move_cursor_to(0, extend_selection, cx);
+    }
 
-        // Remove character at cursor
-        let mut new_value = String::new();
-        if self.cursor_position <= self.value.len() {
-            new_value.push_str(&self.value[..self.cursor_position]);
-        }
-        if next_pos <= self.value.len() {
-            new_value.push_str(&self.value[next_pos..]);
-        }
+    fn move_cursor_end(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
+        self.move_cursor_to(self.buffer.text().len(), extend_selection, cx);
+    }
 
-        self.value = new_value.clone();
+    /// Move the cursor to `pos`, extending the selection from its anchor if requested
+    fn move_cursor_to(&mut self, pos: usize, extend_selection: bool, cx: &mut Context<Self>) {
+        self.buffer.move_to(pos, extend_selection);
         self.pause_blinking(cx);
-        cx.emit(TextInputEvent::Change(new_value));
         cx.notify();
     }
 
-    /// Extend or start selection to the cursor position
-    fn extend_selection_to(&mut self, pos: usize) {
-        if !self.has_selection() {
-            // Start new selection: use the previous cursor position as anchor
-            // But since cursor is already moved, we need to track anchor separately
-            // For now, use current position as end, and adjust after cursor move
-            // This is a simplified approach - in a full implementation, we'd track the anchor
-            self.selection_start = Some(pos);
-            self.selection_end = Some(pos);
+    /// Whether the value is masked, in which case word motion treats it as
+    /// a single word so it doesn't reveal where the words are
+    fn is_masked(&self) -> bool {
+        self.is_password && !self.password_visible
+    }
+
+    /// Start of the previous word, or the start of the input when masked
+    fn previous_word_start(&self) -> usize {
+        if self.is_masked() {
+            0
         } else {
-            // Extend existing selection
-            // Determine which end to extend based on which is closer to the new position
-            if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
-                let (sel_start, sel_end) = if start <= end {
-                    (start, end)
-                } else {
-                    (end, start)
-                };
-                
-                // Extend from the end that's closer to the new position
-                if (pos as i32 - sel_start as i32).abs() < (pos as i32 - sel_end as i32).abs() {
-                    // Extend from start
-                    self.selection_start = Some(pos);
-                    self.selection_end = Some(sel_end);
-                } else {
-                    // Extend from end
-                    self.selection_start = Some(sel_start);
-                    self.selection_end = Some(pos);
-                }
-            }
+            previous_word_start(self.buffer.text(), self.buffer.cursor())
         }
     }
 
-    fn move_cursor_left(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
-        if self.cursor_position > 0 {
-            let old_pos = self.cursor_position;
-            
-            // Find the previous character boundary (handles multi-byte characters)
-            let mut new_pos = self.cursor_position;
-            while new_pos > 0 {
-                new_pos -= 1;
-                if self.value.is_char_boundary(new_pos) {
-                    break;
-                }
-            }
-            self.cursor_position = new_pos;
-            
-            if extend_selection {
-                if !self.has_selection() {
-                    // Start new selection: old position is anchor, new position is end
-                    self.selection_start = Some(old_pos);
-                    self.selection_end = Some(self.cursor_position);
-                } else {
-                    self.extend_selection_to(self.cursor_position);
-                }
-            } else {
-                self.clear_selection();
-            }
-            self.pause_blinking(cx);
-            cx.notify();
+    /// End of the next word, or the end of the input when masked
+    fn next_word_end(&self) -> usize {
+        if self.is_masked() {
+            self.buffer.text().len()
+        } else {
+            next_word_end(self.buffer.text(), self.buffer.cursor())
         }
     }
 
-    fn move_cursor_right(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
-        if self.cursor_position < self.value.len() {
-            let old_pos = self.cursor_position;
-            
-            // Find the next character boundary (handles multi-byte characters)
-            let mut new_pos = self.cursor_position + 1;
-            while new_pos < self.value.len() && !self.value.is_char_boundary(new_pos) {
-                new_pos += 1;
-            }
-            self.cursor_position = new_pos;
-            
-            if extend_selection {
-                if !self.has_selection() {
-                    // Start new selection: old position is anchor, new position is end
-                    self.selection_start = Some(old_pos);
-                    self.selection_end = Some(self.cursor_position);
-                } else {
-                    self.extend_selection_to(self.cursor_position);
-                }
-            } else {
-                self.clear_selection();
-            }
-            self.pause_blinking(cx);
-            cx.notify();
-        }
+    fn move_word_left(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
+        let pos = self.previous_word_start();
+        self.move_cursor_to(pos, extend_selection, cx);
     }
 
-    fn move_cursor_home(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
-        let old_pos = self.cursor_position;
-        self.cursor_position = 0;
-        if extend_selection {
-            if !self.has_selection() {
-                self.selection_start = Some(old_pos);
-                self.selection_end = Some(0);
-            } else {
-                self.extend_selection_to(0);
-            }
-        } else {
-            self.clear_selection();
+    fn move_word_right(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
+        let pos = self.next_word_end();
+        self.move_cursor_to(pos, extend_selection, cx);
+    }
+
+    /// Whether word deletion applies, rather than falling back to a plain
+    /// backspace/delete for a selection or IME composition
+    fn deletes_words(&self) -> bool {
+        !self.disabled && self.buffer.marked_range().is_none() && !self.has_selection()
+    }
+
+    fn delete_word_backward(&mut self, cx: &mut Context<Self>) {
+        if !self.deletes_words() {
+            self.handle_backspace(cx);
+            return;
         }
-        self.pause_blinking(cx);
-        cx.notify();
+        let range = self.previous_word_start()..self.buffer.cursor();
+        let changed = self.buffer.delete_range(range);
+        self.after_edit(changed, cx);
     }
 
-    fn move_cursor_end(&mut self, extend_selection: bool, cx: &mut Context<Self>) {
-        let old_pos = self.cursor_position;
-        self.cursor_position = self.value.len();
-        if extend_selection {
-            if !self.has_selection() {
-                self.selection_start = Some(old_pos);
-                self.selection_end = Some(self.value.len());
-            } else {
-                self.extend_selection_to(self.value.len());
-            }
-        } else {
-            self.clear_selection();
+    fn delete_word_forward(&mut self, cx: &mut Context<Self>) {
+        if !self.deletes_words() {
+            self.handle_delete(cx);
+            return;
         }
-        self.pause_blinking(cx);
-        cx.notify();
+        let range = self.buffer.cursor()..self.next_word_end();
+        let changed = self.buffer.delete_range(range);
+        self.after_edit(changed, cx);
     }
 
     fn handle_submit(&mut self, cx: &mut Context<Self>) {
@@ -1790,21 +1767,25 @@ impl TextInput {
             return;
         }
 
-        cx.emit(TextInputEvent::Submit(self.value.clone()));
+        let text = self.buffer.text().to_string();
+        self.validate_for(ValidateOn::Submit, cx);
+        cx.emit(TextInputEvent::Submit(text));
+        cx.notify();
     }
 
     /// Build password display text based on mask mode
     fn build_password_display_text(&self) -> String {
-        if self.value.is_empty() {
+        let value = self.buffer.text();
+        if value.is_empty() {
             return String::new();
         }
 
         match self.password_mask_mode {
             PasswordMaskMode::All => {
-                "•".repeat(self.value.len())
+                "•".repeat(value.chars().count())
             }
             PasswordMaskMode::Partial { prefix_len, suffix_len } => {
-                let chars: Vec<char> = self.value.chars().collect();
+                let chars: Vec<char> = value.chars().collect();
                 let len = chars.len();
                 
                 // If password is too short, just mask all
@@ -1822,72 +1803,53 @@ impl TextInput {
     }
 
     fn render_display_text(&self) -> String {
-        if self.is_password && !self.password_visible && !self.value.is_empty() {
+        if self.is_masked() && !self.buffer.text().is_empty() {
             self.build_password_display_text()
         } else {
-            self.value.clone()
+            self.buffer.text().to_string()
         }
     }
+}
 
-    /// Convert byte range to UTF-16 code unit range
-    fn range_to_utf16(&self, range: &std::ops::Range<usize>) -> std::ops::Range<usize> {
-        let start_idx = range.start.min(self.value.len());
-        let end_idx = range.end.min(self.value.len());
-        
-        let start = self.value[..start_idx].encode_utf16().count();
-        let end = self.value[..end_idx].encode_utf16().count();
-        start..end
+impl EventEmitter<TextInputEvent> for TextInput {}
+
+impl Focusable for TextInput {
+    fn focus_handle(&self, _cx: &App) -> FocusHandle {
+        self.focus_handle.clone()
     }
+}
 
-    /// Convert UTF-16 code unit range to byte range
-    fn range_from_utf16(&self, range_utf16: &std::ops::Range<usize>) -> std::ops::Range<usize> {
-        let mut utf16_count = 0;
-        let mut start = None;
-        let mut end = None;
+impl FormControl for TextInput {
+    fn form_value(&self) -> Value {
+        Value::String(self.buffer.text().to_string())
+    }
 
-        for (byte_idx, ch) in self.value.char_indices() {
-            if start.is_none() && utf16_count >= range_utf16.start {
-                start = Some(byte_idx);
-            }
-            
-            utf16_count += ch.len_utf16();
-            
-            if end.is_none() && utf16_count >= range_utf16.end {
-                end = Some(byte_idx + ch.len_utf8());
-                break;
-            }
-        }
+    fn set_form_value(&mut self, value: &Value, cx: &mut Context<Self>) {
+        self.set_value(value_to_string(value), cx);
+    }
 
-        let start = start.unwrap_or(self.value.len());
-        let end = end.unwrap_or(self.value.len());
-        
-        start..end
+    fn validate_form(&mut self, cx: &mut Context<Self>) -> ValidationState {
+        self.run_validation(cx);
+        self.validation.state().clone()
     }
 
-    /// Get current selection as a range
-    fn selected_range(&self) -> std::ops::Range<usize> {
-        if let (Some(start), Some(end)) = (self.selection_start, self.selection_end) {
-            if start <= end {
-                start..end
-            } else {
-                end..start
-            }
-        } else {
-            self.cursor_position..self.cursor_position
-        }
+    fn form_validation_state(&self) -> ValidationState {
+        self.validation.state().clone()
     }
-}
 
-impl EventEmitter<TextInputEvent> for TextInput {}
+    fn clear_form_error(&mut self, cx: &mut Context<Self>) {
+        self.set_error(None, cx);
+    }
 
-impl Focusable for TextInput {
-    fn focus_handle(&self, _cx: &App) -> FocusHandle {
-        self.focus_handle.clone()
+    fn form_focus_handle(&self, _cx: &App) -> Option<FocusHandle> {
+        Some(self.focus_handle.clone())
     }
 }
 
 impl Render for TextInput {
     fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let theme = cx.theme().clone();
+        let font_size = theme.font_size(ComponentSize::Medium);
         let is_focused = self.focus_handle.is_focused(window);
         
         // Start blinking when focused
@@ -1897,17 +1859,30 @@ impl Render for TextInput {
         }
         
         let display_text = self.render_display_text();
-        let show_placeholder = self.value.is_empty();
+        let show_placeholder = self.buffer.text().is_empty();
         let disabled = self.disabled;
-        let placeholder = self.placeholder.clone();
+        // Fall back to the mask template, e.g. "(___) ___-____"
+        let placeholder = match self.buffer.formatter() {
+            Some(formatter) if self.placeholder.is_empty() => {
+                formatter.placeholder().unwrap_or_default()
+            }
+            _ => self.placeholder.clone(),
+        };
+        
+        if self._blur_subscription.is_none() {
+            self._blur_subscription = Some(cx.on_blur(&self.focus_handle, window, |this, _window, cx| {
+                this.handle_blur(cx);
+            }));
+        }
+        let error = self.validation.error().map(str::to_string);
         
         // Create a shared container for layout info that will be filled during paint
         let layout_container: Arc<Mutex<Option<TextInputLayout>>> = Arc::new(Mutex::new(None));
 
-        div()
+        let field = div()
             .id("text-input")
             .track_focus(&self.focus_handle)
-            .on_key_down(cx.listener(|this, event: &KeyDownEvent, window, cx| {
+            .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
                 if this.disabled {
                     return;
                 }
@@ -1927,22 +1902,39 @@ impl Render for TextInput {
                     return;
                 }
 
+                // Check for Cmd/Ctrl + Z (Undo) and Cmd/Ctrl + Shift + Z (Redo)
+                if is_cmd_or_ctrl && event.keystroke.key.as_str().eq_ignore_ascii_case("z") {
+                    if modifiers.shift {
+                        this.redo(cx);
+                    } else {
+                        this.undo(cx);
+                    }
+                    return;
+                }
+
+                // Check for Ctrl + Y (Redo) outside macOS
+                if !cfg!(target_os = "macos")
+                    && is_cmd_or_ctrl
+                    && event.keystroke.key.as_str().eq_ignore_ascii_case("y")
+                {
+                    this.redo(cx);
+                    return;
+                }
+
                 // Check for Cmd/Ctrl + C (Copy)
                 // Only allow copy for non-password inputs
                 if is_cmd_or_ctrl && event.keystroke.key.as_str().eq_ignore_ascii_case("c") {
                     if !this.is_password && !this.disabled {
                         if this.has_selection() {
                             // Copy selected text
-                            let range = this.selected_range();
-                            let text_to_copy = this.value[range].to_string();
                             if let Ok(mut clipboard) = Clipboard::new() {
-                                let _ = clipboard.set_text(&text_to_copy);
+                                let _ = clipboard.set_text(this.buffer.selected_text());
                             }
                         } else {
                             // No selection - copy all text
-                            if !this.value.is_empty() {
+                            if !this.buffer.text().is_empty() {
                                 if let Ok(mut clipboard) = Clipboard::new() {
-                                    let _ = clipboard.set_text(&this.value);
+                                    let _ = clipboard.set_text(this.buffer.text());
                                 }
                             }
                         }
@@ -1956,11 +1948,9 @@ impl Render for TextInput {
                     if !this.disabled {
                         if let Ok(mut clipboard) = Clipboard::new() {
                             if let Ok(clipboard_text) = clipboard.get_text() {
-                                // Use replace_text_in_range to insert the text
-                                // This will handle validation and max_length checks
-                                let range = this.selected_range();
-                                let range_utf16 = Some(this.range_to_utf16(&range));
-                                this.replace_text_in_range(range_utf16, &clipboard_text, window, cx);
+                                // Paste is its own undo step, truncated to max_length
+                                let changed = this.buffer.paste(&clipboard_text);
+                                this.after_edit(changed, cx);
                             }
                         }
                     }
@@ -1970,26 +1960,40 @@ impl Render for TextInput {
                 // Check for Cmd/Ctrl + X (Cut)
                 // Only allow cut for non-password inputs
                 if is_cmd_or_ctrl && event.keystroke.key.as_str().eq_ignore_ascii_case("x") {
-                    if !this.is_password && !this.disabled && this.has_selection() {
-                        let range = this.selected_range();
-                        let text_to_copy = this.value[range].to_string();
-                        if let Ok(mut clipboard) = Clipboard::new() {
-                            let _ = clipboard.set_text(&text_to_copy);
+                    if !this.is_password && !this.disabled {
+                        if let Some(text) = this.buffer.cut() {
+                            if let Ok(mut clipboard) = Clipboard::new() {
+                                let _ = clipboard.set_text(&text);
+                            }
+                            this.after_edit(true, cx);
                         }
-                        // Delete the selected text
-                        this.cursor_position = this.delete_selection();
-                        this.pause_blinking(cx);
-                        cx.emit(TextInputEvent::Change(this.value.clone()));
-                        cx.notify();
                     }
                     return;
                 }
 
                 // Check for Shift key to extend selection
                 let shift_pressed = modifiers.shift;
+                // Word motion uses Alt on macOS and Ctrl elsewhere
+                let word_modifier = if cfg!(target_os = "macos") {
+                    modifiers.alt
+                } else {
+                    modifiers.control
+                };
 
                 // Handle special keys
                 match event.keystroke.key.as_str() {
+                    "backspace" if word_modifier => {
+                        this.delete_word_backward(cx);
+                    }
+                    "delete" if word_modifier => {
+                        this.delete_word_forward(cx);
+                    }
+                    "left" if word_modifier => {
+                        this.move_word_left(shift_pressed, cx);
+                    }
+                    "right" if word_modifier => {
+                        this.move_word_right(shift_pressed, cx);
+                    }
                     "backspace" => {
                         this.handle_backspace(cx);
                     }
@@ -2028,27 +2032,26 @@ impl Render for TextInput {
                         this.last_layout = layout.clone();
                     }
                     
-                    // Start dragging
-                    this.is_dragging = true;
-                    
                     // Calculate click position
                     let index = this.index_for_mouse_position(event.position);
-                    
-                    if event.modifiers.shift {
-                        // Shift+click extends selection
-                        if !this.has_selection() {
-                            this.selection_start = Some(this.cursor_position);
-                            this.selection_end = Some(index);
-                        } else {
-                            // Extend existing selection
-                            this.selection_end = Some(index);
-                        }
-                    } else {
-                        // Normal click - clear selection and set cursor
-                        this.clear_selection();
+
+                    // Triple-click selects everything, double-click selects a word
+                    if event.click_count >= 3 || (event.click_count == 2 && this.is_masked()) {
+                        this.buffer.select_all();
+                        this.pause_blinking(cx);
+                        return;
+                    }
+                    if event.click_count == 2 {
+                        this.buffer.select_word_at(index);
+                        this.pause_blinking(cx);
+                        return;
                     }
+
+                    // Start dragging
+                    this.is_dragging = true;
                     
-                    this.cursor_position = index;
+                    // Shift+click extends the selection, a normal click clears it
+                    this.buffer.move_to(index, event.modifiers.shift);
                     this.pause_blinking(cx);
                     cx.notify();
                 })
@@ -2061,45 +2064,39 @@ impl Render for TextInput {
                 if this.is_dragging {
                     // Update selection while dragging
                     let index = this.index_for_mouse_position(event.position);
-                    
-                    if !this.has_selection() {
-                        // Start new selection from cursor position
-                        this.selection_start = Some(this.cursor_position);
-                        this.selection_end = Some(index);
-                    } else {
-                        // Update selection end
-                        this.selection_end = Some(index);
-                    }
-                    
-                    this.cursor_position = index;
+                    this.buffer.move_to(index, true);
                     cx.notify();
                 }
             }))
             .flex()
             .items_center()
             .w_full()
-            .h(px(36.))
-            .pl(px(12.)) // Left padding: 12px
+            .h(theme.height(ComponentSize::Medium))
+            .pl(px(theme.spacing.md))
             .when_some(self.custom_right_padding, |this, padding| {
                 this.pr(px(padding))
             })
             .when(self.custom_right_padding.is_none(), |this| {
-                this.pr(px(12.)) // Default right padding: 12px
+                this.pr(px(theme.spacing.md))
             })
             .bg(self.custom_bg_color.unwrap_or(if disabled {
-                rgb(0xF5F5F5)
+                theme.colors.background_secondary
             } else {
-                rgb(0xFFFFFF)
+                theme.colors.background
             }))
             .when(self.show_border, |this| {
                 this.border_1()
-                    .border_color(self.custom_border_color.unwrap_or(if is_focused {
-                        rgb(0x696FC7)
+                    .border_color(if error.is_some() {
+                        theme.colors.border_error
                     } else {
-                        rgb(0xE0E0E0)
-                    }))
+                        self.custom_border_color.unwrap_or(if is_focused {
+                            theme.colors.border_focus
+                        } else {
+                            theme.colors.border
+                        })
+                    })
             })
-            .rounded(px(6.))
+            .rounded(px(theme.radius.md))
             .when(!disabled, |this| {
                 this.cursor(CursorStyle::IBeam)
             })
@@ -2112,11 +2109,11 @@ impl Render for TextInput {
                     .items_center()
                     .flex_1()
                     .min_w(px(0.))  // Allow shrinking to prevent content from expanding
-                    .text_sm()
+                    .text_size(font_size)
                     .when(show_placeholder, |this| {
                         // Show placeholder with cursor when focused
                         if is_focused && !disabled && !self.has_selection() && self.cursor_visible {
-                            this.text_color(rgb(0x999999))
+                            this.text_color(theme.colors.text_disabled)
                                 .relative()
                                 .child(placeholder)
                                 .child(
@@ -2126,39 +2123,35 @@ impl Render for TextInput {
                                         .left(px(0.))
                                         .top(px(2.))  // Adjusted to match input cursor position
                                         .w(px(2.))
-                                        .h(px(18.))
-                                        .bg(rgb(0x000000))  // Black cursor
+                                        .h(px(theme.typography.line_height_tight))
+                                        .bg(theme.colors.text)
                                 )
                         } else {
-                            this.text_color(rgb(0x999999))
+                            this.text_color(theme.colors.text_disabled)
                                 .child(placeholder)
                         }
                     })
                     .when(!show_placeholder && !is_focused, |this| {
                         // Not focused: show full text
                         this.text_color(if disabled {
-                            rgb(0x999999)
+                            theme.colors.text_disabled
                         } else {
-                            rgb(0x333333)
+                            theme.colors.text
                         })
                         .child(display_text.clone())
                     })
                     .when(!show_placeholder && is_focused && !disabled, |this| {
                         // Focused: show text with cursor and selection using TextRun API
-                        let cursor_pos = self.cursor_position;
+                        let cursor_pos = self.buffer.cursor();
+                        let value = self.buffer.text();
                         
-                        // Get font from settings (you may need to adjust this)
-                        let font = gpui::Font {
-                            family: ".SystemUIFont".into(),
-                            features: Default::default(),
-                            weight: Default::default(),
-                            style: Default::default(),
-                            fallbacks: None,
-                        };
-                        let font_size = px(14.);
+                        // Get font from the theme
+                        let font = theme.typography.font();
+                        let line_height = px(theme.typography.line_height_tight);
                         
                         // Build TextRun array with selection support
-                        let (display_text, text_runs) = self.build_text_runs(font.clone(), font_size);
+                        let (display_text, text_runs) = self.build_text_runs(font.clone(), font_size, &theme.colors);
+                        let text_color = theme.colors.text;
                         let display_text_for_cursor = display_text.clone();  // Clone for cursor calculation
                         let has_selection = self.has_selection();
                         let cursor_visible = self.cursor_visible;
@@ -2171,23 +2164,23 @@ impl Render for TextInput {
                             match self.password_mask_mode {
                                 PasswordMaskMode::All => {
                                     // Count characters, not bytes
-                                    let char_count = self.value[..cursor_pos.min(self.value.len())].chars().count();
+                                    let char_count = value[..cursor_pos.min(value.len())].chars().count();
                                     char_count * "•".len()
                                 }
                                 PasswordMaskMode::Partial { prefix_len, suffix_len } => {
-                                    let chars: Vec<char> = self.value.chars().collect();
+                                    let chars: Vec<char> = value.chars().collect();
                                     let len = chars.len();
                                     
                                     if len <= prefix_len + suffix_len {
                                         // Too short, treat as all masked
-                                        let char_count = self.value[..cursor_pos.min(self.value.len())].chars().count();
+                                        let char_count = value[..cursor_pos.min(value.len())].chars().count();
                                         char_count * "•".len()
                                     } else {
                                         // Calculate display position based on prefix/suffix
                                         let mut display_pos = 0;
                                         
                                         for (i, ch) in chars.iter().enumerate() {
-                                            let value_byte_pos = self.value[..i].len() + ch.len_utf8();
+                                            let value_byte_pos = value[..i].len() + ch.len_utf8();
                                             if value_byte_pos > cursor_pos {
                                                 break;
                                             }
@@ -2229,10 +2222,9 @@ impl Render for TextInput {
                                     canvas(
                                         move |bounds, _, _cx| {
                                             // Return the size for layout - match line height
-                                            gpui::size(bounds.size.width, px(18.))
+                                            gpui::size(bounds.size.width, line_height)
                                         },
                                         move |bounds, _, window, _cx| {
-                                            let line_height = px(18.);
                                             
                                             // Only shape and paint if there's text
                                             if !display_text.is_empty() {
@@ -2267,7 +2259,7 @@ impl Render for TextInput {
                                         },
                                     )
                                     .w_full()
-                                    .h(px(18.))
+                                    .h(line_height)
                                 )
                                 // Show cursor using canvas to get accurate position
                                 .child(
@@ -2295,7 +2287,7 @@ impl Render for TextInput {
                                                         let temp_runs = vec![TextRun {
                                                             len: text_before.len(),
                                                             font: font.clone(),
-                                                            color: rgb(0x333333).into(),
+                                                            color: text_color.into(),
                                                             background_color: None,
                                                             underline: None,
                                                             strikethrough: None,
@@ -2313,9 +2305,9 @@ impl Render for TextInput {
                                                 // Draw cursor - shift down 1px to align with text
                                                 let cursor_bounds = gpui::Bounds {
                                                     origin: bounds.origin + gpui::point(cursor_x, px(1.)),
-                                                    size: gpui::size(px(2.), px(18.)),
+                                                    size: gpui::size(px(2.), line_height),
                                                 };
-                                                window.paint_quad(gpui::fill(cursor_bounds, rgb(0x000000)));
+                                                window.paint_quad(gpui::fill(cursor_bounds, text_color));
                                             }
                                         },
                                     )
@@ -2323,11 +2315,20 @@ impl Render for TextInput {
                                     .top(px(0.))
                                     .left(px(0.))
                                     .w(px(0.))
-                                    .h(px(18.))
+                                    .h(line_height)
                                 )
                         )
                     })
-            )
+            );
+
+        div()
+            .flex()
+            .flex_col()
+            .w_full()
+            .child(field)
+            .when_some(error, |this, message| {
+                this.child(render_error_message(&message, &theme))
+            })
     }
 }
 
@@ -2340,9 +2341,9 @@ impl EntityInputHandler for TextInput {
         _window: &mut Window,
         _cx: &mut Context<Self>,
     ) -> Option<String> {
-        let range = self.range_from_utf16(&range_utf16);
-        actual_range.replace(self.range_to_utf16(&range));
-        Some(self.value[range].to_string())
+        let range = self.buffer.range_from_utf16(&range_utf16);
+        actual_range.replace(self.buffer.range_to_utf16(&range));
+        Some(self.buffer.text()[range].to_string())
     }
 
     fn selected_text_range(
@@ -2351,10 +2352,9 @@ impl EntityInputHandler for TextInput {
         _window: &mut Window,
         _cx: &mut Context<Self>,
     ) -> Option<UTF16Selection> {
-        let range = self.selected_range();
         Some(UTF16Selection {
-            range: self.range_to_utf16(&range),
-            reversed: false, // Simplified - could track selection direction
+            range: self.buffer.range_to_utf16(&self.buffer.selected_range()),
+            reversed: self.buffer.is_selection_reversed(),
         })
     }
 
@@ -2363,13 +2363,13 @@ impl EntityInputHandler for TextInput {
         _window: &mut Window,
         _cx: &mut Context<Self>,
     ) -> Option<std::ops::Range<usize>> {
-        self.marked_range
-            .as_ref()
-            .map(|range| self.range_to_utf16(range))
+        self.buffer
+            .marked_range()
+            .map(|range| self.buffer.range_to_utf16(&range))
     }
 
     fn unmark_text(&mut self, _window: &mut Window, _cx: &mut Context<Self>) {
-        self.marked_range = None;
+        self.buffer.unmark_text();
     }
 
     fn replace_text_in_range(
@@ -2382,55 +2382,9 @@ impl EntityInputHandler for TextInput {
         if self.disabled {
             return;
         }
-        let mut range = range_utf16
-            .as_ref()
-            .map(|range_utf16| self.range_from_utf16(range_utf16))
-            .or(self.marked_range.clone())
-            .unwrap_or_else(|| self.selected_range());
-
-        // Ensure range is within bounds and on character boundaries
-        range.start = range.start.min(self.value.len());
-        range.end = range.end.min(self.value.len());
-        
-        while range.start > 0 && !self.value.is_char_boundary(range.start) {
-            range.start -= 1;
-        }
-        while range.end > 0 && range.end < self.value.len() && !self.value.is_char_boundary(range.end) {
-            range.end += 1;
-        }
-        if range.end > self.value.len() {
-            range.end = self.value.len();
-        }
-
-        // Replace text in range
-        let mut new_value = String::new();
-        new_value.push_str(&self.value[..range.start]);
-        new_value.push_str(new_text);
-        new_value.push_str(&self.value[range.end..]);
-
-        // Validate new value
-        if let Some(ref validator) = self.validator {
-            if !validator(&new_value) {
-                return;
-            }
-        }
-
-        // Check max length
-        if let Some(max_len) = self.max_length {
-            if new_value.len() > max_len {
-                return;
-            }
-        }
-
-        // Apply the change
-        self.value = new_value;
-        self.cursor_position = range.start + new_text.len();
-        self.clear_selection();
-        self.marked_range = None;
-        
-        self.pause_blinking(cx);
-        cx.emit(TextInputEvent::Change(self.value.clone()));
-        cx.notify();
+        let range = range_utf16.map(|range_utf16| self.buffer.range_from_utf16(&range_utf16));
+        let changed = self.buffer.replace_text_in_range(range, new_text);
+        self.after_edit(changed, cx);
     }
 
     fn replace_and_mark_text_in_range(
@@ -2441,66 +2395,11 @@ impl EntityInputHandler for TextInput {
         _window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        let mut range = range_utf16
-            .as_ref()
-            .map(|range_utf16| self.range_from_utf16(range_utf16))
-            .or(self.marked_range.clone())
-            .unwrap_or_else(|| self.selected_range());
-
-        // Ensure range is within bounds and on character boundaries
-        range.start = range.start.min(self.value.len());
-        range.end = range.end.min(self.value.len());
-        
-        while range.start > 0 && !self.value.is_char_boundary(range.start) {
-            range.start -= 1;
-        }
-        while range.end > 0 && range.end < self.value.len() && !self.value.is_char_boundary(range.end) {
-            range.end += 1;
-        }
-        if range.end > self.value.len() {
-            range.end = self.value.len();
-        }
-
-        // Replace text in range
-        let mut new_value = String::new();
-        new_value.push_str(&self.value[..range.start]);
-        new_value.push_str(new_text);
-        new_value.push_str(&self.value[range.end..]);
-
-        // Validate new value
-        if let Some(ref validator) = self.validator {
-            if !validator(&new_value) {
-                return;
-            }
-        }
-
-        // Check max length
-        if let Some(max_len) = self.max_length {
-            if new_value.len() > max_len {
-                return;
-            }
-        }
-
-        self.value = new_value;
-
-        // Mark the newly inserted text for IME composition
-        if !new_text.is_empty() {
-            self.marked_range = Some(range.start..range.start + new_text.len());
-        } else {
-            self.marked_range = None;
-        }
-
-        // Update selection
-        if let Some(new_range_utf16) = new_selected_range_utf16 {
-            let new_range = self.range_from_utf16(&new_range_utf16);
-            self.cursor_position = range.start + new_range.end;
-        } else {
-            self.cursor_position = range.start + new_text.len();
+        let range = range_utf16.map(|range_utf16| self.buffer.range_from_utf16(&range_utf16));
+        if self.buffer.replace_and_mark_text_in_range(range, new_text, new_selected_range_utf16) {
+            self.pause_blinking(cx);
+            cx.notify();
         }
-        self.clear_selection();
-
-        self.pause_blinking(cx);
-        cx.notify();
     }
 
     fn bounds_for_range(
@@ -2523,6 +2422,6 @@ impl EntityInputHandler for TextInput {
         // Convert pixel position to character index
         let byte_index = self.index_for_mouse_position(point);
         // Convert byte index to UTF-16 code units
-        Some(self.value[..byte_index].encode_utf16().count())
+        Some(offset_to_utf16(self.buffer.text(), byte_index))
     }
 }
@@ -0,0 +1,446 @@
+// Validation rules, state and error rendering shared by form components
+
+use std::borrow::Borrow;
+use std::future::Future;
+use std::pin::Pin;
+use std::rc::Rc;
+use std::time::Duration;
+
+use gpui::*;
+
+use crate::theme::{ComponentSize, Theme};
+
+/// Typing pauses shorter than this don't start an async validation
+const ASYNC_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Outcome of a validation rule: `Err` carries the message shown to the user
+pub type ValidationResult = Result<(), String>;
+
+/// When a form control runs its validation rules.
+///
+/// Each trigger also covers the later ones: `Change` validates on every
+/// edit, on blur and on submit. Once a control shows an error it is
+/// re-validated on every change, so the error disappears as soon as the
+/// value is fixed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ValidateOn {
+    /// On every change of the value
+    #[default]
+    Change,
+    /// When the control loses focus or its selection is committed
+    Blur,
+    /// Only when validated explicitly, e.g. by pressing Enter or submitting a form
+    Submit,
+}
+
+/// Validation state of a form control
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum ValidationState {
+    /// Not validated yet
+    #[default]
+    Unvalidated,
+    /// Waiting for an async rule
+    Pending,
+    /// All rules passed
+    Valid,
+    /// A rule failed with this message
+    Invalid(String),
+}
+
+impl ValidationState {
+    /// The error message, if invalid
+    pub fn error(&self) -> Option<&str> {
+        match self {
+            ValidationState::Invalid(message) => Some(message),
+            _ => None,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        matches!(self, ValidationState::Valid)
+    }
+
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, ValidationState::Invalid(_))
+    }
+
+    pub fn is_pending(&self) -> bool {
+        matches!(self, ValidationState::Pending)
+    }
+}
+
+type Rule<T> = Rc<dyn Fn(&T) -> ValidationResult>;
+type AsyncRule<T> = Rc<dyn Fn(&T) -> Pin<Box<dyn Future<Output = ValidationResult>>>>;
+
+/// Validation rules and state of one form control.
+///
+/// `T` is the value being validated: `str` for text controls and
+/// `[String]` (the selected values) for choice controls. Synchronous rules
+/// run first, in order; the async rule only runs when they all pass, and
+/// results of outdated async runs are dropped.
+pub(crate) struct Validation<T: ?Sized> {
+    rules: Vec<Rule<T>>,
+    async_rule: Option<AsyncRule<T>>,
+    trigger: ValidateOn,
+    state: ValidationState,
+    /// Incremented on every run so stale async results can be ignored
+    generation: usize,
+    _task: Option<Task<()>>,
+}
+
+impl<T: ?Sized> Default for Validation<T> {
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            async_rule: None,
+            trigger: ValidateOn::default(),
+            state: ValidationState::default(),
+            generation: 0,
+            _task: None,
+        }
+    }
+}
+
+impl<T: ?Sized + 'static> Validation<T> {
+    pub fn add_rule(&mut self, rule: impl Fn(&T) -> ValidationResult + 'static) {
+        self.rules.push(Rc::new(rule));
+    }
+
+    pub fn set_async_rule<F, Fut>(&mut self, rule: F)
+    where
+        F: Fn(&T) -> Fut + 'static,
+        Fut: Future<Output = ValidationResult> + 'static,
+    {
+        self.async_rule = Some(Rc::new(move |value| Box::pin(rule(value))));
+    }
+
+    pub fn set_trigger(&mut self, trigger: ValidateOn) {
+        self.trigger = trigger;
+    }
+
+    pub fn state(&self) -> &ValidationState {
+        &self.state
+    }
+
+    /// The error message to render, if any
+    pub fn error(&self) -> Option<&str> {
+        self.state.error()
+    }
+
+    /// Whether any rules are set
+    pub fn has_rules(&self) -> bool {
+        !self.rules.is_empty() || self.async_rule.is_some()
+    }
+
+    /// Set the state directly, e.g. for an error reported by a server.
+    /// Cancels a running async rule.
+    pub fn set_state(&mut self, state: ValidationState) {
+        self.generation += 1;
+        self._task = None;
+        self.state = state;
+    }
+
+    /// Validate if `event` is covered by the trigger, or if an error is
+    /// shown and the value changed. Returns whether validation ran.
+    pub fn handle<E: 'static>(
+        &mut self,
+        event: ValidateOn,
+        value: &T,
+        field: fn(&mut E) -> &mut Validation<T>,
+        cx: &mut Context<E>,
+    ) -> bool
+    where
+        T: ToOwned,
+    {
+        if !self.should_run(event) {
+            return false;
+        }
+        self.run(event, value, field, cx);
+        true
+    }
+
+    /// Whether `event` runs the rules: it must be covered by the trigger,
+    /// unless an error is shown, which every event re-checks
+    fn should_run(&self, event: ValidateOn) -> bool {
+        self.has_rules() && (event >= self.trigger || self.state.is_invalid())
+    }
+
+    /// Start a run: check the sync rules, in order, and return the async
+    /// rule with the run's generation if it still has to check the value
+    fn begin_run(&mut self, value: &T) -> Option<(AsyncRule<T>, usize)> {
+        self.generation += 1;
+        self._task = None;
+
+        if let Some(message) = self.rules.iter().find_map(|rule| rule(value).err()) {
+            self.state = ValidationState::Invalid(message);
+            return None;
+        }
+        let Some(async_rule) = self.async_rule.clone() else {
+            self.state = ValidationState::Valid;
+            return None;
+        };
+        self.state = ValidationState::Pending;
+        Some((async_rule, self.generation))
+    }
+
+    /// Apply the async result of run `generation`, unless a later run or
+    /// `set_state` replaced it. Returns whether it was applied.
+    fn finish_run(&mut self, generation: usize, result: ValidationResult) -> bool {
+        if generation != self.generation {
+            return false;
+        }
+        self.state = match result {
+            Ok(()) => ValidationState::Valid,
+            Err(message) => ValidationState::Invalid(message),
+        };
+        true
+    }
+
+    /// Run the rules now.
+    ///
+    /// `field` locates this validation in the component, for async results.
+    pub fn run<E: 'static>(
+        &mut self,
+        event: ValidateOn,
+        value: &T,
+        field: fn(&mut E) -> &mut Validation<T>,
+        cx: &mut Context<E>,
+    ) where
+        T: ToOwned,
+    {
+        let Some((async_rule, generation)) = self.begin_run(value) else {
+            return;
+        };
+        let value = value.to_owned();
+        // Replacing the task drops, and so cancels, the running check,
+        // including a pending debounce timer
+        self._task = Some(cx.spawn(async move |this, cx| {
+            // Wait for typing to pause before starting the check
+            if event == ValidateOn::Change {
+                cx.background_executor().timer(ASYNC_DEBOUNCE).await;
+            }
+            let result = async_rule(value.borrow()).await;
+            _ = this.update(cx, |this, cx| {
+                if field(this).finish_run(generation, result) {
+                    cx.notify();
+                }
+            });
+        }));
+    }
+}
+
+/// Error message shown under an invalid form control
+pub(crate) fn render_error_message(message: &str, theme: &Theme) -> impl IntoElement {
+    div()
+        .mt(px(theme.spacing.xxs))
+        .text_size(theme.font_size(ComponentSize::Small))
+        .text_color(theme.colors.error)
+        .child(message.to_string())
+}
+
+/// Implement the validation API of a form control: `validate`,
+/// `validate_async`, `validate_on`, `validation_state`, `run_validation`,
+/// `set_error`, and the private `validate_for` that runs the rules when an
+/// event triggers them.
+///
+/// The control keeps its rules in a `validation: Validation<$value>` field,
+/// and `$current` reads the value to validate from `$this`. Doc comments
+/// before the control are added to the docs of `validate`.
+macro_rules! validation_methods {
+    ($(#[doc = $doc:literal])* $control:ty: $value:ty = |$this:ident| $current:expr) => {
+        impl $control {
+            /// Add a validation rule; its error is shown below the control.
+            ///
+            /// A rule returns `Err(message)` for an invalid value. Rules run in
+            /// the order they were added and the first error wins. When they run
+            /// is set by [`validate_on`](Self::validate_on).
+            $(#[doc = $doc])*
+            pub fn validate(
+                mut self,
+                rule: impl Fn(&$value) -> $crate::components::form::validation::ValidationResult + 'static,
+            ) -> Self {
+                self.validation.add_rule(rule);
+                self
+            }
+
+            /// Set an async validation rule, e.g. to check a name with a server.
+            ///
+            /// It runs after all [`validate`](Self::validate) rules pass. While it
+            /// runs the state is `ValidationState::Pending`; on change it waits
+            /// for typing to pause, and results for outdated values are dropped.
+            pub fn validate_async<F, Fut>(mut self, rule: F) -> Self
+            where
+                F: Fn(&$value) -> Fut + 'static,
+                Fut: std::future::Future<Output = $crate::components::form::validation::ValidationResult> + 'static,
+            {
+                self.validation.set_async_rule(rule);
+                self
+            }
+
+            /// Set when validation rules run (default: `ValidateOn::Change`).
+            ///
+            /// Once an error is shown the control re-validates on every change.
+            pub fn validate_on(mut self, trigger: $crate::components::form::validation::ValidateOn) -> Self {
+                self.validation.set_trigger(trigger);
+                self
+            }
+
+            /// The current validation state
+            pub fn validation_state(&self) -> &$crate::components::form::validation::ValidationState {
+                self.validation.state()
+            }
+
+            /// Run the validation rules now, regardless of [`validate_on`](Self::validate_on).
+            ///
+            /// Returns `false` if a rule failed; an async rule leaves the state
+            /// `ValidationState::Pending` until it completes.
+            pub fn run_validation(&mut self, cx: &mut gpui::Context<Self>) -> bool {
+                let $this = &*self;
+                let value = $current;
+                self.validation.run(
+                    $crate::components::form::validation::ValidateOn::Submit,
+                    std::borrow::Borrow::borrow(&value),
+                    |this: &mut Self| &mut this.validation,
+                    cx,
+                );
+                cx.notify();
+                !self.validation.state().is_invalid()
+            }
+
+            /// Show an error set from outside, e.g. returned by a server, or clear
+            /// it with `None`.
+            pub fn set_error(&mut self, error: Option<String>, cx: &mut gpui::Context<Self>) {
+                use $crate::components::form::validation::ValidationState;
+                self.validation.set_state(match error {
+                    Some(message) => ValidationState::Invalid(message),
+                    None => ValidationState::Unvalidated,
+                });
+                cx.notify();
+            }
+
+            /// Run the rules if `event` triggers them, or if an error is shown
+            fn validate_for(
+                &mut self,
+                event: $crate::components::form::validation::ValidateOn,
+                cx: &mut gpui::Context<Self>,
+            ) {
+                let $this = &*self;
+                let value = $current;
+                self.validation.handle(
+                    event,
+                    std::borrow::Borrow::borrow(&value),
+                    |this: &mut Self| &mut this.validation,
+                    cx,
+                );
+            }
+        }
+    };
+}
+
+pub(crate) use validation_methods;
+
+#[cfg(test)]
+mod tests {
+    use std::future::ready;
+
+    use super::{ValidateOn, Validation, ValidationState};
+
+    fn username() -> Validation<str> {
+        let mut validation = Validation::default();
+        validation.add_rule(|value: &str| {
+            if value.is_empty() {
+                Err("Required".into())
+            } else {
+                Ok(())
+            }
+        });
+        validation.add_rule(|value: &str| {
+            if value.len() < 3 {
+                Err("Too short".into())
+            } else {
+                Ok(())
+            }
+        });
+        validation
+    }
+
+    #[test]
+    fn earlier_triggers_cover_later_events() {
+        assert!(ValidateOn::Change < ValidateOn::Blur);
+        assert!(ValidateOn::Blur < ValidateOn::Submit);
+    }
+
+    #[test]
+    fn only_invalid_state_has_an_error() {
+        let invalid = ValidationState::Invalid("Required".into());
+        assert_eq!(invalid.error(), Some("Required"));
+        assert!(invalid.is_invalid());
+        assert_eq!(ValidationState::Pending.error(), None);
+        assert!(ValidationState::Valid.is_valid());
+        assert_eq!(ValidationState::default(), ValidationState::Unvalidated);
+    }
+
+    #[test]
+    fn events_before_the_trigger_only_recheck_errors() {
+        let mut validation = username();
+        validation.set_trigger(ValidateOn::Blur);
+        assert!(!validation.should_run(ValidateOn::Change));
+        assert!(validation.should_run(ValidateOn::Blur));
+        assert!(validation.should_run(ValidateOn::Submit));
+
+        // Once an error shows, typing re-checks it
+        validation.set_state(ValidationState::Invalid("Taken".into()));
+        assert!(validation.should_run(ValidateOn::Change));
+
+        // Without rules nothing runs
+        let empty = Validation::<str>::default();
+        assert!(!empty.should_run(ValidateOn::Submit));
+    }
+
+    #[test]
+    fn the_first_failing_rule_wins() {
+        let mut validation = username();
+        assert!(validation.begin_run("").is_none());
+        assert_eq!(validation.state(), &ValidationState::Invalid("Required".into()));
+
+        assert!(validation.begin_run("ab").is_none());
+        assert_eq!(validation.error(), Some("Too short"));
+
+        assert!(validation.begin_run("abc").is_none());
+        assert_eq!(validation.state(), &ValidationState::Valid);
+    }
+
+    #[test]
+    fn async_rule_runs_after_the_sync_rules_pass() {
+        let mut validation = username();
+        validation.set_async_rule(|_: &str| ready(Ok(())));
+
+        assert!(validation.begin_run("a").is_none());
+        assert_eq!(validation.error(), Some("Too short"));
+
+        let (_, generation) = validation.begin_run("alice").unwrap();
+        assert!(validation.state().is_pending());
+        assert!(validation.finish_run(generation, Err("Taken".into())));
+        assert_eq!(validation.error(), Some("Taken"));
+    }
+
+    #[test]
+    fn stale_async_results_are_dropped() {
+        let mut validation = username();
+        validation.set_async_rule(|_: &str| ready(Ok(())));
+
+        // A re-run replaces the pending check
+        let (_, first) = validation.begin_run("alice").unwrap();
+        let (_, second) = validation.begin_run("alicia").unwrap();
+        assert!(!validation.finish_run(first, Err("Taken".into())));
+        assert!(validation.state().is_pending());
+        assert!(validation.finish_run(second, Ok(())));
+        assert!(validation.state().is_valid());
+
+        // So does setting the state directly
+        let (_, generation) = validation.begin_run("bob").unwrap();
+        validation.set_state(ValidationState::Invalid("Server error".into()));
+        assert!(!validation.finish_run(generation, Ok(())));
+        assert_eq!(validation.error(), Some("Server error"));
+    }
+}
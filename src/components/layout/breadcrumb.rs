@@ -164,10 +164,10 @@ impl Breadcrumb {
 
 impl Render for Breadcrumb {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let theme = Theme::default();
+        let theme = cx.theme().clone();
         let items = self.items.clone();
         let separator = self.separator;
-        let text_size = self.size.font_size();
+        let text_size = theme.font_size(self.size);
         let text_color = self.custom_text_color.unwrap_or(theme.colors.text);
         let separator_color = self.custom_separator_color.unwrap_or(theme.colors.text_secondary);
 
@@ -176,7 +176,7 @@ impl Render for Breadcrumb {
             .flex()
             .flex_row()
             .items_center()
-            .gap_2()
+            .gap(px(theme.spacing.sm))
             .children(items.iter().enumerate().flat_map(|(index, item)| {
                 let is_last = index == items.len() - 1;
                 let item_label = item.label.clone();
@@ -198,7 +198,7 @@ impl Render for Breadcrumb {
                                 .hover(|style| style.text_color(theme.colors.primary))
                         })
                         .when(is_last, |this| {
-                            this.font_weight(FontWeight::MEDIUM)
+                            this.font_weight(FontWeight(theme.typography.font_weight_medium))
                         })
                         .on_mouse_down(MouseButton::Left, cx.listener({
                             let index = index;
@@ -216,7 +216,7 @@ impl Render for Breadcrumb {
                         div()
                             .text_size(text_size)
                             .text_color(separator_color)
-                            .px(px(4.))
+                            .px(px(theme.spacing.xxs))
                             .child(separator.display())
                             .into_any_element()
                     );
@@ -103,8 +103,8 @@ impl Tabs {
     }
     
     /// Get the padding for tab items
-    fn tab_padding(&self) -> (Pixels, Pixels) {
-        let (py, px) = self.size.padding();
+    fn tab_padding(&self, theme: &Theme) -> (Pixels, Pixels) {
+        let (py, px) = theme.padding(self.size);
         (py, px)
     }
 }
@@ -121,39 +121,26 @@ impl Default for Tabs {
 
 impl Render for Tabs {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        let theme = Theme::default();
+        let theme = cx.theme().clone();
         // Clone only the labels, we'll use the original tabs for content
         let tab_labels: Vec<String> = self.tabs.iter().map(|t| t.label.clone()).collect();
         let tabs_ref = &self.tabs;
         let active_index = self.active_index;
         let size = self.size;
-        let (tab_py, tab_px) = self.tab_padding();
+        let (tab_py, tab_px) = self.tab_padding(&theme);
         
-        // Tab bar background color (light gray)
-        let tab_bar_bg = rgb(0xF5F5F5); // Light gray background
-        let tab_bar_radius = px(BorderRadius::LG);
+        // Tab bar background color
+        let tab_bar_bg = theme.colors.background_secondary;
+        let tab_bar_radius = px(theme.radius.lg);
         
-        // Shadow for tab bar
-        let tab_bar_shadow = BoxShadow {
-            color: rgba(0x0000000A).into(), // Subtle shadow
-            offset: point(px(0.), px(1.)),
-            blur_radius: px(2.),
-            spread_radius: px(0.),
-        };
-        
-        // Helper function to create active tab shadow
-        let make_active_tab_shadow = || BoxShadow {
-            color: rgba(0x0000000A).into(),
-            offset: point(px(0.), px(1.)),
-            blur_radius: px(2.),
-            spread_radius: px(0.),
-        };
+        // Shadow for tab bar and active tab
+        let tab_bar_shadow = theme.shadow_sm();
         
         div()
             .flex()
             .flex_col()
             .w_full()
-            .gap_4()
+            .gap(px(theme.spacing.lg))
             .child(
                 // Tab bar container
                 div()
@@ -161,9 +148,9 @@ impl Render for Tabs {
                     .flex_row()
                     .bg(tab_bar_bg)
                     .rounded(tab_bar_radius)
-                    .shadow(vec![tab_bar_shadow])
-                    .p_1() // Padding around tabs
-                    .gap_1() // Gap between tabs
+                    .shadow(tab_bar_shadow)
+                    .p(px(theme.spacing.xxs)) // Padding around tabs
+                    .gap(px(theme.spacing.xxs)) // Gap between tabs
                     .children(tab_labels.iter().enumerate().map({
                         let theme = theme.clone();
                         move |(index, label)| {
@@ -178,13 +165,13 @@ impl Render for Tabs {
                                 .justify_center()
                                 .py(tab_py)
                                 .px(tab_px)
-                                .rounded(px(BorderRadius::MD))
-                                .text_size(size.font_size())
+                                .rounded(px(theme.radius.md))
+                                .text_size(theme.font_size(size))
                                 .when(is_active, |this| {
-                                    this.bg(rgb(0xFFFFFF)) // White background for active tab
-                                        .font_weight(FontWeight::BOLD)
-                                        .text_color(rgb(0x000000)) // Black text for active tab
-                                        .shadow(vec![make_active_tab_shadow()])
+                                    this.bg(theme.colors.background) // Raised background for active tab
+                                        .font_weight(FontWeight(theme.typography.font_weight_bold))
+                                        .text_color(theme.colors.text)
+                                        .shadow(theme.shadow_sm())
                                 })
                                 .when(!is_active, |this| {
                                     this.text_color(theme.colors.text_secondary) // Gray text for inactive tabs
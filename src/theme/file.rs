@@ -0,0 +1,505 @@
+// Theme file format: load themes from JSON or TOML
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use gpui::*;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{ColorPalette, RadiusScale, ShadowScale, SizeScale, SpacingScale, Theme, ThemeMode, Typography};
+use crate::utils::{parse_hex, to_hex};
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// A problem with a single key in a theme file
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThemeKeyError {
+    /// A required key is missing
+    Missing(String),
+    /// A key that the theme format does not know about
+    Unknown(String),
+    /// A color value that is not a valid hex string
+    InvalidColor { key: String, value: String },
+}
+
+impl fmt::Display for ThemeKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeKeyError::Missing(key) => write!(f, "missing key `{}`", key),
+            ThemeKeyError::Unknown(key) => write!(f, "unknown key `{}`", key),
+            ThemeKeyError::InvalidColor { key, value } => {
+                write!(f, "invalid color `{}` for `{}`, expected #RRGGBB or #RRGGBBAA", value, key)
+            }
+        }
+    }
+}
+
+/// Error returned when loading a theme file
+#[derive(Debug, Clone, PartialEq)]
+pub enum ThemeError {
+    /// The file could not be read
+    Io(String),
+    /// The file is not valid JSON/TOML, or a value has the wrong type
+    Parse(String),
+    /// The file parsed, but some keys are missing or invalid
+    Invalid(Vec<ThemeKeyError>),
+}
+
+impl fmt::Display for ThemeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ThemeError::Io(message) => write!(f, "failed to read theme: {}", message),
+            ThemeError::Parse(message) => write!(f, "failed to parse theme: {}", message),
+            ThemeError::Invalid(errors) => {
+                write!(f, "invalid theme: ")?;
+                for (index, error) in errors.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, "; ")?;
+                    }
+                    write!(f, "{}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ThemeError {}
+
+// ============================================================================
+// Palette (de)serialization
+// ============================================================================
+
+macro_rules! palette_fields {
+    ($($field:ident),* $(,)?) => {
+        impl ColorPalette {
+            /// Names of every palette key, in declaration order
+            pub const KEYS: &'static [&'static str] = &[$(stringify!($field)),*];
+
+            /// Every palette entry as `(key, color)`
+            pub fn entries(&self) -> Vec<(&'static str, Rgba)> {
+                vec![$((stringify!($field), self.$field)),*]
+            }
+
+            fn entry_mut(&mut self, key: &str) -> Option<&mut Rgba> {
+                match key {
+                    $(stringify!($field) => Some(&mut self.$field),)*
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+palette_fields!(
+    primary,
+    primary_hover,
+    primary_active,
+    secondary,
+    secondary_hover,
+    secondary_active,
+    text,
+    text_secondary,
+    text_disabled,
+    background,
+    background_secondary,
+    background_hover,
+    border,
+    border_focus,
+    border_error,
+    success,
+    warning,
+    error,
+    info,
+    text_on_primary,
+    selection,
+    selection_text,
+);
+
+impl ColorPalette {
+    /// Build a palette from `key -> hex string` pairs.
+    ///
+    /// Every key is required. All problems are collected and reported together,
+    /// with `prefix` prepended to key names (e.g. `"dark."`).
+    pub fn from_hex_map(map: &BTreeMap<String, String>, prefix: &str) -> Result<Self, Vec<ThemeKeyError>> {
+        let mut palette = ColorPalette::default();
+        let mut errors = Vec::new();
+
+        for key in Self::KEYS {
+            if !map.contains_key(*key) {
+                errors.push(ThemeKeyError::Missing(format!("{}{}", prefix, key)));
+            }
+        }
+
+        for (key, value) in map {
+            match palette.entry_mut(key) {
+                Some(slot) => match parse_hex(value) {
+                    Some(color) => *slot = color,
+                    None => errors.push(ThemeKeyError::InvalidColor {
+                        key: format!("{}{}", prefix, key),
+                        value: value.clone(),
+                    }),
+                },
+                None => errors.push(ThemeKeyError::Unknown(format!("{}{}", prefix, key))),
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(palette)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Convert the palette to `key -> hex string` pairs
+    pub fn to_hex_map(&self) -> BTreeMap<String, String> {
+        self.entries()
+            .into_iter()
+            .map(|(key, color)| (key.to_string(), to_hex(color)))
+            .collect()
+    }
+}
+
+impl Serialize for ColorPalette {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_hex_map().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for ColorPalette {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let map = BTreeMap::<String, String>::deserialize(deserializer)?;
+        ColorPalette::from_hex_map(&map, "")
+            .map_err(|errors| serde::de::Error::custom(ThemeError::Invalid(errors)))
+    }
+}
+
+// ============================================================================
+// Theme file
+// ============================================================================
+
+/// On-disk theme format.
+///
+/// Every section is optional. A missing palette falls back to the built-in
+/// palette for that mode, and missing spacing/radius/size/typography/shadow
+/// values fall back to the defaults. A palette that is present must define every key.
+///
+/// ```toml
+/// mode = "dark"
+///
+/// [dark]
+/// primary = "#7F85D6"
+/// # ...every other ColorPalette key
+///
+/// [spacing]
+/// md = 10
+///
+/// [sizes.medium]
+/// height = 32
+/// font_size = 13
+/// padding_y = 6
+/// padding_x = 12
+/// min_width = 80
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ThemeFile {
+    /// Initial mode; keeps the current mode when reloading if omitted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mode: Option<ThemeMode>,
+    /// Palette used for both modes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub colors: Option<BTreeMap<String, String>>,
+    /// Light mode palette, overrides `colors`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub light: Option<BTreeMap<String, String>>,
+    /// Dark mode palette, overrides `colors`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dark: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spacing: Option<SpacingScale>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub radius: Option<RadiusScale>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sizes: Option<SizeScale>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub typography: Option<Typography>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shadows: Option<ShadowScale>,
+}
+
+impl ThemeFile {
+    /// Parse a theme file from JSON
+    pub fn from_json(source: &str) -> Result<Self, ThemeError> {
+        serde_json::from_str(source).map_err(|err| ThemeError::Parse(err.to_string()))
+    }
+
+    /// Parse a theme file from TOML
+    pub fn from_toml(source: &str) -> Result<Self, ThemeError> {
+        toml::from_str(source).map_err(|err| ThemeError::Parse(err.to_string()))
+    }
+
+    /// Read and parse a theme file, picking the format from the extension
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        let path = path.as_ref();
+        let source = std::fs::read_to_string(path)
+            .map_err(|err| ThemeError::Io(format!("{}: {}", path.display(), err)))?;
+
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("toml") => Self::from_toml(&source),
+            _ => Self::from_json(&source),
+        }
+    }
+
+    /// Build a theme, using `fallback_mode` if the file does not set a mode
+    pub fn into_theme(self, fallback_mode: ThemeMode) -> Result<Theme, ThemeError> {
+        let mut errors = Vec::new();
+        let mut parse = |map: &BTreeMap<String, String>, prefix: &str| {
+            ColorPalette::from_hex_map(map, prefix)
+                .map_err(|mut palette_errors| errors.append(&mut palette_errors))
+                .ok()
+        };
+
+        let shared = self.colors.as_ref().and_then(|map| parse(map, "colors."));
+        let light = match &self.light {
+            Some(map) => parse(map, "light."),
+            None => shared.clone(),
+        };
+        let dark = match &self.dark {
+            Some(map) => parse(map, "dark."),
+            None => shared,
+        };
+
+        if !errors.is_empty() {
+            return Err(ThemeError::Invalid(errors));
+        }
+
+        let light = light.unwrap_or_else(ColorPalette::light);
+        let dark = dark.unwrap_or_else(ColorPalette::dark);
+        let mut theme = Theme::with_palettes(light, dark).mode(self.mode.unwrap_or(fallback_mode));
+        theme.spacing = self.spacing.unwrap_or_default();
+        theme.radius = self.radius.unwrap_or_default();
+        theme.sizes = self.sizes.unwrap_or_default();
+        theme.typography = self.typography.unwrap_or_default();
+        theme.shadows = self.shadows.unwrap_or_default();
+        Ok(theme)
+    }
+}
+
+impl From<Theme> for ThemeFile {
+    fn from(theme: Theme) -> Self {
+        Self {
+            mode: Some(theme.mode),
+            colors: None,
+            light: Some(theme.light_colors.to_hex_map()),
+            dark: Some(theme.dark_colors.to_hex_map()),
+            spacing: Some(theme.spacing),
+            radius: Some(theme.radius),
+            sizes: Some(theme.sizes),
+            typography: Some(theme.typography),
+            shadows: Some(theme.shadows),
+        }
+    }
+}
+
+impl TryFrom<ThemeFile> for Theme {
+    type Error = ThemeError;
+
+    fn try_from(file: ThemeFile) -> Result<Self, Self::Error> {
+        file.into_theme(ThemeMode::Light)
+    }
+}
+
+// ============================================================================
+// Loading
+// ============================================================================
+
+impl Theme {
+    /// Parse a theme from JSON
+    pub fn from_json(source: &str) -> Result<Self, ThemeError> {
+        ThemeFile::from_json(source)?.into_theme(ThemeMode::Light)
+    }
+
+    /// Parse a theme from TOML
+    pub fn from_toml(source: &str) -> Result<Self, ThemeError> {
+        ThemeFile::from_toml(source)?.into_theme(ThemeMode::Light)
+    }
+
+    /// Load a theme from a `.json` or `.toml` file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ThemeError> {
+        ThemeFile::load(path)?.into_theme(ThemeMode::Light)
+    }
+
+    /// Load a theme file, falling back to the default theme if it is invalid
+    pub fn load_or_default(path: impl AsRef<Path>) -> (Self, Option<ThemeError>) {
+        match Self::load(path) {
+            Ok(theme) => (theme, None),
+            Err(err) => (Self::default(), Some(err)),
+        }
+    }
+
+    /// Serialize the theme to pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(&ThemeFile::from(self.clone())).unwrap_or_default()
+    }
+
+    /// Serialize the theme to TOML
+    pub fn to_toml(&self) -> String {
+        toml::to_string_pretty(&ThemeFile::from(self.clone())).unwrap_or_default()
+    }
+
+    /// Watch a theme file and apply it as the global theme whenever it changes.
+    ///
+    /// The file is applied immediately and then polled for changes. Invalid
+    /// edits are reported through `on_error` and the last good theme stays
+    /// active. If the file does not set a mode, the current mode is kept, so
+    /// this works together with [`Theme::follow_system_appearance`]. Dropping
+    /// the returned task stops watching.
+    pub fn watch_file(
+        path: impl Into<PathBuf>,
+        cx: &mut App,
+        on_error: impl Fn(&ThemeError, &mut App) + 'static,
+    ) -> Task<()> {
+        const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+        let path = path.into();
+        cx.spawn(async move |cx| {
+            let mut last_modified: Option<SystemTime> = None;
+            loop {
+                let modified = std::fs::metadata(&path).and_then(|meta| meta.modified()).ok();
+                if modified.is_some() && modified != last_modified {
+                    last_modified = modified;
+                    let load_path = path.clone();
+                    let loaded = cx
+                        .background_executor()
+                        .spawn(async move { ThemeFile::load(load_path) })
+                        .await;
+                    let updated = cx.update(|cx| {
+                        match loaded.and_then(|file| file.into_theme(Theme::global(cx).mode)) {
+                            Ok(theme) => Theme::change(theme, cx),
+                            Err(err) => on_error(&err, cx),
+                        }
+                    });
+                    if updated.is_err() {
+                        break;
+                    }
+                }
+                cx.background_executor().timer(POLL_INTERVAL).await;
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use gpui::rgb;
+
+    use super::{ThemeError, ThemeFile, ThemeKeyError};
+    use crate::theme::{ColorPalette, Theme, ThemeMode};
+
+    fn dark_file(edit: impl FnOnce(&mut BTreeMap<String, String>)) -> ThemeFile {
+        let mut dark = ColorPalette::dark().to_hex_map();
+        edit(&mut dark);
+        ThemeFile {
+            dark: Some(dark),
+            ..ThemeFile::default()
+        }
+    }
+
+    fn key_errors(file: ThemeFile) -> Vec<ThemeKeyError> {
+        match file.into_theme(ThemeMode::Dark) {
+            Err(ThemeError::Invalid(errors)) => errors,
+            other => panic!("expected key errors, got {:?}", other.map(|theme| theme.mode)),
+        }
+    }
+
+    /// Every palette, scale and mode that a theme file stores
+    fn assert_same_theme(left: &Theme, right: &Theme) {
+        assert_eq!(left.mode, right.mode);
+        assert_eq!(left.light_colors.to_hex_map(), right.light_colors.to_hex_map());
+        assert_eq!(left.dark_colors.to_hex_map(), right.dark_colors.to_hex_map());
+        assert_eq!(left.to_json(), right.to_json());
+    }
+
+    fn custom_theme() -> Theme {
+        let mut theme = Theme::with_palettes(
+            ColorPalette::from_seed(rgb(0xE53935), ThemeMode::Light),
+            ColorPalette::from_seed(rgb(0x27AE60), ThemeMode::Dark),
+        )
+        .mode(ThemeMode::Dark)
+        .compact();
+        theme.spacing.md = 10.0;
+        theme
+    }
+
+    #[test]
+    fn a_missing_palette_key_is_reported_with_its_section() {
+        let file = dark_file(|dark| {
+            dark.remove("primary");
+        });
+
+        assert_eq!(key_errors(file), vec![ThemeKeyError::Missing("dark.primary".into())]);
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let file = dark_file(|dark| {
+            dark.insert("accent".into(), "#FF0000".into());
+        });
+        assert_eq!(key_errors(file), vec![ThemeKeyError::Unknown("dark.accent".into())]);
+
+        let top_level = ThemeFile::from_json(r#"{ "mode": "dark", "colours": {} }"#);
+        assert!(
+            matches!(&top_level, Err(ThemeError::Parse(message)) if message.contains("colours")),
+            "{:?}",
+            top_level.map(|file| file.mode),
+        );
+        let nested = ThemeFile::from_toml("[spacing]\nhuge = 40\n");
+        assert!(
+            matches!(&nested, Err(ThemeError::Parse(message)) if message.contains("huge")),
+            "{:?}",
+            nested.map(|file| file.mode),
+        );
+    }
+
+    #[test]
+    fn an_invalid_hex_color_is_reported_with_its_value() {
+        let file = dark_file(|dark| {
+            dark.insert("border".into(), "#12".into());
+            dark.insert("text".into(), "white".into());
+        });
+
+        assert_eq!(
+            key_errors(file),
+            vec![
+                ThemeKeyError::InvalidColor { key: "dark.border".into(), value: "#12".into() },
+                ThemeKeyError::InvalidColor { key: "dark.text".into(), value: "white".into() },
+            ]
+        );
+    }
+
+    #[test]
+    fn json_round_trips() {
+        let theme = custom_theme();
+        let loaded = Theme::from_json(&theme.to_json()).unwrap();
+
+        assert_same_theme(&theme, &loaded);
+        assert_eq!(loaded.spacing.md, 10.0);
+        assert_eq!(loaded.colors.to_hex_map(), theme.dark_colors.to_hex_map());
+    }
+
+    #[test]
+    fn toml_round_trips() {
+        let theme = custom_theme();
+        let loaded = Theme::from_toml(&theme.to_toml()).unwrap();
+
+        assert_same_theme(&theme, &loaded);
+        assert_eq!(loaded.to_toml(), theme.to_toml());
+    }
+}
@@ -0,0 +1,453 @@
+// Theme system for Fluix components
+
+use std::sync::OnceLock;
+
+use gpui::*;
+use serde::{Deserialize, Serialize};
+
+pub mod file;
+mod seed;
+pub mod tokens;
+
+pub use file::*;
+pub use tokens::*;
+
+/// Component size variants
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComponentSize {
+    XSmall,
+    Small,
+    Medium,
+    Large,
+    XLarge,
+}
+
+impl ComponentSize {
+    /// Get the built-in pixel size for the variant.
+    ///
+    /// Components read sizes from [`Theme::sizes`], which defaults to these values.
+    pub fn px(&self) -> f32 {
+        match self {
+            ComponentSize::XSmall => 20.0,
+            ComponentSize::Small => 28.0,
+            ComponentSize::Medium => 36.0,
+            ComponentSize::Large => 44.0,
+            ComponentSize::XLarge => 52.0,
+        }
+    }
+
+    /// Get font size for the variant
+    pub fn font_size(&self) -> Pixels {
+        match self {
+            ComponentSize::XSmall => px(11.0),
+            ComponentSize::Small => px(13.0),
+            ComponentSize::Medium => px(14.0),
+            ComponentSize::Large => px(16.0),
+            ComponentSize::XLarge => px(18.0),
+        }
+    }
+
+    /// Get padding for the variant
+    pub fn padding(&self) -> (Pixels, Pixels) {
+        match self {
+            ComponentSize::XSmall => (px(4.0), px(8.0)),
+            ComponentSize::Small => (px(6.0), px(12.0)),
+            ComponentSize::Medium => (px(8.0), px(16.0)),
+            ComponentSize::Large => (px(10.0), px(20.0)),
+            ComponentSize::XLarge => (px(12.0), px(24.0)),
+        }
+    }
+
+    /// Get minimum width for buttons
+    pub fn min_width(&self) -> Pixels {
+        match self {
+            ComponentSize::XSmall => px(60.0),
+            ComponentSize::Small => px(72.0),
+            ComponentSize::Medium => px(88.0),
+            ComponentSize::Large => px(104.0),
+            ComponentSize::XLarge => px(120.0),
+        }
+    }
+}
+
+/// Color palette for the theme
+#[derive(Debug, Clone)]
+pub struct ColorPalette {
+    // Primary colors
+    pub primary: Rgba,
+    pub primary_hover: Rgba,
+    pub primary_active: Rgba,
+    
+    // Secondary colors
+    pub secondary: Rgba,
+    pub secondary_hover: Rgba,
+    pub secondary_active: Rgba,
+    
+    // Neutral colors
+    pub text: Rgba,
+    pub text_secondary: Rgba,
+    pub text_disabled: Rgba,
+    
+    // Background colors
+    pub background: Rgba,
+    pub background_secondary: Rgba,
+    pub background_hover: Rgba,
+    
+    // Border colors
+    pub border: Rgba,
+    pub border_focus: Rgba,
+    pub border_error: Rgba,
+    
+    // State colors
+    pub success: Rgba,
+    pub warning: Rgba,
+    pub error: Rgba,
+    pub info: Rgba,
+
+    // Foreground colors
+    pub text_on_primary: Rgba,
+    pub selection: Rgba,
+    pub selection_text: Rgba,
+}
+
+impl Default for ColorPalette {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl ColorPalette {
+    /// Light palette (the default)
+    pub fn light() -> Self {
+        Self {
+            // Primary colors (purple palette)
+            primary: rgb(0x696FC7),
+            primary_hover: rgb(0xA7AAE1),
+            primary_active: rgb(0x5A5FB0),
+            
+            // Secondary colors (gray palette)
+            secondary: rgb(0x666666),
+            secondary_hover: rgb(0x888888),
+            secondary_active: rgb(0x555555),
+            
+            // Neutral colors
+            text: rgb(0x333333),
+            text_secondary: rgb(0x666666),
+            text_disabled: rgb(0x999999),
+            
+            // Background colors
+            background: rgb(0xFFFFFF),
+            background_secondary: rgb(0xF5F5F5),
+            background_hover: rgb(0xF0F0F0),
+            
+            // Border colors
+            border: rgb(0xE0E0E0),
+            border_focus: rgb(0x696FC7),
+            border_error: rgb(0xE74C3C),
+            
+            // State colors
+            success: rgb(0x27AE60),
+            warning: rgb(0xF39C12),
+            error: rgb(0xE74C3C),
+            info: rgb(0x3498DB),
+
+            // Foreground colors
+            text_on_primary: rgb(0xFFFFFF),
+            selection: rgb(0x4A90E2),
+            selection_text: rgb(0xFFFFFF),
+        }
+    }
+
+    /// Dark palette
+    pub fn dark() -> Self {
+        Self {
+            // Primary colors (purple palette)
+            primary: rgb(0x7F85D6),
+            primary_hover: rgb(0x9599E0),
+            primary_active: rgb(0x6A70C8),
+
+            // Secondary colors (gray palette)
+            secondary: rgb(0xA0A0A0),
+            secondary_hover: rgb(0xBBBBBB),
+            secondary_active: rgb(0x888888),
+
+            // Neutral colors
+            text: rgb(0xE6E6E6),
+            text_secondary: rgb(0xA6A6A6),
+            text_disabled: rgb(0x6B6B6B),
+
+            // Background colors
+            background: rgb(0x1E1E1E),
+            background_secondary: rgb(0x252526),
+            background_hover: rgb(0x2F2F31),
+
+            // Border colors
+            border: rgb(0x3C3C3C),
+            border_focus: rgb(0x7F85D6),
+            border_error: rgb(0xF0645A),
+
+            // State colors
+            success: rgb(0x3DC37A),
+            warning: rgb(0xF5A623),
+            error: rgb(0xF0645A),
+            info: rgb(0x4AA3E8),
+
+            // Foreground colors
+            text_on_primary: rgb(0xFFFFFF),
+            selection: rgb(0x264F78),
+            selection_text: rgb(0xFFFFFF),
+        }
+    }
+
+    /// Built-in palette for the given mode
+    pub fn for_mode(mode: ThemeMode) -> Self {
+        match mode {
+            ThemeMode::Light => Self::light(),
+            ThemeMode::Dark => Self::dark(),
+        }
+    }
+}
+
+/// Light or dark appearance
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThemeMode {
+    #[default]
+    Light,
+    Dark,
+}
+
+impl ThemeMode {
+    /// Whether this is the dark mode
+    pub fn is_dark(&self) -> bool {
+        matches!(self, ThemeMode::Dark)
+    }
+
+    /// Map a window appearance reported by the OS to a theme mode
+    pub fn from_appearance(appearance: WindowAppearance) -> Self {
+        match appearance {
+            WindowAppearance::Light | WindowAppearance::VibrantLight => ThemeMode::Light,
+            WindowAppearance::Dark | WindowAppearance::VibrantDark => ThemeMode::Dark,
+        }
+    }
+}
+
+/// Default spacing values, see [`Theme::spacing`] for the themeable scale
+pub struct Spacing;
+
+impl Spacing {
+    pub const XXXS: f32 = 2.0;
+    pub const XXS: f32 = 4.0;
+    pub const XS: f32 = 6.0;
+    pub const SM: f32 = 8.0;
+    pub const MD: f32 = 12.0;
+    pub const LG: f32 = 16.0;
+    pub const XL: f32 = 20.0;
+    pub const XXL: f32 = 24.0;
+    pub const XXXL: f32 = 32.0;
+}
+
+/// Default border radius values, see [`Theme::radius`] for the themeable scale
+pub struct BorderRadius;
+
+impl BorderRadius {
+    pub const NONE: f32 = 0.0;
+    pub const SM: f32 = 4.0;
+    pub const MD: f32 = 6.0;
+    pub const LG: f32 = 8.0;
+    pub const XL: f32 = 12.0;
+    pub const FULL: f32 = 9999.0;
+}
+
+/// Main theme configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "ThemeFile", into = "ThemeFile")]
+pub struct Theme {
+    /// Active mode
+    pub mode: ThemeMode,
+    /// Active palette, always one of `light_colors` / `dark_colors`
+    pub colors: ColorPalette,
+    /// Palette used in light mode
+    pub light_colors: ColorPalette,
+    /// Palette used in dark mode
+    pub dark_colors: ColorPalette,
+    /// Spacing scale, used for the padding, gaps and margins of components
+    pub spacing: SpacingScale,
+    /// Border radius scale
+    pub radius: RadiusScale,
+    /// Per-size component metrics
+    pub sizes: SizeScale,
+    /// Fonts, weights and line heights
+    pub typography: Typography,
+    /// Shadow elevation levels
+    pub shadows: ShadowScale,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::light()
+    }
+}
+
+impl Theme {
+    /// Create a new theme
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Built-in light theme
+    pub fn light() -> Self {
+        Self::with_palettes(ColorPalette::light(), ColorPalette::dark()).mode(ThemeMode::Light)
+    }
+
+    /// Built-in dark theme
+    pub fn dark() -> Self {
+        Self::with_palettes(ColorPalette::light(), ColorPalette::dark()).mode(ThemeMode::Dark)
+    }
+
+    /// Create a custom theme with specific colors, used for both modes
+    pub fn custom(colors: ColorPalette) -> Self {
+        Self::with_palettes(colors.clone(), colors)
+    }
+
+    /// Create a theme from a light/dark palette pair
+    pub fn with_palettes(light: ColorPalette, dark: ColorPalette) -> Self {
+        Self {
+            mode: ThemeMode::Light,
+            colors: light.clone(),
+            light_colors: light,
+            dark_colors: dark,
+            spacing: SpacingScale::default(),
+            radius: RadiusScale::default(),
+            sizes: SizeScale::default(),
+            typography: Typography::default(),
+            shadows: ShadowScale::default(),
+        }
+    }
+
+    /// Use denser spacing and smaller controls
+    pub fn compact(mut self) -> Self {
+        self.spacing = SpacingScale::compact();
+        self.sizes = SizeScale::compact();
+        self
+    }
+
+    /// Switch to the given mode
+    pub fn mode(mut self, mode: ThemeMode) -> Self {
+        self.set_mode(mode);
+        self
+    }
+
+    /// Switch the active palette to the given mode
+    pub fn set_mode(&mut self, mode: ThemeMode) {
+        self.mode = mode;
+        self.colors = match mode {
+            ThemeMode::Light => self.light_colors.clone(),
+            ThemeMode::Dark => self.dark_colors.clone(),
+        };
+    }
+
+    /// Whether the active mode is dark
+    pub fn is_dark(&self) -> bool {
+        self.mode.is_dark()
+    }
+
+    /// Control height for a component size
+    pub fn height(&self, size: ComponentSize) -> Pixels {
+        px(self.sizes.get(size).height)
+    }
+
+    /// Font size for a component size
+    pub fn font_size(&self, size: ComponentSize) -> Pixels {
+        px(self.sizes.get(size).font_size)
+    }
+
+    /// Padding `(vertical, horizontal)` for a component size
+    pub fn padding(&self, size: ComponentSize) -> (Pixels, Pixels) {
+        let metrics = self.sizes.get(size);
+        (px(metrics.padding_y), px(metrics.padding_x))
+    }
+
+    /// Minimum width for a component size
+    pub fn min_width(&self, size: ComponentSize) -> Pixels {
+        px(self.sizes.get(size).min_width)
+    }
+
+    /// Shadow for resting controls
+    pub fn shadow_sm(&self) -> Vec<BoxShadow> {
+        ShadowScale::to_box_shadows(&self.shadows.sm)
+    }
+
+    /// Shadow for raised controls
+    pub fn shadow_md(&self) -> Vec<BoxShadow> {
+        ShadowScale::to_box_shadows(&self.shadows.md)
+    }
+
+    /// Shadow for floating surfaces
+    pub fn shadow_lg(&self) -> Vec<BoxShadow> {
+        ShadowScale::to_box_shadows(&self.shadows.lg)
+    }
+
+    /// Install the default theme as the global theme if none is set yet
+    pub fn init(cx: &mut App) {
+        if !cx.has_global::<Theme>() {
+            cx.set_global(Theme::default());
+        }
+    }
+
+    /// Get the active global theme, falling back to the default theme
+    pub fn global(cx: &App) -> &Theme {
+        static DEFAULT_THEME: OnceLock<Theme> = OnceLock::new();
+        cx.try_global::<Theme>()
+            .unwrap_or_else(|| DEFAULT_THEME.get_or_init(Theme::default))
+    }
+
+    /// Replace the global theme and redraw all windows with it
+    pub fn change(theme: Theme, cx: &mut App) {
+        cx.set_global(theme);
+        cx.refresh_windows();
+    }
+
+    /// Update the global theme in place and redraw all windows
+    pub fn update(cx: &mut App, f: impl FnOnce(&mut Theme)) {
+        let mut theme = Self::global(cx).clone();
+        f(&mut theme);
+        Self::change(theme, cx);
+    }
+
+    /// Switch the global theme to the given mode
+    pub fn change_mode(mode: ThemeMode, cx: &mut App) {
+        if Self::global(cx).mode != mode {
+            Self::update(cx, |theme| theme.set_mode(mode));
+        }
+    }
+
+    /// Match the global theme to the window's current OS appearance
+    pub fn sync_system_appearance(window: &Window, cx: &mut App) {
+        Self::change_mode(ThemeMode::from_appearance(window.appearance()), cx);
+    }
+
+    /// Follow the OS appearance for as long as the returned subscription is held.
+    ///
+    /// The theme is synced immediately, so call this while building the window
+    /// to avoid a frame in the wrong mode.
+    pub fn follow_system_appearance(window: &Window, cx: &mut App) -> Subscription {
+        Self::sync_system_appearance(window, cx);
+        window.observe_window_appearance(|window, cx| {
+            Self::sync_system_appearance(window, cx);
+        })
+    }
+}
+
+impl Global for Theme {}
+
+/// Access the active theme from any gpui context
+pub trait ActiveTheme {
+    fn theme(&self) -> &Theme;
+}
+
+impl ActiveTheme for App {
+    fn theme(&self) -> &Theme {
+        Theme::global(self)
+    }
+}
@@ -0,0 +1,356 @@
+// Palette generation from a single seed color
+
+use gpui::*;
+
+use super::{ColorPalette, Theme, ThemeMode};
+use crate::utils::{
+    contrast_ratio, from_oklch, lighten_perceptual, to_oklch, Oklch, WCAG_AA_LARGE, WCAG_AA_NORMAL,
+};
+
+/// Lightness step used when nudging a color to reach a contrast target
+const CONTRAST_STEP: f32 = 0.01;
+
+/// Move `foreground` away from `background` in lightness until the pair
+/// reaches `min_ratio`, keeping hue and chroma.
+fn ensure_contrast(foreground: Rgba, background: Rgba, min_ratio: f32) -> Rgba {
+    let towards_dark = to_oklch(background).l > 0.5;
+    let mut color = foreground;
+    for _ in 0..100 {
+        if contrast_ratio(color, background) >= min_ratio {
+            break;
+        }
+        let step = if towards_dark {
+            -CONTRAST_STEP
+        } else {
+            CONTRAST_STEP
+        };
+        color = lighten_perceptual(color, step);
+    }
+    color
+}
+
+/// Lowest contrast of `foreground` against any of `backgrounds`
+fn min_contrast(foreground: Rgba, backgrounds: &[Rgba]) -> f32 {
+    backgrounds
+        .iter()
+        .map(|background| contrast_ratio(foreground, *background))
+        .fold(f32::MAX, f32::min)
+}
+
+/// Like [`ensure_contrast`], against every background at once
+fn ensure_contrast_all(foreground: Rgba, backgrounds: &[Rgba], min_ratio: f32) -> Rgba {
+    let mut color = foreground;
+    for background in backgrounds {
+        color = ensure_contrast(color, *background, min_ratio);
+    }
+    color
+}
+
+/// A neutral tinted slightly toward the seed hue
+fn neutral(seed: Oklch, l: f32, c: f32) -> Rgba {
+    from_oklch(Oklch {
+        l,
+        c: c.min(seed.c),
+        h: seed.h,
+        a: 1.0,
+    })
+}
+
+/// A state color at a fixed hue, lightness chosen per mode
+fn state(h: f32, mode: ThemeMode) -> Rgba {
+    let l = if mode.is_dark() { 0.72 } else { 0.58 };
+    from_oklch(Oklch {
+        l,
+        c: 0.15,
+        h,
+        a: 1.0,
+    })
+}
+
+impl ColorPalette {
+    /// Derive a complete palette from a single brand color.
+    ///
+    /// Hover/active/focus shades, neutrals, borders and state colors are
+    /// generated in OKLCH so they share the seed's hue. Text colors are
+    /// adjusted until they meet WCAG AA (4.5:1) on every background, and
+    /// text on the primary color meets AA on its hover/active shades too.
+    /// Very light or very dark seeds may be shifted in lightness to make
+    /// that possible.
+    pub fn from_seed(primary: Rgba, mode: ThemeMode) -> Self {
+        let primary = Rgba { a: 1.0, ..primary };
+        let seed = to_oklch(primary);
+        let dark = mode.is_dark();
+
+        // Backgrounds
+        let (background, background_secondary, background_hover) = if dark {
+            (
+                neutral(seed, 0.22, 0.01),
+                neutral(seed, 0.25, 0.012),
+                neutral(seed, 0.29, 0.014),
+            )
+        } else {
+            (
+                neutral(seed, 1.0, 0.0),
+                neutral(seed, 0.975, 0.005),
+                neutral(seed, 0.95, 0.008),
+            )
+        };
+        let surfaces = [background, background_secondary, background_hover];
+
+        // Text
+        let (text_l, text_secondary_l, text_disabled_l) = if dark {
+            (0.93, 0.75, 0.52)
+        } else {
+            (0.27, 0.47, 0.68)
+        };
+        let text = ensure_contrast_all(neutral(seed, text_l, 0.01), &surfaces, WCAG_AA_NORMAL);
+        let text_secondary = ensure_contrast_all(
+            neutral(seed, text_secondary_l, 0.015),
+            &surfaces,
+            WCAG_AA_NORMAL,
+        );
+        let text_disabled = neutral(seed, text_disabled_l, 0.01);
+
+        // Primary: pick the foreground that reads best, then push the primary
+        // shades away from it until every shade meets AA
+        let white = rgb(0xFFFFFF);
+        let ink = neutral(seed, 0.2, 0.02);
+        let text_on_primary = if contrast_ratio(white, primary) >= contrast_ratio(ink, primary) {
+            white
+        } else {
+            ink
+        };
+        let away = if to_oklch(text_on_primary).l > 0.5 {
+            -1.0
+        } else {
+            1.0
+        };
+        let primary = ensure_contrast(primary, text_on_primary, WCAG_AA_NORMAL);
+        let primary_hover = ensure_contrast(
+            lighten_perceptual(primary, 0.05 * away),
+            text_on_primary,
+            WCAG_AA_NORMAL,
+        );
+        let primary_active = ensure_contrast(
+            lighten_perceptual(primary, 0.10 * away),
+            text_on_primary,
+            WCAG_AA_NORMAL,
+        );
+
+        // The focus ring is a UI component, so it needs 3:1 against the background
+        let border_focus = ensure_contrast(primary, background, WCAG_AA_LARGE);
+
+        // Secondary: a muted version of the seed
+        let secondary_l = if dark { 0.72 } else { 0.5 };
+        let secondary =
+            ensure_contrast(neutral(seed, secondary_l, 0.03), background, WCAG_AA_LARGE);
+        let secondary_step = if dark { 0.06 } else { -0.06 };
+        let secondary_hover = lighten_perceptual(secondary, secondary_step);
+        let secondary_active = lighten_perceptual(secondary, secondary_step * 2.0);
+
+        // Borders
+        let border = neutral(seed, if dark { 0.36 } else { 0.9 }, 0.01);
+
+        // States keep fixed hues and need 3:1 against the background
+        let success = ensure_contrast(state(150.0, mode), background, WCAG_AA_LARGE);
+        let warning = ensure_contrast(state(75.0, mode), background, WCAG_AA_LARGE);
+        let error = ensure_contrast(state(27.0, mode), background, WCAG_AA_LARGE);
+        let info = ensure_contrast(state(240.0, mode), background, WCAG_AA_LARGE);
+
+        Self {
+            primary,
+            primary_hover,
+            primary_active,
+            secondary,
+            secondary_hover,
+            secondary_active,
+            text,
+            text_secondary,
+            text_disabled,
+            background,
+            background_secondary,
+            background_hover,
+            border,
+            border_focus,
+            border_error: error,
+            success,
+            warning,
+            error,
+            info,
+            text_on_primary,
+            selection: primary,
+            selection_text: text_on_primary,
+        }
+    }
+
+    /// Lowest contrast ratio between this palette's text colors and its
+    /// backgrounds, useful for validating hand-written palettes
+    pub fn min_text_contrast(&self) -> f32 {
+        let surfaces = [
+            self.background,
+            self.background_secondary,
+            self.background_hover,
+        ];
+        min_contrast(self.text, &surfaces)
+            .min(min_contrast(self.text_secondary, &surfaces))
+            .min(contrast_ratio(self.text_on_primary, self.primary))
+            .min(contrast_ratio(self.selection_text, self.selection))
+    }
+}
+
+impl Theme {
+    /// Build a light/dark theme pair from a single brand color
+    pub fn from_seed(primary: Rgba) -> Self {
+        Self::with_palettes(
+            ColorPalette::from_seed(primary, ThemeMode::Light),
+            ColorPalette::from_seed(primary, ThemeMode::Dark),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use gpui::{rgb, Rgba};
+
+    use super::{ColorPalette, ThemeMode};
+    use crate::utils::{contrast_ratio, to_oklch, WCAG_AA_LARGE, WCAG_AA_NORMAL};
+
+    const SEEDS: &[u32] = &[
+        0x696FC7, // default purple
+        0xFFD400, // bright yellow
+        0x00E5FF, // bright cyan
+        0x0B1F4D, // dark navy
+        0xE53935, // red
+        0x27AE60, // green
+        0x808080, // neutral gray
+        0xFFFFFF, // white
+        0x000000, // black
+    ];
+
+    fn palettes() -> Vec<(u32, ThemeMode, ColorPalette)> {
+        SEEDS
+            .iter()
+            .flat_map(|seed| {
+                [ThemeMode::Light, ThemeMode::Dark]
+                    .into_iter()
+                    .map(move |mode| (*seed, mode, ColorPalette::from_seed(rgb(*seed), mode)))
+            })
+            .collect()
+    }
+
+    fn assert_contrast(
+        name: &str,
+        seed: u32,
+        mode: ThemeMode,
+        foreground: Rgba,
+        background: Rgba,
+        min: f32,
+    ) {
+        let ratio = contrast_ratio(foreground, background);
+        assert!(
+            ratio >= min,
+            "{name}: {ratio:.2} < {min} for seed #{seed:06X} in {mode:?} mode",
+        );
+    }
+
+    #[test]
+    fn text_meets_wcag_aa_on_every_background() {
+        for (seed, mode, palette) in palettes() {
+            for background in [
+                palette.background,
+                palette.background_secondary,
+                palette.background_hover,
+            ] {
+                assert_contrast("text", seed, mode, palette.text, background, WCAG_AA_NORMAL);
+                assert_contrast(
+                    "text_secondary",
+                    seed,
+                    mode,
+                    palette.text_secondary,
+                    background,
+                    WCAG_AA_NORMAL,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn text_on_primary_meets_wcag_aa_in_every_state() {
+        for (seed, mode, palette) in palettes() {
+            for primary in [
+                palette.primary,
+                palette.primary_hover,
+                palette.primary_active,
+            ] {
+                assert_contrast(
+                    "text_on_primary",
+                    seed,
+                    mode,
+                    palette.text_on_primary,
+                    primary,
+                    WCAG_AA_NORMAL,
+                );
+            }
+            assert_contrast(
+                "selection_text",
+                seed,
+                mode,
+                palette.selection_text,
+                palette.selection,
+                WCAG_AA_NORMAL,
+            );
+        }
+    }
+
+    #[test]
+    fn ui_colors_meet_non_text_contrast() {
+        for (seed, mode, palette) in palettes() {
+            for (name, color) in [
+                ("border_focus", palette.border_focus),
+                ("secondary", palette.secondary),
+                ("success", palette.success),
+                ("warning", palette.warning),
+                ("error", palette.error),
+                ("info", palette.info),
+            ] {
+                assert_contrast(name, seed, mode, color, palette.background, WCAG_AA_LARGE);
+            }
+        }
+    }
+
+    #[test]
+    fn modes_have_matching_lightness() {
+        let light = ColorPalette::from_seed(rgb(0x696FC7), ThemeMode::Light);
+        let dark = ColorPalette::from_seed(rgb(0x696FC7), ThemeMode::Dark);
+        assert!(to_oklch(light.background).l > 0.9);
+        assert!(to_oklch(dark.background).l < 0.3);
+        assert!(to_oklch(light.text).l < to_oklch(light.background).l);
+        assert!(to_oklch(dark.text).l > to_oklch(dark.background).l);
+    }
+
+    #[test]
+    fn derived_shades_keep_the_seed_hue() {
+        let seed = to_oklch(rgb(0x696FC7));
+        let palette = ColorPalette::from_seed(rgb(0x696FC7), ThemeMode::Light);
+        for color in [
+            palette.primary,
+            palette.primary_hover,
+            palette.primary_active,
+        ] {
+            let hue = to_oklch(color).h;
+            assert!(
+                (hue - seed.h).abs() < 5.0,
+                "hue drifted from {} to {}",
+                seed.h,
+                hue
+            );
+        }
+    }
+
+    #[test]
+    fn from_seed_keeps_an_accessible_seed() {
+        let palette = ColorPalette::from_seed(rgb(0x3B5BDB), ThemeMode::Light);
+        assert_eq!(palette.primary, rgb(0x3B5BDB));
+        assert_eq!(palette.text_on_primary, rgb(0xFFFFFF));
+    }
+}
@@ -0,0 +1,283 @@
+// Design token scales stored on the theme
+
+use gpui::*;
+use serde::{Deserialize, Serialize};
+
+use super::{BorderRadius, ComponentSize, Spacing};
+
+/// Spacing scale, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SpacingScale {
+    pub xxxs: f32,
+    pub xxs: f32,
+    pub xs: f32,
+    pub sm: f32,
+    pub md: f32,
+    pub lg: f32,
+    pub xl: f32,
+    pub xxl: f32,
+    pub xxxl: f32,
+}
+
+impl Default for SpacingScale {
+    fn default() -> Self {
+        Self {
+            xxxs: Spacing::XXXS,
+            xxs: Spacing::XXS,
+            xs: Spacing::XS,
+            sm: Spacing::SM,
+            md: Spacing::MD,
+            lg: Spacing::LG,
+            xl: Spacing::XL,
+            xxl: Spacing::XXL,
+            xxxl: Spacing::XXXL,
+        }
+    }
+}
+
+/// Border radius scale, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RadiusScale {
+    pub none: f32,
+    pub sm: f32,
+    pub md: f32,
+    pub lg: f32,
+    pub xl: f32,
+    pub full: f32,
+}
+
+impl Default for RadiusScale {
+    fn default() -> Self {
+        Self {
+            none: BorderRadius::NONE,
+            sm: BorderRadius::SM,
+            md: BorderRadius::MD,
+            lg: BorderRadius::LG,
+            xl: BorderRadius::XL,
+            full: BorderRadius::FULL,
+        }
+    }
+}
+
+/// Metrics for a single component size, in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SizeMetrics {
+    pub height: f32,
+    pub font_size: f32,
+    pub padding_y: f32,
+    pub padding_x: f32,
+    pub min_width: f32,
+}
+
+impl SizeMetrics {
+    fn builtin(size: ComponentSize) -> Self {
+        let (padding_y, padding_x) = size.padding();
+        Self {
+            height: size.px(),
+            font_size: size.font_size().into(),
+            padding_y: padding_y.into(),
+            padding_x: padding_x.into(),
+            min_width: size.min_width().into(),
+        }
+    }
+}
+
+/// Metrics for every component size
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SizeScale {
+    pub xsmall: SizeMetrics,
+    pub small: SizeMetrics,
+    pub medium: SizeMetrics,
+    pub large: SizeMetrics,
+    pub xlarge: SizeMetrics,
+}
+
+impl Default for SizeScale {
+    fn default() -> Self {
+        Self {
+            xsmall: SizeMetrics::builtin(ComponentSize::XSmall),
+            small: SizeMetrics::builtin(ComponentSize::Small),
+            medium: SizeMetrics::builtin(ComponentSize::Medium),
+            large: SizeMetrics::builtin(ComponentSize::Large),
+            xlarge: SizeMetrics::builtin(ComponentSize::XLarge),
+        }
+    }
+}
+
+impl SizeScale {
+    /// Metrics for the given size
+    pub fn get(&self, size: ComponentSize) -> &SizeMetrics {
+        match size {
+            ComponentSize::XSmall => &self.xsmall,
+            ComponentSize::Small => &self.small,
+            ComponentSize::Medium => &self.medium,
+            ComponentSize::Large => &self.large,
+            ComponentSize::XLarge => &self.xlarge,
+        }
+    }
+}
+
+impl SpacingScale {
+    /// Tighter spacing for dense layouts
+    pub fn compact() -> Self {
+        Self {
+            xxxs: 1.0,
+            xxs: 2.0,
+            xs: 4.0,
+            sm: 6.0,
+            md: 8.0,
+            lg: 12.0,
+            xl: 16.0,
+            xxl: 20.0,
+            xxxl: 24.0,
+        }
+    }
+}
+
+impl SizeScale {
+    /// Smaller controls for dense layouts, each size one step down
+    pub fn compact() -> Self {
+        let builtin = Self::default();
+        Self {
+            xsmall: SizeMetrics {
+                height: 18.0,
+                font_size: 10.0,
+                padding_y: 2.0,
+                padding_x: 6.0,
+                min_width: 52.0,
+            },
+            small: builtin.xsmall,
+            medium: builtin.small,
+            large: builtin.medium,
+            xlarge: builtin.large,
+        }
+    }
+}
+
+/// Font families, weights and line heights
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Typography {
+    /// UI font family
+    pub font_family: String,
+    /// Monospace font family, used for code
+    pub mono_font_family: String,
+    pub font_weight: f32,
+    pub font_weight_medium: f32,
+    pub font_weight_semibold: f32,
+    pub font_weight_bold: f32,
+    /// Line height for single-line controls, in pixels
+    pub line_height_tight: f32,
+    /// Line height for body and multi-line text, in pixels
+    pub line_height: f32,
+    /// Line height for relaxed reading text, in pixels
+    pub line_height_relaxed: f32,
+}
+
+impl Default for Typography {
+    fn default() -> Self {
+        Self {
+            font_family: ".SystemUIFont".to_string(),
+            mono_font_family: "Menlo".to_string(),
+            font_weight: FontWeight::NORMAL.0,
+            font_weight_medium: FontWeight::MEDIUM.0,
+            font_weight_semibold: FontWeight::SEMIBOLD.0,
+            font_weight_bold: FontWeight::BOLD.0,
+            line_height_tight: 18.0,
+            line_height: 20.0,
+            line_height_relaxed: 24.0,
+        }
+    }
+}
+
+impl Typography {
+    /// The UI font
+    pub fn font(&self) -> Font {
+        Self::font_for(&self.font_family)
+    }
+
+    /// The monospace font
+    pub fn mono_font(&self) -> Font {
+        Self::font_for(&self.mono_font_family)
+    }
+
+    fn font_for(family: &str) -> Font {
+        Font {
+            family: family.to_string().into(),
+            features: Default::default(),
+            weight: Default::default(),
+            style: Default::default(),
+            fallbacks: None,
+        }
+    }
+}
+
+/// A single shadow layer
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ShadowLayer {
+    pub color: Rgba,
+    #[serde(default)]
+    pub offset_x: f32,
+    #[serde(default)]
+    pub offset_y: f32,
+    #[serde(default)]
+    pub blur: f32,
+    #[serde(default)]
+    pub spread: f32,
+}
+
+impl ShadowLayer {
+    /// Convert to a gpui box shadow
+    pub fn to_box_shadow(&self) -> BoxShadow {
+        BoxShadow {
+            color: self.color.into(),
+            offset: point(px(self.offset_x), px(self.offset_y)),
+            blur_radius: px(self.blur),
+            spread_radius: px(self.spread),
+        }
+    }
+}
+
+/// Shadow elevation levels
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ShadowScale {
+    /// Resting controls: inputs, secondary buttons, tabs
+    pub sm: Vec<ShadowLayer>,
+    /// Raised controls: primary buttons
+    pub md: Vec<ShadowLayer>,
+    /// Floating surfaces: dropdowns and popovers
+    pub lg: Vec<ShadowLayer>,
+}
+
+impl Default for ShadowScale {
+    fn default() -> Self {
+        let layer = |color: u32, offset_y: f32, blur: f32, spread: f32| ShadowLayer {
+            color: rgba(color),
+            offset_x: 0.0,
+            offset_y,
+            blur,
+            spread,
+        };
+        Self {
+            sm: vec![layer(0x0000000A, 1.0, 2.0, 0.0)],
+            md: vec![layer(0x00000018, 1.0, 2.0, 0.0)],
+            lg: vec![
+                layer(0x00000010, 4.0, 16.0, -2.0),
+                layer(0x00000008, 2.0, 8.0, 0.0),
+            ],
+        }
+    }
+}
+
+impl ShadowScale {
+    /// Convert a level to gpui box shadows
+    pub fn to_box_shadows(layers: &[ShadowLayer]) -> Vec<BoxShadow> {
+        layers.iter().map(ShadowLayer::to_box_shadow).collect()
+    }
+}
@@ -7,6 +7,22 @@ pub fn hex_to_rgba(hex: u32) -> Rgba {
     rgb(hex)
 }
 
+/// Parse a hex color string (`#RGB`, `#RGBA`, `#RRGGBB` or `#RRGGBBAA`)
+pub fn parse_hex(hex: &str) -> Option<Rgba> {
+    Rgba::try_from(hex).ok()
+}
+
+/// Format a color as `#RRGGBB`, or `#RRGGBBAA` when it is not fully opaque
+pub fn to_hex(color: Rgba) -> String {
+    let channel = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+    let (r, g, b, a) = (channel(color.r), channel(color.g), channel(color.b), channel(color.a));
+    if a == 0xFF {
+        format!("#{:02X}{:02X}{:02X}", r, g, b)
+    } else {
+        format!("#{:02X}{:02X}{:02X}{:02X}", r, g, b, a)
+    }
+}
+
 /// Lighten a color by a percentage (0.0 - 1.0)
 pub fn lighten(color: Rgba, amount: f32) -> Rgba {
     let amount = amount.clamp(0.0, 1.0);
@@ -47,3 +63,225 @@ pub fn mix(color1: Rgba, color2: Rgba, weight: f32) -> Rgba {
         a: color1.a * weight + color2.a * (1.0 - weight),
     }
 }
+
+// ============================================================================
+// HSL
+// ============================================================================
+
+/// Convert a color to HSL (all components in 0.0 - 1.0)
+pub fn to_hsl(color: Rgba) -> Hsla {
+    Hsla::from(color)
+}
+
+/// Convert an HSL color back to Rgba
+pub fn from_hsl(color: Hsla) -> Rgba {
+    Rgba::from(color)
+}
+
+// ============================================================================
+// OKLCH
+// ============================================================================
+
+/// A color in the OKLCH space: perceptual lightness, chroma and hue
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Oklch {
+    /// Perceptual lightness, 0.0 (black) - 1.0 (white)
+    pub l: f32,
+    /// Chroma, 0.0 (gray) - roughly 0.37 for the most saturated sRGB colors
+    pub c: f32,
+    /// Hue angle in degrees, 0.0 - 360.0
+    pub h: f32,
+    /// Alpha, 0.0 - 1.0
+    pub a: f32,
+}
+
+fn srgb_to_linear(value: f32) -> f32 {
+    if value <= 0.04045 {
+        value / 12.92
+    } else {
+        ((value + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> f32 {
+    if value <= 0.003_130_8 {
+        value * 12.92
+    } else {
+        1.055 * value.powf(1.0 / 2.4) - 0.055
+    }
+}
+
+/// Convert a color to OKLCH
+pub fn to_oklch(color: Rgba) -> Oklch {
+    let r = srgb_to_linear(color.r);
+    let g = srgb_to_linear(color.g);
+    let b = srgb_to_linear(color.b);
+
+    let l = (0.412_221_46 * r + 0.536_332_55 * g + 0.051_445_995 * b).cbrt();
+    let m = (0.211_903_5 * r + 0.680_699_5 * g + 0.107_396_96 * b).cbrt();
+    let s = (0.088_302_46 * r + 0.281_718_85 * g + 0.629_978_7 * b).cbrt();
+
+    let lightness = 0.210_454_26 * l + 0.793_617_8 * m - 0.004_072_047 * s;
+    let ok_a = 1.977_998_5 * l - 2.428_592_2 * m + 0.450_593_7 * s;
+    let ok_b = 0.025_904_037 * l + 0.782_771_77 * m - 0.808_675_77 * s;
+
+    let chroma = (ok_a * ok_a + ok_b * ok_b).sqrt();
+    let hue = if chroma < 1e-4 {
+        0.0
+    } else {
+        ok_b.atan2(ok_a).to_degrees().rem_euclid(360.0)
+    };
+
+    Oklch {
+        l: lightness,
+        c: chroma,
+        h: hue,
+        a: color.a,
+    }
+}
+
+/// Convert OKLCH to linear-light RGB without clamping
+fn oklch_to_linear(color: Oklch) -> (f32, f32, f32) {
+    let (sin, cos) = color.h.to_radians().sin_cos();
+    let ok_a = color.c * cos;
+    let ok_b = color.c * sin;
+
+    let l = (color.l + 0.396_337_78 * ok_a + 0.215_803_76 * ok_b).powi(3);
+    let m = (color.l - 0.105_561_35 * ok_a - 0.063_854_17 * ok_b).powi(3);
+    let s = (color.l - 0.089_484_18 * ok_a - 1.291_485_5 * ok_b).powi(3);
+
+    (
+        4.076_741_7 * l - 3.307_711_6 * m + 0.230_969_94 * s,
+        -1.268_438 * l + 2.609_757_4 * m - 0.341_319_38 * s,
+        -0.004_196_086_3 * l - 0.703_418_6 * m + 1.707_614_7 * s,
+    )
+}
+
+fn in_gamut((r, g, b): (f32, f32, f32)) -> bool {
+    const EPSILON: f32 = 1e-4;
+    [r, g, b].iter().all(|v| (-EPSILON..=1.0 + EPSILON).contains(v))
+}
+
+/// Convert an OKLCH color back to Rgba.
+///
+/// Colors outside the sRGB gamut keep their lightness and hue, and lose
+/// chroma until they fit.
+pub fn from_oklch(color: Oklch) -> Rgba {
+    let color = Oklch {
+        l: color.l.clamp(0.0, 1.0),
+        c: color.c.max(0.0),
+        ..color
+    };
+
+    let mut linear = oklch_to_linear(color);
+    if !in_gamut(linear) {
+        let (mut low, mut high) = (0.0, color.c);
+        for _ in 0..24 {
+            let mid = (low + high) / 2.0;
+            if in_gamut(oklch_to_linear(Oklch { c: mid, ..color })) {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        linear = oklch_to_linear(Oklch { c: low, ..color });
+    }
+
+    let (r, g, b) = linear;
+    Rgba {
+        r: linear_to_srgb(r.clamp(0.0, 1.0)),
+        g: linear_to_srgb(g.clamp(0.0, 1.0)),
+        b: linear_to_srgb(b.clamp(0.0, 1.0)),
+        a: color.a,
+    }
+}
+
+/// Lighten a color perceptually by raising its OKLCH lightness (0.0 - 1.0).
+///
+/// Unlike [`lighten`], hue and chroma are preserved, so brand colors do not
+/// wash out toward gray.
+pub fn lighten_perceptual(color: Rgba, amount: f32) -> Rgba {
+    let oklch = to_oklch(color);
+    from_oklch(Oklch {
+        l: (oklch.l + amount).clamp(0.0, 1.0),
+        ..oklch
+    })
+}
+
+/// Darken a color perceptually by lowering its OKLCH lightness (0.0 - 1.0)
+pub fn darken_perceptual(color: Rgba, amount: f32) -> Rgba {
+    lighten_perceptual(color, -amount)
+}
+
+// ============================================================================
+// Contrast
+// ============================================================================
+
+/// Minimum WCAG AA contrast ratio for normal text
+pub const WCAG_AA_NORMAL: f32 = 4.5;
+/// Minimum WCAG AA contrast ratio for large text and UI components
+pub const WCAG_AA_LARGE: f32 = 3.0;
+/// Minimum WCAG AAA contrast ratio for normal text
+pub const WCAG_AAA_NORMAL: f32 = 7.0;
+
+/// WCAG relative luminance of a color (alpha is ignored)
+pub fn relative_luminance(color: Rgba) -> f32 {
+    0.2126 * srgb_to_linear(color.r) + 0.7152 * srgb_to_linear(color.g) + 0.0722 * srgb_to_linear(color.b)
+}
+
+/// WCAG contrast ratio between two colors, from 1.0 to 21.0
+///
+/// ```
+/// use fluix::utils::contrast_ratio;
+/// use gpui::rgb;
+///
+/// let ratio = contrast_ratio(rgb(0x000000), rgb(0xFFFFFF));
+/// assert!((ratio - 21.0).abs() < 0.01);
+/// ```
+pub fn contrast_ratio(color1: Rgba, color2: Rgba) -> f32 {
+    let l1 = relative_luminance(color1);
+    let l2 = relative_luminance(color2);
+    let (lighter, darker) = if l1 > l2 { (l1, l2) } else { (l2, l1) };
+    (lighter + 0.05) / (darker + 0.05)
+}
+
+/// Whether text in `foreground` on `background` meets WCAG AA for normal text
+pub fn meets_wcag_aa(foreground: Rgba, background: Rgba) -> bool {
+    contrast_ratio(foreground, background) >= WCAG_AA_NORMAL
+}
+
+/// Pick black or white, whichever reads better on `background`
+///
+/// ```
+/// use fluix::utils::readable_foreground;
+/// use gpui::rgb;
+///
+/// assert_eq!(readable_foreground(rgb(0x1E1E1E)), rgb(0xFFFFFF));
+/// assert_eq!(readable_foreground(rgb(0xF5F5F5)), rgb(0x000000));
+/// ```
+pub fn readable_foreground(background: Rgba) -> Rgba {
+    let white = rgb(0xFFFFFF);
+    let black = rgb(0x000000);
+    if contrast_ratio(white, background) >= contrast_ratio(black, background) {
+        white
+    } else {
+        black
+    }
+}
+
+/// Pick the first candidate that meets `min_ratio` against `background`,
+/// or the highest-contrast candidate if none does.
+///
+/// Falls back to [`readable_foreground`] when `candidates` is empty.
+pub fn pick_readable(background: Rgba, candidates: &[Rgba], min_ratio: f32) -> Rgba {
+    candidates
+        .iter()
+        .copied()
+        .find(|candidate| contrast_ratio(*candidate, background) >= min_ratio)
+        .or_else(|| {
+            candidates.iter().copied().max_by(|a, b| {
+                contrast_ratio(*a, background).total_cmp(&contrast_ratio(*b, background))
+            })
+        })
+        .unwrap_or_else(|| readable_foreground(background))
+}
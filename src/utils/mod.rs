@@ -1,5 +1,7 @@
 // Utility functions and helpers
 
 pub mod colors;
+pub mod text;
 
 pub use colors::*;
+pub use text::*;
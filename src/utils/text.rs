@@ -0,0 +1,267 @@
+// Text segmentation helpers for editors
+//
+// All offsets are byte offsets into the text. Cursor movement and deletion
+// work on extended grapheme clusters (UAX #29), so an emoji ZWJ sequence,
+// a flag or a base letter with combining marks is edited as one character.
+
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Whether a character is punctuation that word motion should skip over
+fn is_punctuation(c: char) -> bool {
+    c.is_ascii_punctuation()
+        || matches!(c,
+            '\u{2000}'..='\u{206F}' | // General Punctuation
+            '\u{3000}'..='\u{303F}' | // CJK Symbols and Punctuation
+            '\u{FF01}'..='\u{FF0F}' | // Fullwidth punctuation
+            '\u{FF1A}'..='\u{FF20}' |
+            '\u{FF3B}'..='\u{FF40}' |
+            '\u{FF5B}'..='\u{FF65}'
+        )
+}
+
+/// Whether a word-boundary segment is a word (not whitespace or punctuation)
+fn is_word(segment: &str) -> bool {
+    segment
+        .chars()
+        .any(|c| !c.is_whitespace() && !is_punctuation(c))
+}
+
+/// Start of the word before `offset`, skipping whitespace and punctuation.
+///
+/// Uses Unicode word boundaries (UAX #29), so each CJK ideograph and each
+/// emoji sequence counts as its own word.
+///
+/// ```
+/// use fluix::utils::previous_word_start;
+///
+/// assert_eq!(previous_word_start("hello world", 11), 6);
+/// assert_eq!(previous_word_start("hello world", 6), 0);
+/// ```
+pub fn previous_word_start(text: &str, offset: usize) -> usize {
+    text.split_word_bound_indices()
+        .rev()
+        .find(|(start, segment)| *start < offset && is_word(segment))
+        .map_or(0, |(start, _)| start)
+}
+
+/// End of the word after `offset`, skipping whitespace and punctuation.
+///
+/// ```
+/// use fluix::utils::next_word_end;
+///
+/// assert_eq!(next_word_end("hello world", 0), 5);
+/// assert_eq!(next_word_end("hello world", 5), 11);
+/// ```
+pub fn next_word_end(text: &str, offset: usize) -> usize {
+    text.split_word_bound_indices()
+        .map(|(start, segment)| (start + segment.len(), segment))
+        .find(|(end, segment)| *end > offset && is_word(segment))
+        .map_or(text.len(), |(end, _)| end)
+}
+
+/// Range of the word-boundary segment at `offset`, for double-click selection.
+///
+/// Prefers the word to the left when `offset` sits between a word and
+/// whitespace or punctuation.
+///
+/// ```
+/// use fluix::utils::word_range_at;
+///
+/// assert_eq!(word_range_at("hello world", 2), 0..5);
+/// assert_eq!(word_range_at("hello world", 5), 0..5);
+/// ```
+pub fn word_range_at(text: &str, offset: usize) -> Range<usize> {
+    let mut previous = None;
+    for (start, segment) in text.split_word_bound_indices() {
+        let range = start..start + segment.len();
+        if range.contains(&offset) {
+            // At the start of a non-word, prefer the word just before it
+            if offset == start && !is_word(segment) {
+                if let Some((previous, true)) = previous {
+                    return previous;
+                }
+            }
+            return range;
+        }
+        previous = Some((range, is_word(segment)));
+    }
+    previous.map_or(offset..offset, |(range, _)| range)
+}
+
+/// Start of the grapheme cluster before `offset`.
+///
+/// ```
+/// use fluix::utils::previous_grapheme_boundary;
+///
+/// // "e" followed by a combining acute accent is a single cluster
+/// assert_eq!(previous_grapheme_boundary("ae\u{301}", 4), 1);
+/// ```
+pub fn previous_grapheme_boundary(text: &str, offset: usize) -> usize {
+    text.grapheme_indices(true)
+        .rev()
+        .map(|(start, _)| start)
+        .find(|start| *start < offset)
+        .unwrap_or(0)
+}
+
+/// End of the grapheme cluster after `offset`.
+///
+/// ```
+/// use fluix::utils::next_grapheme_boundary;
+///
+/// assert_eq!(next_grapheme_boundary("ae\u{301}", 1), 4);
+/// ```
+pub fn next_grapheme_boundary(text: &str, offset: usize) -> usize {
+    text.grapheme_indices(true)
+        .map(|(start, grapheme)| start + grapheme.len())
+        .find(|end| *end > offset)
+        .unwrap_or(text.len())
+}
+
+/// Snap `offset` back to the start of the grapheme cluster containing it.
+///
+/// Offsets already on a cluster boundary are returned unchanged.
+pub fn floor_grapheme_boundary(text: &str, offset: usize) -> usize {
+    if offset >= text.len() {
+        return text.len();
+    }
+    text.grapheme_indices(true)
+        .rev()
+        .map(|(start, _)| start)
+        .find(|start| *start <= offset)
+        .unwrap_or(0)
+}
+
+/// Number of user-perceived characters (extended grapheme clusters).
+///
+/// ```
+/// use fluix::utils::grapheme_count;
+///
+/// assert_eq!(grapheme_count("🇯🇵🇰🇷"), 2);
+/// ```
+pub fn grapheme_count(text: &str) -> usize {
+    text.graphemes(true).count()
+}
+
+/// Number of UTF-16 code units before the byte `offset`, as used by
+/// platform input methods.
+///
+/// ```
+/// use fluix::utils::offset_to_utf16;
+///
+/// assert_eq!(offset_to_utf16("a😀b", 5), 3);
+/// ```
+pub fn offset_to_utf16(text: &str, offset: usize) -> usize {
+    text[..offset.min(text.len())].encode_utf16().count()
+}
+
+/// Byte offset of the character at UTF-16 code unit `offset_utf16`.
+///
+/// Offsets inside a surrogate pair round up to the end of that character.
+///
+/// ```
+/// use fluix::utils::offset_from_utf16;
+///
+/// assert_eq!(offset_from_utf16("a😀b", 3), 5);
+/// ```
+pub fn offset_from_utf16(text: &str, offset_utf16: usize) -> usize {
+    let mut utf16_count = 0;
+    for (idx, c) in text.char_indices() {
+        if utf16_count >= offset_utf16 {
+            return idx;
+        }
+        utf16_count += c.len_utf16();
+    }
+    text.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        floor_grapheme_boundary, grapheme_count, next_grapheme_boundary, previous_grapheme_boundary,
+    };
+
+    /// Every cluster boundary reached by stepping forward, then backward
+    fn walk(text: &str) -> (Vec<usize>, Vec<usize>) {
+        let mut forward = vec![0];
+        while *forward.last().unwrap() < text.len() {
+            forward.push(next_grapheme_boundary(text, *forward.last().unwrap()));
+        }
+        let mut backward = vec![text.len()];
+        while *backward.last().unwrap() > 0 {
+            backward.push(previous_grapheme_boundary(text, *backward.last().unwrap()));
+        }
+        backward.reverse();
+        (forward, backward)
+    }
+
+    fn assert_clusters(text: &str, clusters: &[&str]) {
+        let mut expected = vec![0];
+        for cluster in clusters {
+            expected.push(expected.last().unwrap() + cluster.len());
+        }
+        assert_eq!(clusters.concat(), text);
+
+        let (forward, backward) = walk(text);
+        assert_eq!(forward, expected, "forward boundaries of {:?}", text);
+        assert_eq!(backward, expected, "backward boundaries of {:?}", text);
+        assert_eq!(grapheme_count(text), clusters.len());
+
+        // Offsets inside a cluster snap back to its start
+        for window in expected.windows(2) {
+            for offset in window[0]..window[1] {
+                if text.is_char_boundary(offset) {
+                    assert_eq!(floor_grapheme_boundary(text, offset), window[0]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn emoji_sequences() {
+        // ZWJ family, skin tone modifier, keycap and variation selector
+        assert_clusters("a👨‍👩‍👧‍👦b👍🏽1️⃣❤️", &["a", "👨‍👩‍👧‍👦", "b", "👍🏽", "1️⃣", "❤️"]);
+    }
+
+    #[test]
+    fn flags() {
+        assert_clusters("🇯🇵🇰🇷🇺🇸", &["🇯🇵", "🇰🇷", "🇺🇸"]);
+        // Subdivision flag (England) uses tag characters
+        let england = "🏴\u{E0067}\u{E0062}\u{E0065}\u{E006E}\u{E0067}\u{E007F}";
+        assert_clusters(&format!("{england}!"), &[england, "!"]);
+    }
+
+    #[test]
+    fn combining_marks() {
+        assert_clusters("ae\u{301}o\u{308}\u{304}", &["a", "e\u{301}", "o\u{308}\u{304}"]);
+    }
+
+    #[test]
+    fn devanagari() {
+        // "नमस्ते": the virama joins स and त into a conjunct
+        assert_clusters("नमस्ते", &["न", "म", "स्ते"]);
+        // Vowel signs attach to their consonant
+        assert_clusters("हिंदी", &["हिं", "दी"]);
+    }
+
+    #[test]
+    fn hangul() {
+        // Precomposed syllables
+        assert_clusters("한국어", &["한", "국", "어"]);
+        // Conjoining jamo (L V T) form a single syllable
+        assert_clusters(
+            "\u{1112}\u{1161}\u{11AB}\u{1100}\u{116E}",
+            &["\u{1112}\u{1161}\u{11AB}", "\u{1100}\u{116E}"],
+        );
+    }
+
+    #[test]
+    fn out_of_range_offsets() {
+        assert_eq!(previous_grapheme_boundary("", 0), 0);
+        assert_eq!(next_grapheme_boundary("", 0), 0);
+        assert_eq!(next_grapheme_boundary("ab", 2), 2);
+        assert_eq!(floor_grapheme_boundary("ab", 5), 2);
+    }
+}